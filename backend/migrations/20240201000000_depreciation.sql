@@ -0,0 +1,8 @@
+-- Depreciation inputs for US tax reporting
+
+-- Land is not depreciable, so track how much of the purchase price it accounts for
+ALTER TABLE properties ADD COLUMN land_value REAL;
+ALTER TABLE properties ADD COLUMN placed_in_service_date TIMESTAMP;
+
+-- Capital improvements are depreciated separately from repairs
+ALTER TABLE maintenance_records ADD COLUMN capital_improvement BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,32 @@
+-- Contractors and vendors we pay, tracked for 1099 reporting
+CREATE TABLE IF NOT EXISTS contractors (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    entity_type TEXT NOT NULL DEFAULT 'individual', -- individual, llc, partnership, corporation
+    tax_id TEXT,
+    address TEXT,
+    city TEXT,
+    state TEXT,
+    zip_code TEXT,
+    email TEXT,
+    phone TEXT,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE maintenance_records ADD COLUMN contractor_id INTEGER REFERENCES contractors(id) ON DELETE SET NULL;
+ALTER TABLE expenses ADD COLUMN contractor_id INTEGER REFERENCES contractors(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_contractors_name ON contractors(name);
+CREATE INDEX IF NOT EXISTS idx_maintenance_contractor_id ON maintenance_records(contractor_id);
+CREATE INDEX IF NOT EXISTS idx_expenses_contractor_id ON expenses(contractor_id);
+
+-- Promote the free-text contractor names already on maintenance records
+INSERT INTO contractors (name)
+SELECT DISTINCT TRIM(contractor) FROM maintenance_records
+WHERE contractor IS NOT NULL AND TRIM(contractor) != '';
+
+UPDATE maintenance_records
+SET contractor_id = (SELECT id FROM contractors WHERE contractors.name = TRIM(maintenance_records.contractor))
+WHERE contractor IS NOT NULL;
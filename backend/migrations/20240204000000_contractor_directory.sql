@@ -0,0 +1,9 @@
+-- Directory details for picking and vetting vendors
+ALTER TABLE contractors ADD COLUMN trade TEXT; -- plumbing, electrical, hvac, roofing, painting, general
+ALTER TABLE contractors ADD COLUMN contact_name TEXT;
+ALTER TABLE contractors ADD COLUMN license_number TEXT;
+ALTER TABLE contractors ADD COLUMN license_expiry TIMESTAMP;
+ALTER TABLE contractors ADD COLUMN insurance_expiry TIMESTAMP;
+ALTER TABLE contractors ADD COLUMN hourly_rate REAL;
+
+CREATE INDEX IF NOT EXISTS idx_contractors_trade ON contractors(trade);
@@ -0,0 +1,23 @@
+-- Audit trail for work-order status transitions
+CREATE TABLE IF NOT EXISTS maintenance_status_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    maintenance_id INTEGER NOT NULL,
+    from_status TEXT, -- NULL when the record was created
+    to_status TEXT NOT NULL,
+    changed_by TEXT,
+    reason TEXT,
+    changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (maintenance_id) REFERENCES maintenance_records(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_maintenance_history_maintenance_id ON maintenance_status_history(maintenance_id);
+
+-- Completed records must carry a completion date
+UPDATE maintenance_records
+SET completed_date = COALESCE(scheduled_date, updated_at)
+WHERE status = 'completed' AND completed_date IS NULL;
+
+-- Seed history from the current state of existing records
+INSERT INTO maintenance_status_history (maintenance_id, from_status, to_status, changed_by, reason, changed_at)
+SELECT id, NULL, status, 'system', 'Imported existing record', created_at
+FROM maintenance_records;
@@ -0,0 +1,16 @@
+-- Response and resolution targets per maintenance priority
+CREATE TABLE IF NOT EXISTS maintenance_sla_targets (
+    priority TEXT PRIMARY KEY, -- low, medium, high, urgent
+    response_hours REAL NOT NULL,
+    resolution_hours REAL NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO maintenance_sla_targets (priority, response_hours, resolution_hours)
+VALUES
+    ('urgent', 4, 24),
+    ('high', 24, 72),
+    ('medium', 72, 168),
+    ('low', 168, 720);
+
+CREATE INDEX IF NOT EXISTS idx_maintenance_created_at ON maintenance_records(created_at);
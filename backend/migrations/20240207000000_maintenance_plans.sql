@@ -0,0 +1,37 @@
+-- Recurring preventive maintenance that generates work orders ahead of each due date
+CREATE TABLE IF NOT EXISTS maintenance_plans (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    property_id INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    priority TEXT NOT NULL DEFAULT 'low',
+    contractor_id INTEGER,
+    estimated_cost REAL,
+    interval_value INTEGER NOT NULL,
+    interval_unit TEXT NOT NULL, -- days, weeks, months, years
+    lead_days INTEGER NOT NULL DEFAULT 14,
+    next_due_date TIMESTAMP NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT 1,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (property_id) REFERENCES properties(id) ON DELETE CASCADE,
+    FOREIGN KEY (contractor_id) REFERENCES contractors(id) ON DELETE SET NULL
+);
+
+-- One row per generated due date; the unique key keeps repeated runs idempotent
+CREATE TABLE IF NOT EXISTS maintenance_plan_occurrences (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    plan_id INTEGER NOT NULL,
+    due_date TIMESTAMP NOT NULL,
+    maintenance_id INTEGER,
+    event_id INTEGER,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (plan_id, due_date),
+    FOREIGN KEY (plan_id) REFERENCES maintenance_plans(id) ON DELETE CASCADE,
+    FOREIGN KEY (maintenance_id) REFERENCES maintenance_records(id) ON DELETE SET NULL,
+    FOREIGN KEY (event_id) REFERENCES calendar_events(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_maintenance_plans_property_id ON maintenance_plans(property_id);
+CREATE INDEX IF NOT EXISTS idx_maintenance_plans_next_due_date ON maintenance_plans(next_due_date);
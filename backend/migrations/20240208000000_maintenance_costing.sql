@@ -0,0 +1,43 @@
+-- Estimate vs actual costing for maintenance work orders
+ALTER TABLE maintenance_records ADD COLUMN estimated_cost REAL;
+
+-- Charges posted to a tenant's ledger (rent, fees, charge-backs); credits are negative
+CREATE TABLE IF NOT EXISTS tenant_charges (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tenant_id INTEGER NOT NULL,
+    property_id INTEGER NOT NULL,
+    charge_type TEXT NOT NULL, -- rent, late_fee, maintenance, utility, credit, other
+    amount REAL NOT NULL,
+    charge_date TIMESTAMP NOT NULL,
+    description TEXT,
+    maintenance_id INTEGER,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE,
+    FOREIGN KEY (property_id) REFERENCES properties(id) ON DELETE CASCADE,
+    FOREIGN KEY (maintenance_id) REFERENCES maintenance_records(id) ON DELETE SET NULL
+);
+
+-- Labor, materials and fees making up an estimate or the actual bill
+CREATE TABLE IF NOT EXISTS maintenance_line_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    maintenance_id INTEGER NOT NULL,
+    kind TEXT NOT NULL DEFAULT 'actual', -- estimate, actual
+    item_type TEXT NOT NULL, -- labor, materials, fee
+    description TEXT NOT NULL,
+    quantity REAL NOT NULL DEFAULT 1,
+    unit_cost REAL NOT NULL,
+    amount REAL NOT NULL,
+    invoice_number TEXT,
+    tenant_chargeable BOOLEAN NOT NULL DEFAULT 0,
+    tenant_charge_id INTEGER,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (maintenance_id) REFERENCES maintenance_records(id) ON DELETE CASCADE,
+    FOREIGN KEY (tenant_charge_id) REFERENCES tenant_charges(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_line_items_maintenance_id ON maintenance_line_items(maintenance_id);
+CREATE INDEX IF NOT EXISTS idx_tenant_charges_tenant_id ON tenant_charges(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_tenant_charges_charge_date ON tenant_charges(charge_date);
+
+-- Existing costs become the estimate for anything not yet completed
+UPDATE maintenance_records SET estimated_cost = cost WHERE status != 'completed' AND cost IS NOT NULL;
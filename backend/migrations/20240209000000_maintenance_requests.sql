@@ -0,0 +1,39 @@
+-- Per-tenant link token for the tenant portal
+ALTER TABLE tenants ADD COLUMN portal_token TEXT;
+UPDATE tenants SET portal_token = lower(hex(randomblob(24))) WHERE portal_token IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tenants_portal_token ON tenants(portal_token);
+
+-- Requests submitted by tenants, triaged by staff into work orders
+CREATE TABLE IF NOT EXISTS maintenance_requests (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tenant_id INTEGER NOT NULL,
+    property_id INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    urgency TEXT NOT NULL DEFAULT 'medium', -- low, medium, high, urgent (as reported by the tenant)
+    permission_to_enter BOOLEAN NOT NULL DEFAULT 0,
+    entry_notes TEXT, -- pets, alarm codes, etc.
+    status TEXT NOT NULL DEFAULT 'submitted', -- submitted, converted, declined
+    maintenance_id INTEGER,
+    triaged_by TEXT,
+    triage_notes TEXT,
+    triaged_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE,
+    FOREIGN KEY (property_id) REFERENCES properties(id) ON DELETE CASCADE,
+    FOREIGN KEY (maintenance_id) REFERENCES maintenance_records(id) ON DELETE SET NULL
+);
+
+-- Times the tenant prefers staff or contractors to come by
+CREATE TABLE IF NOT EXISTS maintenance_request_access_windows (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    request_id INTEGER NOT NULL,
+    starts_at TIMESTAMP NOT NULL,
+    ends_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (request_id) REFERENCES maintenance_requests(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_maintenance_requests_status ON maintenance_requests(status);
+CREATE INDEX IF NOT EXISTS idx_maintenance_requests_tenant ON maintenance_requests(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_request_access_windows_request ON maintenance_request_access_windows(request_id);
@@ -0,0 +1,38 @@
+-- Files attached to properties, tenants and maintenance records. The blob
+-- itself lives in attachment storage, keyed by its SHA-256 digest.
+CREATE TABLE IF NOT EXISTS attachments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT NOT NULL, -- property, tenant, maintenance
+    entity_id INTEGER NOT NULL,
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    sha256 TEXT NOT NULL,
+    category TEXT NOT NULL DEFAULT 'other', -- photo, lease, invoice, inspection, other
+    description TEXT,
+    uploaded_by TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (entity_type, entity_id, sha256)
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_entity ON attachments(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_attachments_sha256 ON attachments(sha256);
+
+-- attachments can't carry a foreign key to three tables, so clean up here
+CREATE TRIGGER IF NOT EXISTS trg_properties_delete_attachments
+AFTER DELETE ON properties
+BEGIN
+    DELETE FROM attachments WHERE entity_type = 'property' AND entity_id = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_tenants_delete_attachments
+AFTER DELETE ON tenants
+BEGIN
+    DELETE FROM attachments WHERE entity_type = 'tenant' AND entity_id = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_maintenance_delete_attachments
+AFTER DELETE ON maintenance_records
+BEGIN
+    DELETE FROM attachments WHERE entity_type = 'maintenance' AND entity_id = OLD.id;
+END;
@@ -0,0 +1,19 @@
+-- Resized, web-optimized copies of uploaded photos
+CREATE TABLE IF NOT EXISTS attachment_variants (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    attachment_id INTEGER NOT NULL,
+    variant TEXT NOT NULL, -- thumb, web
+    content_type TEXT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    sha256 TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (attachment_id, variant),
+    FOREIGN KEY (attachment_id) REFERENCES attachments(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachment_variants_sha256 ON attachment_variants(sha256);
+
+-- Photo shown for the property in listings
+ALTER TABLE properties ADD COLUMN cover_attachment_id INTEGER REFERENCES attachments(id) ON DELETE SET NULL;
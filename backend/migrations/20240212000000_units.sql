@@ -0,0 +1,43 @@
+-- Rentable units within a property. Single-family properties have one unit.
+CREATE TABLE IF NOT EXISTS units (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    property_id INTEGER NOT NULL,
+    unit_number TEXT NOT NULL,
+    bedrooms INTEGER,
+    bathrooms REAL,
+    square_feet INTEGER,
+    market_rent REAL,
+    status TEXT NOT NULL DEFAULT 'vacant', -- occupied, vacant, maintenance
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (property_id, unit_number),
+    FOREIGN KEY (property_id) REFERENCES properties(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_units_property_id ON units(property_id);
+
+-- Every existing property becomes a building with a single unit
+INSERT INTO units (property_id, unit_number, bedrooms, bathrooms, square_feet, market_rent, status)
+SELECT id, '1', bedrooms, bathrooms, square_feet, monthly_rent, status
+FROM properties;
+
+ALTER TABLE tenants ADD COLUMN unit_id INTEGER REFERENCES units(id) ON DELETE SET NULL;
+ALTER TABLE maintenance_records ADD COLUMN unit_id INTEGER REFERENCES units(id) ON DELETE SET NULL;
+ALTER TABLE calendar_events ADD COLUMN unit_id INTEGER REFERENCES units(id) ON DELETE SET NULL;
+ALTER TABLE maintenance_requests ADD COLUMN unit_id INTEGER REFERENCES units(id) ON DELETE SET NULL;
+
+UPDATE tenants SET unit_id = (SELECT id FROM units WHERE units.property_id = tenants.property_id);
+UPDATE maintenance_records SET unit_id = (SELECT id FROM units WHERE units.property_id = maintenance_records.property_id);
+UPDATE calendar_events SET unit_id = (SELECT id FROM units WHERE units.property_id = calendar_events.property_id)
+WHERE property_id IS NOT NULL;
+UPDATE maintenance_requests SET unit_id = (SELECT unit_id FROM tenants WHERE tenants.id = maintenance_requests.tenant_id);
+
+CREATE INDEX IF NOT EXISTS idx_tenants_unit_id ON tenants(unit_id);
+CREATE INDEX IF NOT EXISTS idx_maintenance_unit_id ON maintenance_records(unit_id);
+CREATE INDEX IF NOT EXISTS idx_events_unit_id ON calendar_events(unit_id);
+
+-- The original seed stored lease dates as bare dates, which don't decode as
+-- timestamps; normalise them
+UPDATE tenants SET lease_start = datetime(lease_start) WHERE length(lease_start) = 10;
+UPDATE tenants SET lease_end = datetime(lease_end) WHERE length(lease_end) = 10;
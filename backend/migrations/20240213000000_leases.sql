@@ -0,0 +1,46 @@
+-- Leases are separate from the people on them: one lease can have several
+-- occupants, and a unit keeps its full lease history.
+CREATE TABLE IF NOT EXISTS leases (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    property_id INTEGER NOT NULL,
+    unit_id INTEGER,
+    lease_type TEXT NOT NULL DEFAULT 'fixed', -- fixed, month_to_month
+    start_date TIMESTAMP NOT NULL,
+    end_date TIMESTAMP, -- open-ended for month-to-month
+    monthly_rent REAL NOT NULL,
+    deposit_amount REAL,
+    status TEXT NOT NULL DEFAULT 'active', -- pending, active, ended
+    previous_lease_id INTEGER, -- the lease this one renewed
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (property_id) REFERENCES properties(id) ON DELETE CASCADE,
+    FOREIGN KEY (unit_id) REFERENCES units(id) ON DELETE SET NULL,
+    FOREIGN KEY (previous_lease_id) REFERENCES leases(id) ON DELETE SET NULL
+);
+
+CREATE TABLE IF NOT EXISTS lease_occupants (
+    lease_id INTEGER NOT NULL,
+    tenant_id INTEGER NOT NULL,
+    role TEXT NOT NULL DEFAULT 'primary', -- primary, co_tenant, occupant
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (lease_id, tenant_id),
+    FOREIGN KEY (lease_id) REFERENCES leases(id) ON DELETE CASCADE,
+    FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_leases_property_id ON leases(property_id);
+CREATE INDEX IF NOT EXISTS idx_leases_unit_id ON leases(unit_id);
+CREATE INDEX IF NOT EXISTS idx_leases_status ON leases(status);
+CREATE INDEX IF NOT EXISTS idx_lease_occupants_tenant_id ON lease_occupants(tenant_id);
+
+-- Each existing tenant row becomes a fixed-term lease with the same id and
+-- that tenant as its primary occupant
+INSERT INTO leases (id, property_id, unit_id, lease_type, start_date, end_date, monthly_rent, deposit_amount, status, created_at)
+SELECT id, property_id, unit_id, 'fixed', lease_start, lease_end, monthly_rent, deposit_amount,
+       CASE status WHEN 'active' THEN 'active' WHEN 'pending' THEN 'pending' ELSE 'ended' END,
+       created_at
+FROM tenants;
+
+INSERT INTO lease_occupants (lease_id, tenant_id, role)
+SELECT id, id, 'primary' FROM tenants;
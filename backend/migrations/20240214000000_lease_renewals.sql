@@ -0,0 +1,26 @@
+-- Renewal negotiation for a lease nearing its end. Accepting an offer creates
+-- the next lease period (linked through leases.previous_lease_id) while the
+-- current lease stays on record.
+CREATE TABLE IF NOT EXISTS lease_renewals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    lease_id INTEGER NOT NULL UNIQUE,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending, offered, accepted, declined, signed
+    proposed_rent REAL,
+    proposed_lease_type TEXT, -- fixed, month_to_month
+    proposed_term_months INTEGER,
+    proposed_start_date TIMESTAMP,
+    proposed_end_date TIMESTAMP,
+    new_lease_id INTEGER,
+    event_id INTEGER,
+    offered_at TIMESTAMP,
+    responded_at TIMESTAMP,
+    signed_at TIMESTAMP,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (lease_id) REFERENCES leases(id) ON DELETE CASCADE,
+    FOREIGN KEY (new_lease_id) REFERENCES leases(id) ON DELETE SET NULL,
+    FOREIGN KEY (event_id) REFERENCES calendar_events(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_lease_renewals_status ON lease_renewals(status);
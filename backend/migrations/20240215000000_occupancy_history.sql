@@ -0,0 +1,26 @@
+-- Unit status over time, maintained as leases start and end. Occupied spans
+-- carry the lease that filled them; open spans have no ended_at.
+CREATE TABLE IF NOT EXISTS unit_occupancy_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    unit_id INTEGER NOT NULL,
+    property_id INTEGER NOT NULL,
+    status TEXT NOT NULL, -- occupied, vacant, maintenance
+    lease_id INTEGER,
+    started_at TIMESTAMP NOT NULL,
+    ended_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (unit_id) REFERENCES units(id) ON DELETE CASCADE,
+    FOREIGN KEY (property_id) REFERENCES properties(id) ON DELETE CASCADE,
+    FOREIGN KEY (lease_id) REFERENCES leases(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_unit_occupancy_history_unit_id ON unit_occupancy_history(unit_id, started_at);
+CREATE INDEX IF NOT EXISTS idx_unit_occupancy_history_property_id ON unit_occupancy_history(property_id);
+
+-- Back-fill occupied spans from lease history; the occupancy job opens the
+-- current span for each unit on its first run
+INSERT INTO unit_occupancy_history (unit_id, property_id, status, lease_id, started_at, ended_at)
+SELECT unit_id, property_id, 'occupied', id, start_date,
+       CASE WHEN status = 'ended' THEN end_date END
+FROM leases
+WHERE unit_id IS NOT NULL AND status IN ('active', 'ended');
@@ -0,0 +1,59 @@
+-- Statutory deposit rules per state: how long a landlord has to return the
+-- deposit after move-out, and the annual interest owed where required.
+-- Edit these to match local law; the '*' row applies to unlisted states.
+CREATE TABLE IF NOT EXISTS deposit_rules (
+    state TEXT PRIMARY KEY,
+    return_days INTEGER NOT NULL,
+    interest_rate REAL, -- annual percentage; NULL when no interest is owed
+    notes TEXT,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO deposit_rules (state, return_days, interest_rate, notes) VALUES
+    ('*', 30, NULL, 'Default for states without a specific rule'),
+    ('CA', 21, NULL, NULL),
+    ('NY', 14, NULL, NULL),
+    ('OR', 31, NULL, NULL),
+    ('TX', 30, NULL, NULL),
+    ('WA', 30, NULL, NULL);
+
+-- Money held against a lease. Amounts are positive; the entry type gives the
+-- direction (received, interest and transfer_in add to the balance held).
+CREATE TABLE IF NOT EXISTS deposit_transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    lease_id INTEGER NOT NULL,
+    entry_type TEXT NOT NULL, -- received, interest, deduction, refund, transfer_in, transfer_out
+    amount REAL NOT NULL,
+    description TEXT,
+    maintenance_id INTEGER,
+    transaction_date TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (lease_id) REFERENCES leases(id) ON DELETE CASCADE,
+    FOREIGN KEY (maintenance_id) REFERENCES maintenance_records(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_deposit_transactions_lease_id ON deposit_transactions(lease_id);
+
+-- Move-out and the statutory deadline for returning what is owed
+CREATE TABLE IF NOT EXISTS deposit_dispositions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    lease_id INTEGER NOT NULL UNIQUE,
+    move_out_date TIMESTAMP NOT NULL,
+    return_deadline TIMESTAMP NOT NULL,
+    forwarding_address TEXT,
+    status TEXT NOT NULL DEFAULT 'open', -- open, settled
+    settled_at TIMESTAMP,
+    event_id INTEGER,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (lease_id) REFERENCES leases(id) ON DELETE CASCADE,
+    FOREIGN KEY (event_id) REFERENCES calendar_events(id) ON DELETE SET NULL
+);
+
+-- Deposits recorded on existing leases are assumed received when the lease
+-- started; renewals inherit theirs from the lease they renewed
+INSERT INTO deposit_transactions (lease_id, entry_type, amount, description, transaction_date)
+SELECT id, 'received', deposit_amount, 'Opening balance', start_date
+FROM leases
+WHERE deposit_amount > 0 AND previous_lease_id IS NULL;
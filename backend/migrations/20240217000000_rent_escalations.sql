@@ -0,0 +1,31 @@
+-- Scheduled rent changes on a lease. leases.monthly_rent is the starting
+-- rent; each escalation changes it from its effective date:
+--   fixed   - rent becomes `amount`
+--   percent - rent rises by `percent`, again every `interval_months` if set
+--   cpi     - rent moves with cpi_index over each interval, within the
+--             optional floor and cap (percent)
+CREATE TABLE IF NOT EXISTS rent_escalations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    lease_id INTEGER NOT NULL,
+    escalation_type TEXT NOT NULL CHECK (escalation_type IN ('fixed', 'percent', 'cpi')),
+    effective_date TIMESTAMP NOT NULL,
+    amount REAL,
+    percent REAL,
+    interval_months INTEGER,
+    cpi_floor REAL,
+    cpi_cap REAL,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (lease_id) REFERENCES leases(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_rent_escalations_lease ON rent_escalations(lease_id);
+
+-- Consumer price index by month (YYYY-MM), entered by hand from the
+-- published figures
+CREATE TABLE IF NOT EXISTS cpi_index (
+    period TEXT PRIMARY KEY,
+    index_value REAL NOT NULL,
+    notes TEXT,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,10 @@
+-- Rent charges generated from a lease record the lease, the month billed
+-- and how the amount was worked out (e.g. the proration of a partial month)
+ALTER TABLE tenant_charges ADD COLUMN lease_id INTEGER REFERENCES leases(id) ON DELETE SET NULL;
+ALTER TABLE tenant_charges ADD COLUMN billing_period TEXT; -- YYYY-MM
+ALTER TABLE tenant_charges ADD COLUMN calculation TEXT;
+
+-- One generated rent charge per lease per month
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tenant_charges_lease_period
+    ON tenant_charges(lease_id, billing_period)
+    WHERE charge_type = 'rent' AND lease_id IS NOT NULL AND billing_period IS NOT NULL;
@@ -0,0 +1,9 @@
+-- Day of the month rent falls due on each lease; the rent charge job posts
+-- each month's charge and its rent_due event on that day
+ALTER TABLE leases ADD COLUMN rent_due_day INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE tenant_charges ADD COLUMN event_id INTEGER REFERENCES calendar_events(id) ON DELETE SET NULL;
+
+-- Rent due reminders now come from the generated charges
+DELETE FROM calendar_events
+WHERE event_type = 'rent_due' AND title = 'Rent Due - Downtown Loft' AND description = 'Monthly rent payment';
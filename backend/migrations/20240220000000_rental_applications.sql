@@ -0,0 +1,58 @@
+-- Applications to rent a vacant property or unit. Approving one creates the
+-- tenant and a pending lease.
+CREATE TABLE IF NOT EXISTS rental_applications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    property_id INTEGER NOT NULL,
+    unit_id INTEGER,
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    email TEXT,
+    phone TEXT,
+    date_of_birth TIMESTAMP,
+    current_address TEXT,
+    monthly_income REAL NOT NULL,
+    employer TEXT,
+    job_title TEXT,
+    employment_start TIMESTAMP,
+    desired_move_in TIMESTAMP NOT NULL,
+    desired_rent REAL, -- the rent applied for; unit market rent otherwise
+    occupants INTEGER NOT NULL DEFAULT 1,
+    pets TEXT,
+    notes TEXT,
+    status TEXT NOT NULL DEFAULT 'submitted', -- submitted, screened, approved, rejected
+    credit_score INTEGER,
+    background_check TEXT, -- clear, flagged, pending
+    eviction_history BOOLEAN,
+    income_verified BOOLEAN NOT NULL DEFAULT 0,
+    screened_by TEXT,
+    screening_notes TEXT,
+    screened_at TIMESTAMP,
+    decided_by TEXT,
+    decision_notes TEXT,
+    decided_at TIMESTAMP,
+    tenant_id INTEGER,
+    lease_id INTEGER,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (property_id) REFERENCES properties(id) ON DELETE CASCADE,
+    FOREIGN KEY (unit_id) REFERENCES units(id) ON DELETE SET NULL,
+    FOREIGN KEY (tenant_id) REFERENCES tenants(id) ON DELETE SET NULL,
+    FOREIGN KEY (lease_id) REFERENCES leases(id) ON DELETE SET NULL
+);
+
+-- Landlord, employer and personal references given by the applicant
+CREATE TABLE IF NOT EXISTS rental_application_references (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    application_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    relationship TEXT NOT NULL, -- landlord, employer, personal
+    phone TEXT,
+    email TEXT,
+    verified BOOLEAN NOT NULL DEFAULT 0,
+    notes TEXT,
+    FOREIGN KEY (application_id) REFERENCES rental_applications(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_rental_applications_status ON rental_applications(status);
+CREATE INDEX IF NOT EXISTS idx_rental_applications_property ON rental_applications(property_id);
+CREATE INDEX IF NOT EXISTS idx_rental_application_references_application ON rental_application_references(application_id);
@@ -0,0 +1,11 @@
+-- Whether a work order's actual and estimated totals come from its line items.
+-- Once itemized, deleting the last line item of a kind clears that total
+-- rather than leaving the deleted amount behind.
+ALTER TABLE maintenance_records ADD COLUMN cost_itemized BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE maintenance_records ADD COLUMN estimate_itemized BOOLEAN NOT NULL DEFAULT 0;
+
+UPDATE maintenance_records SET cost_itemized = 1
+WHERE id IN (SELECT maintenance_id FROM maintenance_line_items WHERE kind = 'actual');
+
+UPDATE maintenance_records SET estimate_itemized = 1
+WHERE id IN (SELECT maintenance_id FROM maintenance_line_items WHERE kind = 'estimate');
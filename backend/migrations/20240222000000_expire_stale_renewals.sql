@@ -0,0 +1,12 @@
+-- Renewals left pending on leases that have already ended can no longer be
+-- offered; close them and their calendar events
+UPDATE calendar_events SET completed = 1, updated_at = CURRENT_TIMESTAMP
+WHERE id IN (
+    SELECT r.event_id FROM lease_renewals r
+    JOIN leases l ON l.id = r.lease_id
+    WHERE r.status = 'pending' AND l.status = 'ended'
+);
+
+UPDATE lease_renewals SET status = 'expired', updated_at = CURRENT_TIMESTAMP
+WHERE status = 'pending'
+  AND lease_id IN (SELECT id FROM leases WHERE status = 'ended');
@@ -0,0 +1,10 @@
+-- Work orders generated from maintenance plans take the plan's estimate
+UPDATE maintenance_records
+SET estimated_cost = (
+    SELECT p.estimated_cost FROM maintenance_plan_occurrences o
+    JOIN maintenance_plans p ON p.id = o.plan_id
+    WHERE o.maintenance_id = maintenance_records.id
+)
+WHERE estimated_cost IS NULL
+  AND estimate_itemized = 0
+  AND id IN (SELECT maintenance_id FROM maintenance_plan_occurrences WHERE maintenance_id IS NOT NULL);
@@ -0,0 +1,19 @@
+-- Content keys whose attachment or variant rows were deleted. The owner-delete
+-- triggers can remove rows but not stored files, so deletions are queued here
+-- and the store drops each blob once nothing references it any more.
+CREATE TABLE IF NOT EXISTS attachment_blob_releases (
+    sha256 TEXT PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TRIGGER IF NOT EXISTS trg_attachments_release_blob
+AFTER DELETE ON attachments
+BEGIN
+    INSERT OR IGNORE INTO attachment_blob_releases (sha256) VALUES (OLD.sha256);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_attachment_variants_release_blob
+AFTER DELETE ON attachment_variants
+BEGIN
+    INSERT OR IGNORE INTO attachment_blob_releases (sha256) VALUES (OLD.sha256);
+END;
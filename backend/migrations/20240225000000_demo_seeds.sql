@@ -0,0 +1,5 @@
+-- Demo data sets loaded when SEED_DEMO_DATA is set, so each is applied once
+CREATE TABLE IF NOT EXISTS demo_seeds (
+    name TEXT PRIMARY KEY,
+    applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,21 @@
+-- Split land from building and record when each property went into service
+UPDATE properties SET land_value = 255000, placed_in_service_date = '2019-06-15 00:00:00' WHERE title = 'Sunset Villa';
+UPDATE properties SET land_value = 195000, placed_in_service_date = '2020-03-01 00:00:00' WHERE title = 'Downtown Loft';
+UPDATE properties SET land_value = 135000, placed_in_service_date = '2021-09-10 00:00:00' WHERE title = 'Garden Cottage';
+UPDATE properties SET land_value = 360000, placed_in_service_date = '2018-01-20 00:00:00' WHERE title = 'Commercial Plaza';
+
+-- The exterior repaint is a repair; a roof replacement is a capital improvement
+INSERT INTO contractors (name)
+SELECT 'Lone Star Roofing'
+WHERE NOT EXISTS (SELECT 1 FROM contractors WHERE name = 'Lone Star Roofing');
+
+INSERT INTO maintenance_records (property_id, unit_id, title, description, priority, status, cost, scheduled_date, completed_date, contractor, contractor_id, notes, capital_improvement)
+SELECT p.id, (SELECT MIN(id) FROM units WHERE property_id = p.id), 'Roof replacement', 'Full TPO roof replacement', 'high', 'completed', 48000, '2023-04-03 08:00:00', '2023-04-28 17:00:00',
+       'Lone Star Roofing', (SELECT id FROM contractors WHERE name = 'Lone Star Roofing'), 'Capital improvement', 1
+FROM properties p
+WHERE p.title = 'Commercial Plaza';
+
+INSERT INTO maintenance_status_history (maintenance_id, from_status, to_status, changed_by, reason, changed_at)
+SELECT id, NULL, status, 'system', 'Demo data', created_at
+FROM maintenance_records
+WHERE title = 'Roof replacement' AND id = last_insert_rowid();
@@ -0,0 +1,27 @@
+-- Rent received, operating expenses and mortgages for the sample portfolio
+INSERT INTO rent_payments (tenant_id, property_id, amount, payment_date, payment_method, reference)
+SELECT t.id, t.property_id, p.amount, p.payment_date, p.payment_method, p.reference
+FROM tenants t
+JOIN (SELECT 'john.doe@email.com' AS email, 4500 AS amount, '2024-01-01 09:00:00' AS payment_date, 'ach' AS payment_method, 'JAN-2024' AS reference
+      UNION ALL SELECT 'john.doe@email.com', 4500, '2024-02-01 09:00:00', 'ach', 'FEB-2024'
+      UNION ALL SELECT 'john.doe@email.com', 4500, '2024-03-01 09:00:00', 'ach', 'MAR-2024'
+      UNION ALL SELECT 'jane.smith@email.com', 3200, '2024-03-01 10:30:00', 'check', '1042'
+      UNION ALL SELECT 'jane.smith@email.com', 3200, '2024-04-01 10:30:00', 'check', '1043') p
+  ON p.email = t.email;
+
+INSERT INTO expenses (property_id, category, amount, expense_date, payee, description)
+SELECT p.id, e.category, e.amount, e.expense_date, e.payee, e.description
+FROM properties p
+JOIN (SELECT 'Sunset Villa' AS title, 'insurance' AS category, 2400 AS amount, '2024-01-15 00:00:00' AS expense_date, 'State Farm' AS payee, 'Annual landlord policy' AS description
+      UNION ALL SELECT 'Sunset Villa', 'taxes', 10200, '2024-04-10 00:00:00', 'San Francisco Tax Collector', 'Property tax'
+      UNION ALL SELECT 'Downtown Loft', 'management_fees', 320, '2024-03-31 00:00:00', 'Bay Property Management', 'March management fee'
+      UNION ALL SELECT 'Garden Cottage', 'advertising', 150, '2024-02-05 00:00:00', 'Zillow Rentals', 'Vacancy listing'
+      UNION ALL SELECT 'Commercial Plaza', 'utilities', 1850, '2024-02-28 00:00:00', 'Austin Energy', 'Common area electricity') e
+  ON e.title = p.title;
+
+INSERT INTO loans (property_id, lender, original_principal, interest_rate, term_months, first_payment_date, notes)
+SELECT p.id, l.lender, l.original_principal, l.interest_rate, l.term_months, l.first_payment_date, l.notes
+FROM properties p
+JOIN (SELECT 'Sunset Villa' AS title, 'First Republic Bank' AS lender, 680000 AS original_principal, 4.25 AS interest_rate, 360 AS term_months, '2019-08-01 00:00:00' AS first_payment_date, '30 year fixed' AS notes
+      UNION ALL SELECT 'Commercial Plaza', 'Frost Bank', 900000, 5.1, 240, '2018-03-01 00:00:00', '20 year commercial note') l
+  ON l.title = p.title;
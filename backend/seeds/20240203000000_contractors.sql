@@ -0,0 +1,4 @@
+-- Tax details for the sample vendors
+UPDATE contractors SET tax_id = '12-3456789', address = '88 Valencia St', city = 'San Francisco', state = 'CA', zip_code = '94103', phone = '555-0150' WHERE name = 'ABC Plumbing';
+UPDATE contractors SET entity_type = 'llc', tax_id = '98-7654321', address = '410 Pine St', city = 'Seattle', state = 'WA', zip_code = '98101' WHERE name = 'Premium Painters';
+UPDATE contractors SET entity_type = 'corporation', tax_id = '74-1234567', address = '2200 S Lamar Blvd', city = 'Austin', state = 'TX', zip_code = '78704' WHERE name = 'Lone Star Roofing';
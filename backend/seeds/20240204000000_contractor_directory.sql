@@ -0,0 +1,4 @@
+-- Trades, contacts, licensing and rates for the sample vendors
+UPDATE contractors SET trade = 'plumbing', contact_name = 'Maria Lopez', license_number = 'CA-C36-558812', license_expiry = '2026-06-30 00:00:00', insurance_expiry = '2026-01-31 00:00:00', hourly_rate = 125 WHERE name = 'ABC Plumbing';
+UPDATE contractors SET trade = 'painting', contact_name = 'Dave Kim', license_number = 'WA-PREMIPP812', license_expiry = '2025-03-15 00:00:00', insurance_expiry = '2025-12-31 00:00:00', hourly_rate = 85 WHERE name = 'Premium Painters';
+UPDATE contractors SET trade = 'roofing', contact_name = 'Sam Travis', insurance_expiry = '2026-09-30 00:00:00', hourly_rate = 95 WHERE name = 'Lone Star Roofing';
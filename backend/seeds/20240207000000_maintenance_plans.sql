@@ -0,0 +1,7 @@
+-- Recurring jobs for the sample properties, first due shortly after seeding
+INSERT INTO maintenance_plans (property_id, title, description, priority, estimated_cost, interval_value, interval_unit, lead_days, next_due_date)
+SELECT p.id, m.title, m.description, m.priority, m.estimated_cost, m.interval_value, m.interval_unit, m.lead_days, m.next_due_date
+FROM properties p
+JOIN (SELECT 'Sunset Villa' AS property, 'HVAC Maintenance' AS title, 'Check and service HVAC system' AS description, 'medium' AS priority, 180 AS estimated_cost, 6 AS interval_value, 'months' AS interval_unit, 21 AS lead_days, strftime('%Y-%m-%d 14:00:00', 'now', '+30 days') AS next_due_date
+      UNION ALL SELECT 'Downtown Loft', 'Replace air filter', 'Monthly HVAC maintenance', 'low', 50, 1, 'months', 7, strftime('%Y-%m-%d 10:00:00', 'now', '+5 days')) m
+  ON m.property = p.title;
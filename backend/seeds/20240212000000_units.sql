@@ -0,0 +1,55 @@
+-- Sample multi-unit building
+INSERT INTO properties (title, address, city, state, zip_code, property_type, bedrooms, bathrooms, square_feet, purchase_price, current_value, monthly_rent, status, notes, land_value, placed_in_service_date)
+VALUES ('Maple Court Apartments', '250 Maple Ave', 'Portland', 'OR', '97205', 'residential', NULL, NULL, 3800, 1400000, 1480000, NULL, 'occupied', 'Four-unit walk-up', 350000, '2022-06-01 00:00:00');
+
+INSERT INTO units (property_id, unit_number, bedrooms, bathrooms, square_feet, market_rent, status)
+SELECT p.id, u.unit_number, u.bedrooms, u.bathrooms, u.square_feet, u.market_rent, u.status
+FROM properties p,
+     (SELECT '1A' AS unit_number, 1 AS bedrooms, 1.0 AS bathrooms, 750 AS square_feet, 1650.0 AS market_rent, 'occupied' AS status
+      UNION ALL SELECT '1B', 2, 1.0, 950, 1950.0, 'occupied'
+      UNION ALL SELECT '2A', 1, 1.0, 750, 1700.0, 'vacant'
+      UNION ALL SELECT '2B', 2, 1.5, 1350, 2250.0, 'maintenance') u
+WHERE p.title = 'Maple Court Apartments';
+
+INSERT INTO tenants (property_id, unit_id, first_name, last_name, email, phone, lease_start, lease_end, monthly_rent, deposit_amount, status, notes, portal_token)
+SELECT u.property_id, u.id, t.first_name, t.last_name, t.email, t.phone, t.lease_start, t.lease_end, t.monthly_rent, t.deposit_amount, 'active', NULL, lower(hex(randomblob(24)))
+FROM units u
+JOIN properties p ON p.id = u.property_id AND p.title = 'Maple Court Apartments'
+JOIN (SELECT '1A' AS unit_number, 'Maria' AS first_name, 'Garcia' AS last_name, 'maria.garcia@email.com' AS email, '555-0103' AS phone, '2024-06-01 00:00:00' AS lease_start, '2025-05-31 00:00:00' AS lease_end, 1600.0 AS monthly_rent, 1600.0 AS deposit_amount
+      UNION ALL SELECT '1B', 'Sam', 'Lee', 'sam.lee@email.com', '555-0104', '2024-09-01 00:00:00', '2025-08-31 00:00:00', 1900.0, 1900.0) t
+  ON t.unit_number = u.unit_number;
+
+-- Each tenant's lease, the deposit taken at the start and the unit's
+-- occupied span; the occupancy job ends leases that have run out
+INSERT INTO leases (property_id, unit_id, lease_type, start_date, end_date, monthly_rent, deposit_amount, status)
+SELECT property_id, unit_id, 'fixed', lease_start, lease_end, monthly_rent, deposit_amount, 'active'
+FROM tenants
+WHERE email IN ('maria.garcia@email.com', 'sam.lee@email.com');
+
+INSERT INTO lease_occupants (lease_id, tenant_id, role)
+SELECT l.id, t.id, 'primary'
+FROM tenants t
+JOIN leases l ON l.unit_id = t.unit_id AND l.start_date = t.lease_start
+WHERE t.email IN ('maria.garcia@email.com', 'sam.lee@email.com');
+
+INSERT INTO deposit_transactions (lease_id, entry_type, amount, description, transaction_date)
+SELECT l.id, 'received', l.deposit_amount, 'Opening balance', l.start_date
+FROM leases l
+JOIN units u ON u.id = l.unit_id
+JOIN properties p ON p.id = u.property_id AND p.title = 'Maple Court Apartments';
+
+INSERT INTO unit_occupancy_history (unit_id, property_id, status, lease_id, started_at)
+SELECT l.unit_id, l.property_id, 'occupied', l.id, l.start_date
+FROM leases l
+JOIN properties p ON p.id = l.property_id AND p.title = 'Maple Court Apartments';
+
+INSERT INTO maintenance_records (property_id, unit_id, title, description, priority, status, cost, estimated_cost, notes)
+SELECT u.property_id, u.id, 'Refinish floors', 'Sand and refinish hardwood before re-letting', 'medium', 'pending', NULL, 1800, 'Unit offline until complete'
+FROM units u
+JOIN properties p ON p.id = u.property_id AND p.title = 'Maple Court Apartments'
+WHERE u.unit_number = '2B';
+
+INSERT INTO maintenance_status_history (maintenance_id, from_status, to_status, changed_by, reason, changed_at)
+SELECT id, NULL, status, 'system', 'Demo data', created_at
+FROM maintenance_records
+WHERE id = last_insert_rowid();
@@ -0,0 +1,12 @@
+-- Sample co-tenant sharing Sam Lee's lease
+INSERT INTO tenants (property_id, unit_id, first_name, last_name, email, phone, lease_start, lease_end, monthly_rent, deposit_amount, status, notes, portal_token)
+SELECT property_id, unit_id, 'Alex', 'Lee', 'alex.lee@email.com', '555-0105', lease_start, lease_end, monthly_rent, deposit_amount, status, 'Co-tenant with Sam Lee', lower(hex(randomblob(24)))
+FROM tenants
+WHERE email = 'sam.lee@email.com';
+
+INSERT INTO lease_occupants (lease_id, tenant_id, role)
+SELECT o.lease_id, alex.id, 'co_tenant'
+FROM tenants sam
+JOIN lease_occupants o ON o.tenant_id = sam.id
+JOIN tenants alex ON alex.email = 'alex.lee@email.com'
+WHERE sam.email = 'sam.lee@email.com';
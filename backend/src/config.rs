@@ -1,4 +1,6 @@
 use crate::error::{AppError, Result};
+use crate::leasing::proration::ProrationMethod;
+use crate::ledger::statement::Letterhead;
 use std::env;
 
 #[derive(Debug, Clone)]
@@ -8,6 +10,13 @@ pub struct Config {
     pub host: String,
     pub port: u16,
     pub cors_origin: String,
+    pub job_interval_secs: u64,
+    pub renewal_window_days: i64,
+    pub proration_method: ProrationMethod,
+    pub letterhead: Letterhead,
+    pub attachments_dir: String,
+    pub max_upload_bytes: usize,
+    pub seed_demo_data: bool,
 }
 
 impl Config {
@@ -24,6 +33,52 @@ impl Config {
                 .map_err(|_| AppError::Config("Invalid PORT".to_string()))?,
             cors_origin: env::var("CORS_ORIGIN")
                 .unwrap_or_else(|_| "http://localhost:5173".to_string()),
+            job_interval_secs: env::var("JOB_INTERVAL_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .ok_or_else(|| AppError::Config("Invalid JOB_INTERVAL_SECS".to_string()))?,
+            renewal_window_days: env::var("RENEWAL_WINDOW_DAYS")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()
+                .map_err(|_| AppError::Config("Invalid RENEWAL_WINDOW_DAYS".to_string()))?,
+            proration_method: ProrationMethod::parse(
+                &env::var("PRORATION_METHOD").unwrap_or_else(|_| "actual_days".to_string()),
+            )
+            .ok_or_else(|| {
+                AppError::Config(
+                    "Invalid PRORATION_METHOD (actual_days, thirty_day or 365_day)".to_string(),
+                )
+            })?,
+            letterhead: Letterhead {
+                name: env::var("STATEMENT_COMPANY_NAME")
+                    .unwrap_or_else(|_| "Real Estate Dashboard".to_string()),
+                // Address lines are separated by '|'
+                address_lines: env::var("STATEMENT_COMPANY_ADDRESS")
+                    .map(|v| {
+                        v.split('|')
+                            .map(|line| line.trim().to_string())
+                            .filter(|line| !line.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                contact: env::var("STATEMENT_COMPANY_CONTACT")
+                    .ok()
+                    .filter(|v| !v.trim().is_empty()),
+            },
+            attachments_dir: env::var("ATTACHMENTS_DIR")
+                .unwrap_or_else(|_| "./data/attachments".to_string()),
+            max_upload_bytes: env::var("MAX_UPLOAD_BYTES")
+                .unwrap_or_else(|_| "26214400".to_string())
+                .parse()
+                .map_err(|_| AppError::Config("Invalid MAX_UPLOAD_BYTES".to_string()))?,
+            seed_demo_data: env::var("SEED_DEMO_DATA")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .map_err(|_| {
+                    AppError::Config("Invalid SEED_DEMO_DATA (true or false)".to_string())
+                })?,
         })
     }
 }
@@ -3,3 +3,4 @@
 // This module can be extended for complex queries
 
 pub mod queries;
+pub mod seeds;
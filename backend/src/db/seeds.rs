@@ -0,0 +1,60 @@
+// Demo data for local development
+// Kept out of the migrations so production databases start with only the
+// sample rows from the initial seed; each set is applied once, in order
+
+use crate::error::Result;
+use sqlx::{Executor, SqlitePool};
+
+const DEMO_SEEDS: &[(&str, &str)] = &[
+    (
+        "20240201000000_depreciation",
+        include_str!("../../seeds/20240201000000_depreciation.sql"),
+    ),
+    (
+        "20240202000000_payments_expenses_loans",
+        include_str!("../../seeds/20240202000000_payments_expenses_loans.sql"),
+    ),
+    (
+        "20240203000000_contractors",
+        include_str!("../../seeds/20240203000000_contractors.sql"),
+    ),
+    (
+        "20240204000000_contractor_directory",
+        include_str!("../../seeds/20240204000000_contractor_directory.sql"),
+    ),
+    (
+        "20240207000000_maintenance_plans",
+        include_str!("../../seeds/20240207000000_maintenance_plans.sql"),
+    ),
+    (
+        "20240212000000_units",
+        include_str!("../../seeds/20240212000000_units.sql"),
+    ),
+    (
+        "20240213000000_leases",
+        include_str!("../../seeds/20240213000000_leases.sql"),
+    ),
+];
+
+/// Apply any demo data sets not yet loaded. Returns how many were applied.
+pub async fn apply_demo_seeds(pool: &SqlitePool) -> Result<usize> {
+    let mut applied = 0;
+
+    for (name, sql) in DEMO_SEEDS {
+        let mut tx = pool.begin().await?;
+        let claimed = sqlx::query("INSERT OR IGNORE INTO demo_seeds (name) VALUES (?)")
+            .bind(name)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if claimed == 0 {
+            continue;
+        }
+
+        (&mut *tx).execute(*sql).await?;
+        tx.commit().await?;
+        applied += 1;
+    }
+
+    Ok(applied)
+}
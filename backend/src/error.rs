@@ -12,10 +12,21 @@ pub enum AppError {
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
     #[error("Bad request: {0}")]
-    #[allow(dead_code)]
     BadRequest(String),
     #[error("Not found: {0}")]
     NotFound(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    // A 409 that also reports what it collided with
+    #[error("Conflict: {message}")]
+    ConflictWith {
+        message: String,
+        conflict: serde_json::Value,
+    },
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
     #[error("Scraper error: {0}")]
     #[allow(dead_code)]
     Scraper(String),
@@ -39,6 +50,14 @@ impl From<std::io::Error> for AppError {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        if let AppError::ConflictWith { message, conflict } = self {
+            let body = Json(json!({
+                "error": message,
+                "conflict": conflict,
+            }));
+            return (StatusCode::CONFLICT, body).into_response();
+        }
+
         let (status, error_message) = match self {
             AppError::Database(e) => {
                 tracing::error!("Database error: {:?}", e);
@@ -46,6 +65,14 @@ impl IntoResponse for AppError {
             }
             AppError::NotFound(ref msg) => (StatusCode::NOT_FOUND, msg.as_str()),
             AppError::BadRequest(ref msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
+            AppError::Conflict(ref msg)
+            | AppError::ConflictWith {
+                message: ref msg, ..
+            } => (StatusCode::CONFLICT, msg.as_str()),
+            AppError::PayloadTooLarge(ref msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg.as_str()),
+            AppError::UnsupportedMediaType(ref msg) => {
+                (StatusCode::UNSUPPORTED_MEDIA_TYPE, msg.as_str())
+            }
             AppError::Config(ref msg) => {
                 tracing::error!("Config error: {}", msg);
                 (StatusCode::INTERNAL_SERVER_ERROR, "Configuration error")
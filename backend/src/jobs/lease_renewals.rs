@@ -0,0 +1,203 @@
+// Open renewals for leases nearing their end and start signed renewals
+use crate::error::Result;
+use crate::models::{Lease, LeaseRenewal, RenewalRunSummary};
+use crate::occupancy::refresh_property;
+use crate::routes::leases::sync_lease_tenants;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+/// How many days before a fixed-term lease ends its renewal is opened
+#[derive(Debug, Clone, Copy)]
+pub struct RenewalWindow(pub i64);
+
+/// Open a renewal and its `lease_renewal` calendar event for a lease.
+/// Returns None when the lease already has one.
+pub async fn open_renewal(tx: &mut Transaction<'_, Sqlite>, lease: &Lease) -> Result<Option<i64>> {
+    let claimed = sqlx::query("INSERT OR IGNORE INTO lease_renewals (lease_id) VALUES (?)")
+        .bind(lease.id)
+        .execute(&mut **tx)
+        .await?;
+
+    if claimed.rows_affected() == 0 {
+        return Ok(None);
+    }
+    let renewal_id = claimed.last_insert_rowid();
+
+    let names: Option<String> = sqlx::query_scalar(
+        r#"
+        SELECT group_concat(t.first_name || ' ' || t.last_name, ', ')
+        FROM lease_occupants o
+        JOIN tenants t ON t.id = o.tenant_id
+        WHERE o.lease_id = ?
+        "#,
+    )
+    .bind(lease.id)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let description = match lease.end_date {
+        Some(end) => format!("Lease #{} ends on {}", lease.id, end.format("%Y-%m-%d")),
+        None => format!("Lease #{} is month-to-month", lease.id),
+    };
+
+    let event_id = sqlx::query(
+        r#"
+        INSERT INTO calendar_events (
+            title, description, event_type, property_id, unit_id,
+            start_time, reminder_minutes
+        )
+        VALUES (?, ?, 'lease_renewal', ?, ?, ?, ?)
+        "#,
+    )
+    .bind(format!(
+        "Lease renewal: {}",
+        names.as_deref().unwrap_or("vacant lease")
+    ))
+    .bind(description)
+    .bind(lease.property_id)
+    .bind(lease.unit_id)
+    .bind(lease.end_date.unwrap_or_else(Utc::now))
+    .bind(7 * 24 * 60)
+    .execute(&mut **tx)
+    .await?
+    .last_insert_rowid();
+
+    sqlx::query("UPDATE lease_renewals SET event_id = ? WHERE id = ?")
+        .bind(event_id)
+        .bind(renewal_id)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(Some(renewal_id))
+}
+
+/// Close a lease's pending or offered renewal once the lease has ended
+/// without it being taken up, completing its calendar event
+pub async fn expire_renewal(tx: &mut Transaction<'_, Sqlite>, lease_id: i64) -> Result<()> {
+    let event_ids: Vec<Option<i64>> = sqlx::query_scalar(
+        r#"
+        UPDATE lease_renewals
+        SET status = 'expired', updated_at = CURRENT_TIMESTAMP
+        WHERE lease_id = ? AND status IN ('pending', 'offered')
+        RETURNING event_id
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    for event_id in event_ids.into_iter().flatten() {
+        sqlx::query(
+            "UPDATE calendar_events SET completed = 1, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(event_id)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Hand over from the old lease to its signed renewal once the new period starts
+pub async fn start_renewed_lease(
+    tx: &mut Transaction<'_, Sqlite>,
+    renewal: &LeaseRenewal,
+) -> Result<()> {
+    let Some(new_lease_id) = renewal.new_lease_id else {
+        return Ok(());
+    };
+
+    sqlx::query("UPDATE leases SET status = 'ended', updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(renewal.lease_id)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query(
+        "UPDATE leases SET status = 'active', updated_at = CURRENT_TIMESTAMP WHERE id = ? AND status = 'pending'",
+    )
+    .bind(new_lease_id)
+    .execute(&mut **tx)
+    .await?;
+
+    sync_lease_tenants(&mut **tx, renewal.lease_id).await?;
+    sync_lease_tenants(&mut **tx, new_lease_id).await?;
+
+    let property_id: i64 = sqlx::query_scalar("SELECT property_id FROM leases WHERE id = ?")
+        .bind(new_lease_id)
+        .fetch_one(&mut **tx)
+        .await?;
+    refresh_property(tx, property_id, Utc::now()).await?;
+
+    Ok(())
+}
+
+/// Safe to run repeatedly: each lease gets at most one renewal, and a signed
+/// renewal only starts once.
+pub async fn run(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+    window: RenewalWindow,
+) -> Result<RenewalRunSummary> {
+    // Active fixed-term leases that haven't been renewed. Leases already past
+    // their end date are left to the occupancy job to end.
+    let leases = sqlx::query_as::<_, Lease>(
+        r#"
+        SELECT * FROM leases l
+        WHERE l.status = 'active'
+          AND l.lease_type = 'fixed'
+          AND l.end_date IS NOT NULL
+          AND NOT EXISTS (SELECT 1 FROM lease_renewals r WHERE r.lease_id = l.id)
+          AND NOT EXISTS (SELECT 1 FROM leases n WHERE n.previous_lease_id = l.id)
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let horizon = now + Duration::days(window.0);
+    let mut created = 0;
+
+    for lease in &leases {
+        if lease
+            .end_date
+            .is_some_and(|end| end >= now && end <= horizon)
+        {
+            let mut tx = pool.begin().await?;
+            if open_renewal(&mut tx, lease).await?.is_some() {
+                created += 1;
+            }
+            tx.commit().await?;
+        }
+    }
+
+    let signed = sqlx::query_as::<_, LeaseRenewal>(
+        r#"
+        SELECT r.* FROM lease_renewals r
+        JOIN leases n ON n.id = r.new_lease_id
+        WHERE r.status = 'signed' AND n.status = 'pending'
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut started = 0;
+
+    for renewal in &signed {
+        let starts: Option<DateTime<Utc>> =
+            sqlx::query_scalar("SELECT start_date FROM leases WHERE id = ?")
+                .bind(renewal.new_lease_id)
+                .fetch_optional(pool)
+                .await?;
+        if starts.is_some_and(|start| start <= now) {
+            let mut tx = pool.begin().await?;
+            start_renewed_lease(&mut tx, renewal).await?;
+            tx.commit().await?;
+            started += 1;
+        }
+    }
+
+    Ok(RenewalRunSummary {
+        window_days: window.0,
+        leases_checked: leases.len() as i64,
+        renewals_created: created,
+        leases_started: started,
+    })
+}
@@ -0,0 +1,161 @@
+// Generate work orders and calendar events from preventive maintenance plans
+use crate::error::Result;
+use crate::models::{MaintenancePlan, PlanRunSummary};
+use chrono::{DateTime, Duration, Months, Utc};
+use sqlx::SqlitePool;
+
+/// Next occurrence after `date` for a plan's recurrence interval
+pub fn advance(date: DateTime<Utc>, value: i32, unit: &str) -> Option<DateTime<Utc>> {
+    let value = u32::try_from(value).ok().filter(|v| *v > 0)?;
+    match unit {
+        "days" => date.checked_add_signed(Duration::days(value.into())),
+        "weeks" => date.checked_add_signed(Duration::weeks(value.into())),
+        "months" => date.checked_add_months(Months::new(value)),
+        "years" => date.checked_add_months(Months::new(value * 12)),
+        _ => None,
+    }
+}
+
+/// Create work orders for every active plan whose next due date falls within
+/// its lead time. Safe to run repeatedly: each (plan, due date) pair is only
+/// ever generated once.
+pub async fn generate_due_work_orders(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+) -> Result<PlanRunSummary> {
+    let plans =
+        sqlx::query_as::<_, MaintenancePlan>("SELECT * FROM maintenance_plans WHERE active = 1")
+            .fetch_all(pool)
+            .await?;
+
+    let mut created = 0;
+
+    for plan in &plans {
+        let mut due = plan.next_due_date;
+
+        // A plan left idle doesn't back-fill every missed occurrence, only the latest
+        while let Some(next) = advance(due, plan.interval_value, &plan.interval_unit) {
+            if next > now {
+                break;
+            }
+            due = next;
+        }
+
+        while due - Duration::days(plan.lead_days.into()) <= now {
+            if generate_occurrence(pool, plan, due).await? {
+                created += 1;
+            }
+
+            match advance(due, plan.interval_value, &plan.interval_unit) {
+                Some(next) => due = next,
+                None => break,
+            }
+        }
+
+        if due != plan.next_due_date {
+            sqlx::query(
+                "UPDATE maintenance_plans SET next_due_date = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            )
+            .bind(due)
+            .bind(plan.id)
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    Ok(PlanRunSummary {
+        plans_checked: plans.len() as i64,
+        work_orders_created: created,
+    })
+}
+
+/// Returns false when this occurrence was already generated by an earlier run
+async fn generate_occurrence(
+    pool: &SqlitePool,
+    plan: &MaintenancePlan,
+    due: DateTime<Utc>,
+) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    let claimed = sqlx::query(
+        "INSERT OR IGNORE INTO maintenance_plan_occurrences (plan_id, due_date) VALUES (?, ?)",
+    )
+    .bind(plan.id)
+    .bind(due)
+    .execute(&mut *tx)
+    .await?;
+
+    if claimed.rows_affected() == 0 {
+        return Ok(false);
+    }
+    let occurrence_id = claimed.last_insert_rowid();
+
+    let notes = format!("Generated from maintenance plan #{}", plan.id);
+
+    // The plan's estimate is the baseline for the work order's actual costs
+    let maintenance_id = sqlx::query(
+        r#"
+        INSERT INTO maintenance_records (
+            property_id, title, description, priority, status, estimated_cost,
+            scheduled_date, contractor, contractor_id, notes
+        )
+        VALUES (?, ?, ?, ?, 'scheduled', ?, ?, (SELECT name FROM contractors WHERE id = ?), ?, ?)
+        "#,
+    )
+    .bind(plan.property_id)
+    .bind(&plan.title)
+    .bind(&plan.description)
+    .bind(&plan.priority)
+    .bind(plan.estimated_cost)
+    .bind(due)
+    .bind(plan.contractor_id)
+    .bind(plan.contractor_id)
+    .bind(&notes)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    sqlx::query(
+        r#"
+        INSERT INTO maintenance_status_history (
+            maintenance_id, from_status, to_status, changed_by, reason
+        )
+        VALUES (?, NULL, 'scheduled', 'maintenance_plan', ?)
+        "#,
+    )
+    .bind(maintenance_id)
+    .bind(format!("Preventive maintenance plan #{}", plan.id))
+    .execute(&mut *tx)
+    .await?;
+
+    let event_id = sqlx::query(
+        r#"
+        INSERT INTO calendar_events (
+            title, description, event_type, property_id,
+            start_time, reminder_minutes
+        )
+        VALUES (?, ?, 'maintenance', ?, ?, ?)
+        "#,
+    )
+    .bind(&plan.title)
+    .bind(&plan.description)
+    .bind(plan.property_id)
+    .bind(due)
+    .bind(24 * 60)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    sqlx::query(
+        "UPDATE maintenance_plan_occurrences SET maintenance_id = ?, event_id = ? WHERE id = ?",
+    )
+    .bind(maintenance_id)
+    .bind(event_id)
+    .bind(occurrence_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
@@ -0,0 +1,77 @@
+// Background jobs
+// Periodic tasks that run alongside the API server on a shared interval
+
+pub mod lease_renewals;
+pub mod maintenance_plans;
+pub mod occupancy;
+pub mod rent_charges;
+
+use crate::leasing::proration::ProrationMethod;
+use chrono::Utc;
+use lease_renewals::RenewalWindow;
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+pub fn spawn(
+    pool: SqlitePool,
+    interval_secs: u64,
+    renewal_window: RenewalWindow,
+    proration_method: ProrationMethod,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(interval_secs));
+        loop {
+            ticker.tick().await;
+            run_all(&pool, renewal_window, proration_method).await;
+        }
+    });
+}
+
+async fn run_all(
+    pool: &SqlitePool,
+    renewal_window: RenewalWindow,
+    proration_method: ProrationMethod,
+) {
+    match maintenance_plans::generate_due_work_orders(pool, Utc::now()).await {
+        Ok(summary) if summary.work_orders_created > 0 => tracing::info!(
+            "Maintenance plans generated {} work orders",
+            summary.work_orders_created
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::error!("Maintenance plan job failed: {:?}", e),
+    }
+
+    match lease_renewals::run(pool, Utc::now(), renewal_window).await {
+        Ok(summary) if summary.renewals_created > 0 || summary.leases_started > 0 => {
+            tracing::info!(
+                "Lease renewals opened {} renewals and started {} renewed leases",
+                summary.renewals_created,
+                summary.leases_started
+            )
+        }
+        Ok(_) => {}
+        Err(e) => tracing::error!("Lease renewal job failed: {:?}", e),
+    }
+
+    match occupancy::run(pool, Utc::now()).await {
+        Ok(summary) if summary.leases_started > 0 || summary.leases_ended > 0 => tracing::info!(
+            "Occupancy started {} leases, ended {} and updated {} units",
+            summary.leases_started,
+            summary.leases_ended,
+            summary.units_changed
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::error!("Occupancy job failed: {:?}", e),
+    }
+
+    match rent_charges::run(pool, Utc::now(), proration_method).await {
+        Ok(summary) if summary.charges_posted > 0 => tracing::info!(
+            "Rent charges posted {} charges totalling ${:.2} for {}",
+            summary.charges_posted,
+            summary.charged,
+            summary.period
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::error!("Rent charge job failed: {:?}", e),
+    }
+}
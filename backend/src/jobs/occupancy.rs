@@ -0,0 +1,77 @@
+// Start and end leases on their dates and keep unit occupancy in step
+use crate::error::Result;
+use crate::jobs::lease_renewals::expire_renewal;
+use crate::models::{Lease, OccupancyRunSummary};
+use crate::occupancy::refresh_all;
+use crate::routes::leases::sync_lease_tenants;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+/// Renewal leases are left to the renewal job, which hands over from the old
+/// lease. A lease stays active past its end while a renewal is being
+/// negotiated; ending a lease expires a renewal that was never offered.
+pub async fn run(pool: &SqlitePool, now: DateTime<Utc>) -> Result<OccupancyRunSummary> {
+    let pending = sqlx::query_as::<_, Lease>(
+        r#"
+        SELECT * FROM leases l
+        WHERE l.status = 'pending'
+          AND NOT EXISTS (SELECT 1 FROM lease_renewals r WHERE r.new_lease_id = l.id)
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut started = 0;
+    for lease in pending.iter().filter(|l| l.start_date <= now) {
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            "UPDATE leases SET status = 'active', updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(lease.id)
+        .execute(&mut *tx)
+        .await?;
+        sync_lease_tenants(&mut *tx, lease.id).await?;
+        tx.commit().await?;
+        started += 1;
+    }
+
+    let active = sqlx::query_as::<_, Lease>(
+        r#"
+        SELECT * FROM leases l
+        WHERE l.status = 'active'
+          AND l.end_date IS NOT NULL
+          AND NOT EXISTS (
+              SELECT 1 FROM lease_renewals r
+              WHERE r.lease_id = l.id AND r.status IN ('offered', 'accepted', 'signed')
+          )
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut ended = 0;
+    for lease in active
+        .iter()
+        .filter(|l| l.end_date.is_some_and(|end| end < now))
+    {
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            "UPDATE leases SET status = 'ended', updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(lease.id)
+        .execute(&mut *tx)
+        .await?;
+        expire_renewal(&mut tx, lease.id).await?;
+        sync_lease_tenants(&mut *tx, lease.id).await?;
+        tx.commit().await?;
+        ended += 1;
+    }
+
+    let units_changed = refresh_all(pool, now).await?;
+
+    Ok(OccupancyRunSummary {
+        leases_started: started,
+        leases_ended: ended,
+        units_changed,
+    })
+}
@@ -0,0 +1,388 @@
+// Security deposit ledger, interest and move-out disposition
+use crate::error::{AppError, Result};
+use crate::models::{
+    DepositDisposition, DepositLedger, DepositRule, DepositTransaction, DispositionDeduction,
+    DispositionStatement,
+};
+use crate::tax::round_cents;
+use chrono::{DateTime, Utc};
+use sqlx::{Sqlite, SqliteExecutor, SqlitePool, Transaction};
+
+/// Entry types staff can record; transfers are only made by renewals
+pub const DEPOSIT_ENTRY_TYPES: &[&str] = &["received", "interest", "deduction", "refund"];
+
+/// Effect of an entry on the balance held
+pub fn signed_amount(entry_type: &str, amount: f64) -> f64 {
+    match entry_type {
+        "received" | "interest" | "transfer_in" => amount,
+        _ => -amount,
+    }
+}
+
+pub async fn held_balance<'e>(executor: impl SqliteExecutor<'e>, lease_id: i64) -> Result<f64> {
+    let balance: f64 = sqlx::query_scalar(
+        r#"
+        SELECT TOTAL(CASE WHEN entry_type IN ('received', 'interest', 'transfer_in')
+                          THEN amount ELSE -amount END)
+        FROM deposit_transactions
+        WHERE lease_id = ?
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(round_cents(balance))
+}
+
+async fn transactions(pool: &SqlitePool, lease_id: i64) -> Result<Vec<DepositTransaction>> {
+    Ok(sqlx::query_as::<_, DepositTransaction>(
+        "SELECT * FROM deposit_transactions WHERE lease_id = ? ORDER BY transaction_date, id",
+    )
+    .bind(lease_id)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Balance held on a lease at the end of `as_of`
+pub async fn held_as_of(pool: &SqlitePool, lease_id: i64, as_of: DateTime<Utc>) -> Result<f64> {
+    let held = transactions(pool, lease_id)
+        .await?
+        .iter()
+        .filter(|t| t.transaction_date <= as_of)
+        .map(|t| signed_amount(&t.entry_type, t.amount))
+        .sum();
+
+    Ok(round_cents(held))
+}
+
+pub async fn deposit_ledger(pool: &SqlitePool, lease_id: i64) -> Result<DepositLedger> {
+    let required: Option<f64> =
+        sqlx::query_scalar("SELECT deposit_amount FROM leases WHERE id = ?")
+            .bind(lease_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Lease with id {} not found", lease_id)))?;
+
+    let transactions = transactions(pool, lease_id).await?;
+    let of = |types: &[&str]| {
+        transactions
+            .iter()
+            .filter(|t| types.contains(&t.entry_type.as_str()))
+            .collect::<Vec<_>>()
+    };
+    let total = |types: &[&str]| round_cents(of(types).iter().map(|t| t.amount).sum());
+    let sum = |types: &[&str]| {
+        round_cents(
+            of(types)
+                .iter()
+                .map(|t| signed_amount(&t.entry_type, t.amount))
+                .sum(),
+        )
+    };
+
+    Ok(DepositLedger {
+        lease_id,
+        required,
+        received: total(&["received"]),
+        interest: total(&["interest"]),
+        deductions: total(&["deduction"]),
+        refunded: total(&["refund"]),
+        transferred: sum(&["transfer_in", "transfer_out"]),
+        balance: sum(&[
+            "received",
+            "interest",
+            "deduction",
+            "refund",
+            "transfer_in",
+            "transfer_out",
+        ]),
+        transactions,
+    })
+}
+
+/// The rule for a property's state, falling back to the default
+pub async fn rule_for_property<'e>(
+    executor: impl SqliteExecutor<'e>,
+    property_id: i64,
+) -> Result<DepositRule> {
+    sqlx::query_as::<_, DepositRule>(
+        r#"
+        SELECT r.* FROM deposit_rules r
+        WHERE r.state IN ((SELECT upper(state) FROM properties WHERE id = ?), '*')
+        ORDER BY r.state = '*'
+        LIMIT 1
+        "#,
+    )
+    .bind(property_id)
+    .fetch_optional(executor)
+    .await?
+    .ok_or_else(|| AppError::Internal("No default deposit rule configured".to_string()))
+}
+
+/// Simple interest at the annual rate on the balance held since interest was
+/// last credited, up to `through`
+pub async fn accrued_interest(
+    pool: &SqlitePool,
+    lease_id: i64,
+    rate: f64,
+    through: DateTime<Utc>,
+) -> Result<f64> {
+    let transactions = transactions(pool, lease_id).await?;
+    let since = transactions
+        .iter()
+        .rev()
+        .find(|t| t.entry_type == "interest")
+        .map(|t| t.transaction_date);
+
+    // Interest for the balance held over [start, end), skipping anything
+    // already credited
+    let segment = |balance: f64, start: DateTime<Utc>, end: DateTime<Utc>| {
+        let start = since.map_or(start, |since| start.max(since));
+        if end <= start || balance <= 0.0 {
+            return 0.0;
+        }
+        let days = (end - start).num_seconds() as f64 / 86_400.0;
+        balance * rate / 100.0 * days / 365.0
+    };
+
+    let mut interest = 0.0;
+    let mut balance = 0.0;
+    let mut segment_start = None;
+
+    for t in transactions
+        .iter()
+        .filter(|t| t.transaction_date <= through)
+    {
+        if let Some(start) = segment_start {
+            interest += segment(balance, start, t.transaction_date);
+        }
+        balance += signed_amount(&t.entry_type, t.amount);
+        segment_start = Some(t.transaction_date);
+    }
+    if let Some(start) = segment_start {
+        interest += segment(balance, start, through);
+    }
+
+    Ok(round_cents(interest))
+}
+
+/// Carry the balance held over to a renewal, dated when the renewal starts
+pub async fn transfer_to_renewal(
+    tx: &mut Transaction<'_, Sqlite>,
+    from_lease_id: i64,
+    to_lease_id: i64,
+    starts: DateTime<Utc>,
+) -> Result<()> {
+    let held = held_balance(&mut **tx, from_lease_id).await?;
+    if held <= 0.0 {
+        return Ok(());
+    }
+
+    for (lease_id, entry_type, description) in [
+        (
+            from_lease_id,
+            "transfer_out",
+            format!("Carried over to lease #{}", to_lease_id),
+        ),
+        (
+            to_lease_id,
+            "transfer_in",
+            format!("Carried over from lease #{}", from_lease_id),
+        ),
+    ] {
+        sqlx::query(
+            r#"
+            INSERT INTO deposit_transactions (lease_id, entry_type, amount, description, transaction_date)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(lease_id)
+        .bind(entry_type)
+        .bind(held)
+        .bind(description)
+        .bind(starts)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Close an open disposition once nothing is left to return
+pub async fn settle_if_paid(tx: &mut Transaction<'_, Sqlite>, lease_id: i64) -> Result<()> {
+    if held_balance(&mut **tx, lease_id).await? > 0.0 {
+        return Ok(());
+    }
+
+    let event_id: Option<Option<i64>> = sqlx::query_scalar(
+        r#"
+        UPDATE deposit_dispositions
+        SET status = 'settled', settled_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+        WHERE lease_id = ? AND status = 'open'
+        RETURNING event_id
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if let Some(Some(event_id)) = event_id {
+        sqlx::query(
+            "UPDATE calendar_events SET completed = 1, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(event_id)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Reopen a settled move-out when a correction leaves part of the deposit
+/// held again, restoring its `deposit_return` event
+pub async fn reopen_if_owed(tx: &mut Transaction<'_, Sqlite>, lease_id: i64) -> Result<()> {
+    if held_balance(&mut **tx, lease_id).await? <= 0.0 {
+        return Ok(());
+    }
+
+    let event_id: Option<Option<i64>> = sqlx::query_scalar(
+        r#"
+        UPDATE deposit_dispositions
+        SET status = 'open', settled_at = NULL, updated_at = CURRENT_TIMESTAMP
+        WHERE lease_id = ? AND status = 'settled'
+        RETURNING event_id
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if let Some(Some(event_id)) = event_id {
+        sqlx::query(
+            "UPDATE calendar_events SET completed = 0, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(event_id)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn lease_tenant_names<'e>(
+    executor: impl SqliteExecutor<'e>,
+    lease_id: i64,
+) -> Result<Vec<String>> {
+    Ok(sqlx::query_scalar(
+        r#"
+        SELECT t.first_name || ' ' || t.last_name
+        FROM lease_occupants o
+        JOIN tenants t ON t.id = o.tenant_id
+        WHERE o.lease_id = ?
+        ORDER BY CASE o.role WHEN 'primary' THEN 0 WHEN 'co_tenant' THEN 1 ELSE 2 END, t.last_name
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(executor)
+    .await?)
+}
+
+/// Whole days left until the deadline; negative once it has passed
+pub fn days_until(deadline: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    (deadline - now).num_days()
+}
+
+pub async fn disposition_statement(
+    pool: &SqlitePool,
+    lease_id: i64,
+    now: DateTime<Utc>,
+) -> Result<DispositionStatement> {
+    let disposition = sqlx::query_as::<_, DepositDisposition>(
+        "SELECT * FROM deposit_dispositions WHERE lease_id = ?",
+    )
+    .bind(lease_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!(
+            "No move-out has been recorded for lease {}",
+            lease_id
+        ))
+    })?;
+
+    let (property_title, address, city, state, zip_code, unit_number): (
+        String,
+        String,
+        String,
+        String,
+        String,
+        Option<String>,
+    ) = sqlx::query_as(
+        r#"
+        SELECT p.title, p.address, p.city, p.state, p.zip_code, u.unit_number
+        FROM leases l
+        JOIN properties p ON p.id = l.property_id
+        LEFT JOIN units u ON u.id = l.unit_id
+        WHERE l.id = ?
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_one(pool)
+    .await?;
+
+    let ledger = deposit_ledger(pool, lease_id).await?;
+
+    let titles: Vec<(i64, String)> = sqlx::query_as(
+        r#"
+        SELECT m.id, m.title FROM maintenance_records m
+        JOIN deposit_transactions d ON d.maintenance_id = m.id
+        WHERE d.lease_id = ?
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(pool)
+    .await?;
+
+    let deductions: Vec<DispositionDeduction> = ledger
+        .transactions
+        .iter()
+        .filter(|t| t.entry_type == "deduction")
+        .map(|t| DispositionDeduction {
+            date: t.transaction_date,
+            reason: t.description.clone(),
+            amount: t.amount,
+            maintenance_id: t.maintenance_id,
+            maintenance_title: t.maintenance_id.and_then(|id| {
+                titles
+                    .iter()
+                    .find(|(mid, _)| *mid == id)
+                    .map(|(_, title)| title.clone())
+            }),
+        })
+        .collect();
+
+    let deposit_received = round_cents(ledger.received + ledger.transferred);
+    let refund_due = round_cents((deposit_received + ledger.interest - ledger.deductions).max(0.0));
+    let outstanding = round_cents((refund_due - ledger.refunded).max(0.0));
+    let days_until_deadline = days_until(disposition.return_deadline, now);
+    let overdue =
+        disposition.status == "open" && outstanding > 0.0 && now > disposition.return_deadline;
+
+    Ok(DispositionStatement {
+        property_title,
+        property_address: format!("{}, {}, {} {}", address, city, state, zip_code),
+        unit_number,
+        tenants: lease_tenant_names(pool, lease_id).await?,
+        deposit_received,
+        interest: ledger.interest,
+        total_deductions: ledger.deductions,
+        deductions,
+        refund_due,
+        refunded: ledger.refunded,
+        outstanding,
+        days_until_deadline,
+        overdue,
+        disposition,
+    })
+}
@@ -0,0 +1,449 @@
+// Rent escalation schedules and the rent in effect on a date
+use crate::error::{AppError, Result};
+use crate::leasing::rent_roll::in_force;
+use crate::models::{
+    CashFlowMonth, CashFlowProjection, Lease, Loan, RentEscalation, RentSchedule, RentStep,
+};
+use crate::tax::{amortization, round_cents};
+use chrono::{DateTime, Datelike, Months, TimeZone, Utc};
+use sqlx::{SqliteExecutor, SqlitePool};
+use std::collections::{BTreeMap, HashMap};
+
+pub const ESCALATION_TYPES: &[&str] = &["fixed", "percent", "cpi"];
+
+/// CPI adjustments compare the index a year apart unless the lease says otherwise
+const DEFAULT_CPI_INTERVAL_MONTHS: i32 = 12;
+
+/// How far ahead to schedule a lease with no end date
+const OPEN_ENDED_HORIZON_MONTHS: u32 = 12;
+
+pub async fn escalations_for<'e>(
+    executor: impl SqliteExecutor<'e>,
+    lease_id: i64,
+) -> Result<Vec<RentEscalation>> {
+    Ok(sqlx::query_as::<_, RentEscalation>(
+        "SELECT * FROM rent_escalations WHERE lease_id = ? ORDER BY effective_date, id",
+    )
+    .bind(lease_id)
+    .fetch_all(executor)
+    .await?)
+}
+
+pub async fn cpi_table(pool: &SqlitePool) -> Result<BTreeMap<String, f64>> {
+    let rows = sqlx::query_as::<_, (String, f64)>("SELECT period, index_value FROM cpi_index")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows.into_iter().collect())
+}
+
+/// `YYYY-MM` key for a month, as used by the CPI table and billing periods
+pub fn month_key(date: DateTime<Utc>) -> String {
+    format!("{:04}-{:02}", date.year(), date.month())
+}
+
+pub fn parse_month_key(period: &str) -> Option<DateTime<Utc>> {
+    let (year, month) = period.split_once('-')?;
+    if year.len() != 4 || month.len() != 2 {
+        return None;
+    }
+    Utc.with_ymd_and_hms(year.parse().ok()?, month.parse().ok()?, 1, 0, 0, 0)
+        .single()
+}
+
+/// Dates an escalation applies on, up to `through`
+fn occurrences(escalation: &RentEscalation, through: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let interval = match escalation.escalation_type.as_str() {
+        "fixed" => None,
+        "cpi" => Some(
+            escalation
+                .interval_months
+                .unwrap_or(DEFAULT_CPI_INTERVAL_MONTHS),
+        ),
+        _ => escalation.interval_months,
+    }
+    .filter(|months| *months > 0);
+
+    let mut dates = Vec::new();
+    let mut k = 0;
+    while let Some(date) = escalation
+        .effective_date
+        .checked_add_months(Months::new(k * interval.unwrap_or(0) as u32))
+    {
+        if date > through {
+            break;
+        }
+        dates.push(date);
+        if interval.is_none() {
+            break;
+        }
+        k += 1;
+    }
+    dates
+}
+
+/// Every change to the lease's rent up to `through`, in date order, plus
+/// warnings for CPI adjustments that could not be made. Each step applies
+/// to the rent left by the one before it.
+pub fn rent_steps(
+    lease: &Lease,
+    escalations: &[RentEscalation],
+    cpi: &BTreeMap<String, f64>,
+    through: DateTime<Utc>,
+) -> (Vec<RentStep>, Vec<String>) {
+    let through = lease.end_date.map_or(through, |end| end.min(through));
+
+    let mut due: Vec<(DateTime<Utc>, &RentEscalation)> = escalations
+        .iter()
+        .flat_map(|e| occurrences(e, through).into_iter().map(move |d| (d, e)))
+        .collect();
+    due.sort_by_key(|(date, e)| (*date, e.id));
+
+    let mut rent = lease.monthly_rent;
+    let mut steps = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (date, escalation) in due {
+        let (new_rent, description) = match escalation.escalation_type.as_str() {
+            "fixed" => {
+                let Some(amount) = escalation.amount else {
+                    continue;
+                };
+                (amount, "Scheduled rent step".to_string())
+            }
+            "percent" => {
+                let percent = escalation.percent.unwrap_or(0.0);
+                (
+                    rent * (1.0 + percent / 100.0),
+                    format!("{}% escalation", percent),
+                )
+            }
+            _ => {
+                let interval = escalation
+                    .interval_months
+                    .filter(|m| *m > 0)
+                    .unwrap_or(DEFAULT_CPI_INTERVAL_MONTHS);
+                let base_date = date - Months::new(interval as u32);
+                let (base, current) = (month_key(base_date), month_key(date));
+                let (Some(base_index), Some(index)) = (cpi.get(&base), cpi.get(&current)) else {
+                    warnings.push(format!(
+                        "CPI adjustment on {} skipped: no index entered for {}",
+                        date.format("%Y-%m-%d"),
+                        if cpi.contains_key(&base) {
+                            current
+                        } else {
+                            base
+                        }
+                    ));
+                    continue;
+                };
+
+                let mut change = (index / base_index - 1.0) * 100.0;
+                if let Some(floor) = escalation.cpi_floor {
+                    change = change.max(floor);
+                }
+                if let Some(cap) = escalation.cpi_cap {
+                    change = change.min(cap);
+                }
+                (
+                    rent * (1.0 + change / 100.0),
+                    format!("CPI {} to {}: {:.2}%", base, current, change),
+                )
+            }
+        };
+
+        let new_rent = round_cents(new_rent);
+        steps.push(RentStep {
+            effective_date: date,
+            monthly_rent: new_rent,
+            change: round_cents(new_rent - rent),
+            escalation_id: escalation.id,
+            description,
+        });
+        rent = new_rent;
+    }
+
+    (steps, warnings)
+}
+
+/// Rent in effect on `date` given the lease's steps
+pub fn rent_in_effect(lease: &Lease, steps: &[RentStep], date: DateTime<Utc>) -> f64 {
+    steps
+        .iter()
+        .take_while(|s| s.effective_date <= date)
+        .last()
+        .map_or(lease.monthly_rent, |s| s.monthly_rent)
+}
+
+/// The tenant's lease in force on `date`, the latest to start if several are
+pub async fn tenant_lease_on(
+    pool: &SqlitePool,
+    tenant_id: i64,
+    date: DateTime<Utc>,
+) -> Result<Option<Lease>> {
+    let leases = sqlx::query_as::<_, Lease>(
+        r#"
+        SELECT l.* FROM leases l
+        JOIN lease_occupants o ON o.lease_id = l.id
+        WHERE o.tenant_id = ?
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(leases
+        .into_iter()
+        .filter(|l| in_force(l, date))
+        .max_by_key(|l| l.start_date))
+}
+
+/// Monthly rent the lease charges on `date`
+pub async fn rent_on(pool: &SqlitePool, lease: &Lease, date: DateTime<Utc>) -> Result<f64> {
+    let escalations = escalations_for(pool, lease.id).await?;
+    if escalations.is_empty() {
+        return Ok(lease.monthly_rent);
+    }
+
+    let cpi = cpi_table(pool).await?;
+    let (steps, _) = rent_steps(lease, &escalations, &cpi, date);
+
+    Ok(rent_in_effect(lease, &steps, date))
+}
+
+/// The lease's rent schedule through its end (or `through`, for leases
+/// without one)
+pub async fn rent_schedule(
+    pool: &SqlitePool,
+    lease: &Lease,
+    through: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<RentSchedule> {
+    let through = through
+        .or(lease.end_date)
+        .unwrap_or_else(|| now.max(lease.start_date) + Months::new(OPEN_ENDED_HORIZON_MONTHS));
+
+    let escalations = escalations_for(pool, lease.id).await?;
+    let cpi = cpi_table(pool).await?;
+    let (steps, warnings) = rent_steps(lease, &escalations, &cpi, through);
+
+    Ok(RentSchedule {
+        lease_id: lease.id,
+        base_rent: lease.monthly_rent,
+        current_rent: rent_in_effect(lease, &steps, now),
+        escalations,
+        steps,
+        warnings,
+    })
+}
+
+/// Scheduled rent less loan payments for each month from `from`. A lease
+/// counts in every month it overlaps (active holdovers carry on), at the rent
+/// in effect when the month (or the lease) starts.
+pub async fn cash_flow_projection(
+    pool: &SqlitePool,
+    from: DateTime<Utc>,
+    months: u32,
+    property_id: Option<i64>,
+) -> Result<CashFlowProjection> {
+    let first = Utc
+        .with_ymd_and_hms(from.year(), from.month(), 1, 0, 0, 0)
+        .single()
+        .ok_or_else(|| AppError::BadRequest("Invalid projection start".to_string()))?;
+    let horizon = first + Months::new(months);
+
+    let leases = sqlx::query_as::<_, Lease>(
+        r#"
+        SELECT * FROM leases
+        WHERE status IN ('pending', 'active') AND (?1 IS NULL OR property_id = ?1)
+        "#,
+    )
+    .bind(property_id)
+    .fetch_all(pool)
+    .await?;
+
+    let cpi = cpi_table(pool).await?;
+    let mut schedules = HashMap::new();
+    for lease in &leases {
+        let escalations = escalations_for(pool, lease.id).await?;
+        let (steps, _) = rent_steps(lease, &escalations, &cpi, horizon);
+        schedules.insert(lease.id, steps);
+    }
+
+    let loans =
+        sqlx::query_as::<_, Loan>("SELECT * FROM loans WHERE (?1 IS NULL OR property_id = ?1)")
+            .bind(property_id)
+            .fetch_all(pool)
+            .await?;
+
+    let mut projection = Vec::with_capacity(months as usize);
+    for k in 0..months {
+        let start = first + Months::new(k);
+        let end = start + Months::new(1);
+
+        let scheduled_rent: f64 = leases
+            .iter()
+            .filter(|l| {
+                l.start_date < end
+                    && (l.end_date.is_none_or(|e| e >= start) || l.status == "active")
+            })
+            .map(|l| rent_in_effect(l, &schedules[&l.id], start.max(l.start_date)))
+            .sum();
+
+        let month_index = start.year() * 12 + start.month0() as i32;
+        let debt_service: f64 = loans
+            .iter()
+            .filter(|loan| {
+                let first_payment =
+                    loan.first_payment_date.year() * 12 + loan.first_payment_date.month0() as i32;
+                (first_payment..first_payment + loan.term_months).contains(&month_index)
+            })
+            .map(|loan| {
+                amortization::monthly_payment(
+                    loan.original_principal,
+                    loan.interest_rate,
+                    loan.term_months,
+                )
+            })
+            .sum();
+
+        projection.push(CashFlowMonth {
+            month: month_key(start),
+            scheduled_rent: round_cents(scheduled_rent),
+            debt_service: round_cents(debt_service),
+            net: round_cents(scheduled_rent - debt_service),
+        });
+    }
+
+    let scheduled_rent = round_cents(projection.iter().map(|m| m.scheduled_rent).sum());
+    let debt_service = round_cents(projection.iter().map(|m| m.debt_service).sum());
+
+    Ok(CashFlowProjection {
+        property_id,
+        months: projection,
+        scheduled_rent,
+        debt_service,
+        net: round_cents(scheduled_rent - debt_service),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    fn lease(monthly_rent: f64, end_date: Option<DateTime<Utc>>) -> Lease {
+        Lease {
+            id: 1,
+            property_id: 1,
+            unit_id: None,
+            lease_type: "fixed".to_string(),
+            start_date: day(2024, 1, 1),
+            end_date,
+            monthly_rent,
+            deposit_amount: None,
+            status: "active".to_string(),
+            previous_lease_id: None,
+            notes: None,
+            created_at: day(2024, 1, 1),
+            updated_at: day(2024, 1, 1),
+            rent_due_day: 1,
+        }
+    }
+
+    fn escalation(id: i64, escalation_type: &str, effective_date: DateTime<Utc>) -> RentEscalation {
+        RentEscalation {
+            id,
+            lease_id: 1,
+            escalation_type: escalation_type.to_string(),
+            effective_date,
+            amount: None,
+            percent: None,
+            interval_months: None,
+            cpi_floor: None,
+            cpi_cap: None,
+            notes: None,
+            created_at: day(2024, 1, 1),
+        }
+    }
+
+    fn cpi(entries: &[(&str, f64)]) -> BTreeMap<String, f64> {
+        entries
+            .iter()
+            .map(|(period, value)| (period.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn occurrences_repeat_on_the_interval_up_to_through() {
+        let mut percent = escalation(1, "percent", day(2025, 1, 1));
+        percent.interval_months = Some(12);
+        assert_eq!(
+            occurrences(&percent, day(2027, 6, 30)),
+            vec![day(2025, 1, 1), day(2026, 1, 1), day(2027, 1, 1)]
+        );
+
+        // CPI adjusts yearly unless told otherwise; fixed steps happen once
+        let cpi = escalation(2, "cpi", day(2025, 3, 1));
+        assert_eq!(
+            occurrences(&cpi, day(2026, 3, 1)),
+            vec![day(2025, 3, 1), day(2026, 3, 1)]
+        );
+        let fixed = escalation(3, "fixed", day(2025, 1, 1));
+        assert_eq!(occurrences(&fixed, day(2030, 1, 1)), vec![day(2025, 1, 1)]);
+        assert!(occurrences(&fixed, day(2024, 12, 31)).is_empty());
+    }
+
+    #[test]
+    fn percent_steps_compound_and_stop_at_the_lease_end() {
+        let mut percent = escalation(1, "percent", day(2025, 1, 1));
+        percent.percent = Some(3.0);
+        percent.interval_months = Some(12);
+        let lease = lease(2000.0, Some(day(2026, 12, 31)));
+
+        let (steps, warnings) = rent_steps(&lease, &[percent], &cpi(&[]), day(2030, 1, 1));
+
+        assert!(warnings.is_empty());
+        let rents: Vec<f64> = steps.iter().map(|s| s.monthly_rent).collect();
+        assert_eq!(rents, vec![2060.0, 2121.8]);
+        assert_eq!(steps[1].change, 61.8);
+        assert_eq!(rent_in_effect(&lease, &steps, day(2025, 6, 1)), 2060.0);
+        assert_eq!(rent_in_effect(&lease, &steps, day(2024, 6, 1)), 2000.0);
+    }
+
+    #[test]
+    fn cpi_steps_respect_the_cap_and_floor() {
+        let index = cpi(&[("2024-01", 300.0), ("2025-01", 315.0), ("2026-01", 316.575)]);
+        let mut adjustment = escalation(1, "cpi", day(2025, 1, 1));
+        adjustment.cpi_floor = Some(2.0);
+        adjustment.cpi_cap = Some(3.0);
+
+        let (steps, warnings) =
+            rent_steps(&lease(2000.0, None), &[adjustment], &index, day(2026, 1, 1));
+
+        assert!(warnings.is_empty());
+        // 5% is capped at 3%, then 0.5% is raised to the 2% floor
+        let rents: Vec<f64> = steps.iter().map(|s| s.monthly_rent).collect();
+        assert_eq!(rents, vec![2060.0, 2101.2]);
+        assert_eq!(steps[0].description, "CPI 2024-01 to 2025-01: 3.00%");
+    }
+
+    #[test]
+    fn cpi_step_without_an_index_is_skipped_with_a_warning() {
+        let index = cpi(&[("2024-01", 300.0), ("2025-01", 309.0)]);
+        let adjustment = escalation(1, "cpi", day(2025, 1, 1));
+
+        let (steps, warnings) =
+            rent_steps(&lease(2000.0, None), &[adjustment], &index, day(2026, 1, 1));
+
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].monthly_rent, 2060.0);
+        assert_eq!(
+            warnings,
+            vec!["CPI adjustment on 2026-01-01 skipped: no index entered for 2026-01".to_string()]
+        );
+    }
+}
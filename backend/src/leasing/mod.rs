@@ -0,0 +1,6 @@
+// Leasing reports and calculations built on the leases table
+pub mod deposits;
+pub mod escalation;
+pub mod proration;
+pub mod rent_charges;
+pub mod rent_roll;
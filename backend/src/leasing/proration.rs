@@ -0,0 +1,225 @@
+// Rent for partial months
+use crate::tax::round_cents;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+/// How a partial month's rent is worked out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProrationMethod {
+    /// Monthly rent / days in that month, per day occupied
+    ActualDays,
+    /// Monthly rent / 30, per day occupied (at most 30)
+    ThirtyDay,
+    /// Monthly rent x 12 / 365, per day occupied
+    YearDays,
+}
+
+impl ProrationMethod {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "actual_days" => Some(Self::ActualDays),
+            "thirty_day" => Some(Self::ThirtyDay),
+            "365_day" => Some(Self::YearDays),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ActualDays => "actual_days",
+            Self::ThirtyDay => "thirty_day",
+            Self::YearDays => "365_day",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RentCalculation {
+    pub monthly_rent: f64,
+    pub days_billed: i64,
+    pub days_in_month: i64,
+    pub method: &'static str,
+    pub prorated: bool,
+    pub amount: f64,
+    pub explanation: String,
+}
+
+/// Rent for the days from `from` to `to` (both inclusive) of the month
+/// starting `month_start`. A whole month bills the full rent under every
+/// method.
+pub fn prorate(
+    monthly_rent: f64,
+    month_start: DateTime<Utc>,
+    month_end: DateTime<Utc>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    method: ProrationMethod,
+) -> RentCalculation {
+    let days_in_month = (month_end - month_start).num_days();
+    let days_billed = (to - from).num_days() + 1;
+    let money = |v: f64| format!("${:.2}", v);
+
+    if days_billed >= days_in_month {
+        return RentCalculation {
+            monthly_rent,
+            days_billed: days_in_month,
+            days_in_month,
+            method: method.as_str(),
+            prorated: false,
+            amount: round_cents(monthly_rent),
+            explanation: format!("Full month at {}", money(monthly_rent)),
+        };
+    }
+
+    let (amount, explanation) = match method {
+        ProrationMethod::ActualDays => (
+            monthly_rent * days_billed as f64 / days_in_month as f64,
+            format!(
+                "{} x {}/{} days (actual days in month)",
+                money(monthly_rent),
+                days_billed,
+                days_in_month
+            ),
+        ),
+        ProrationMethod::ThirtyDay => {
+            let days = days_billed.min(30);
+            (
+                monthly_rent * days as f64 / 30.0,
+                format!("{} x {}/30 days (30-day month)", money(monthly_rent), days),
+            )
+        }
+        ProrationMethod::YearDays => (
+            monthly_rent * 12.0 * days_billed as f64 / 365.0,
+            format!(
+                "{} x 12 / 365 x {} days (365-day year)",
+                money(monthly_rent),
+                days_billed
+            ),
+        ),
+    };
+    let amount = round_cents(amount);
+
+    RentCalculation {
+        monthly_rent,
+        days_billed,
+        days_in_month,
+        method: method.as_str(),
+        prorated: true,
+        amount,
+        explanation: format!(
+            "{}, {} to {} = {}",
+            explanation,
+            from.format("%Y-%m-%d"),
+            to.format("%Y-%m-%d"),
+            money(amount)
+        ),
+    }
+}
+
+/// Last day of the month starting `month_start`
+pub fn last_day(month_end: DateTime<Utc>) -> DateTime<Utc> {
+    month_end - Duration::days(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Months, TimeZone};
+
+    fn day(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    fn bill(
+        rent: f64,
+        month_start: DateTime<Utc>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        method: ProrationMethod,
+    ) -> RentCalculation {
+        prorate(
+            rent,
+            month_start,
+            month_start + Months::new(1),
+            from,
+            to,
+            method,
+        )
+    }
+
+    #[test]
+    fn prorates_a_mid_month_start() {
+        let (start, from, to) = (day(2026, 1, 1), day(2026, 1, 17), day(2026, 1, 31));
+
+        let actual = bill(1550.0, start, from, to, ProrationMethod::ActualDays);
+        assert_eq!(actual.days_billed, 15);
+        assert_eq!(actual.days_in_month, 31);
+        assert!(actual.prorated);
+        assert_eq!(actual.amount, 750.0);
+        assert_eq!(
+            actual.explanation,
+            "$1550.00 x 15/31 days (actual days in month), 2026-01-17 to 2026-01-31 = $750.00"
+        );
+
+        assert_eq!(
+            bill(1550.0, start, from, to, ProrationMethod::ThirtyDay).amount,
+            775.0
+        );
+        assert_eq!(
+            bill(1550.0, start, from, to, ProrationMethod::YearDays).amount,
+            764.38
+        );
+    }
+
+    #[test]
+    fn prorates_a_mid_month_end() {
+        let (start, from, to) = (day(2026, 1, 1), day(2026, 1, 1), day(2026, 1, 10));
+
+        assert_eq!(
+            bill(1550.0, start, from, to, ProrationMethod::ActualDays).amount,
+            500.0
+        );
+        assert_eq!(
+            bill(1550.0, start, from, to, ProrationMethod::ThirtyDay).amount,
+            516.67
+        );
+        assert_eq!(
+            bill(1550.0, start, from, to, ProrationMethod::YearDays).amount,
+            509.59
+        );
+    }
+
+    #[test]
+    fn prorates_part_of_february() {
+        let (start, from, to) = (day(2026, 2, 1), day(2026, 2, 15), day(2026, 2, 28));
+
+        let actual = bill(1400.0, start, from, to, ProrationMethod::ActualDays);
+        assert_eq!(actual.days_billed, 14);
+        assert_eq!(actual.days_in_month, 28);
+        assert_eq!(actual.amount, 700.0);
+        assert_eq!(
+            bill(1400.0, start, from, to, ProrationMethod::ThirtyDay).amount,
+            653.33
+        );
+        assert_eq!(
+            bill(1400.0, start, from, to, ProrationMethod::YearDays).amount,
+            644.38
+        );
+    }
+
+    #[test]
+    fn whole_february_is_full_rent_under_every_method() {
+        let (start, from, to) = (day(2026, 2, 1), day(2026, 2, 1), day(2026, 2, 28));
+
+        for method in [
+            ProrationMethod::ActualDays,
+            ProrationMethod::ThirtyDay,
+            ProrationMethod::YearDays,
+        ] {
+            let calculation = bill(1400.0, start, from, to, method);
+            assert!(!calculation.prorated);
+            assert_eq!(calculation.amount, 1400.0);
+            assert_eq!(calculation.explanation, "Full month at $1400.00");
+        }
+    }
+}
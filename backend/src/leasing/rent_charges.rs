@@ -0,0 +1,149 @@
+// Monthly rent charges generated from leases
+use crate::error::{AppError, Result};
+use crate::leasing::deposits::lease_tenant_names;
+use crate::leasing::escalation::{month_key, rent_on};
+use crate::leasing::proration::{last_day, prorate, ProrationMethod, RentCalculation};
+use crate::models::{Lease, TenantCharge};
+use chrono::{DateTime, Duration, Months, NaiveTime, Utc};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+fn midnight(date: DateTime<Utc>) -> DateTime<Utc> {
+    date.date_naive().and_time(NaiveTime::MIN).and_utc()
+}
+
+/// When the month's rent falls due: the lease's due day, or the day it
+/// starts if that is later
+pub fn due_date(lease: &Lease, month_start: DateTime<Utc>) -> DateTime<Utc> {
+    let due_day = month_start + Duration::days(lease.rent_due_day.clamp(1, 28) as i64 - 1);
+    due_day.max(midnight(lease.start_date))
+}
+
+/// Rent the lease owes for the month starting `month_start`, prorated when
+/// the lease starts or ends part way through it. None when the lease does
+/// not cover any of the month.
+///
+/// An active lease is holding over while a renewal is worked out, so the
+/// month its end falls in and any after it are billed at the lease's rent
+/// until a renewal lease takes over.
+pub async fn rent_for_month(
+    pool: &SqlitePool,
+    lease: &Lease,
+    month_start: DateTime<Utc>,
+    method: ProrationMethod,
+) -> Result<Option<RentCalculation>> {
+    let month_end = month_start + Months::new(1);
+    let from = month_start.max(midnight(lease.start_date));
+    let mut to = lease.end_date.map_or(last_day(month_end), |end| {
+        last_day(month_end).min(midnight(end))
+    });
+    if lease.status == "active" && to < last_day(month_end) {
+        let renewal_start: Option<DateTime<Utc>> =
+            sqlx::query_scalar("SELECT MIN(start_date) FROM leases WHERE previous_lease_id = ?")
+                .bind(lease.id)
+                .fetch_one(pool)
+                .await?;
+        to = renewal_start.map_or(last_day(month_end), |start| {
+            last_day(month_end).min(midnight(start) - Duration::days(1))
+        });
+    }
+    if to < from {
+        return Ok(None);
+    }
+
+    let rent = rent_on(pool, lease, from).await?;
+
+    Ok(Some(prorate(
+        rent,
+        month_start,
+        month_end,
+        from,
+        to,
+        method,
+    )))
+}
+
+/// Post the month's rent to the lease's primary tenant along with a
+/// `rent_due` calendar event. None when the month has already been billed.
+/// The unique lease/month index makes this safe to repeat.
+pub async fn post_rent_charge(
+    tx: &mut Transaction<'_, Sqlite>,
+    lease: &Lease,
+    month_start: DateTime<Utc>,
+    calculation: &RentCalculation,
+) -> Result<Option<TenantCharge>> {
+    let tenant_id: i64 = sqlx::query_scalar(
+        r#"
+        SELECT tenant_id FROM lease_occupants
+        WHERE lease_id = ?
+        ORDER BY CASE role WHEN 'primary' THEN 0 WHEN 'co_tenant' THEN 1 ELSE 2 END, created_at, tenant_id
+        LIMIT 1
+        "#,
+    )
+    .bind(lease.id)
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| AppError::BadRequest(format!("Lease {} has no tenants to bill", lease.id)))?;
+
+    let month = month_start.format("%B %Y");
+    let description = if calculation.prorated {
+        format!("Prorated rent for {}", month)
+    } else {
+        format!("Rent for {}", month)
+    };
+    let charge_date = due_date(lease, month_start);
+
+    let charge = sqlx::query_as::<_, TenantCharge>(
+        r#"
+        INSERT OR IGNORE INTO tenant_charges (
+            tenant_id, property_id, charge_type, amount, charge_date, description,
+            lease_id, billing_period, calculation
+        )
+        VALUES (?, ?, 'rent', ?, ?, ?, ?, ?, ?)
+        RETURNING *
+        "#,
+    )
+    .bind(tenant_id)
+    .bind(lease.property_id)
+    .bind(calculation.amount)
+    .bind(charge_date)
+    .bind(&description)
+    .bind(lease.id)
+    .bind(month_key(month_start))
+    .bind(&calculation.explanation)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let Some(mut charge) = charge else {
+        return Ok(None);
+    };
+
+    let names = lease_tenant_names(&mut **tx, lease.id).await?;
+    let event_id = sqlx::query(
+        r#"
+        INSERT INTO calendar_events (
+            title, description, event_type, property_id, unit_id, start_time
+        )
+        VALUES (?, ?, 'rent_due', ?, ?, ?)
+        "#,
+    )
+    .bind(format!("Rent due: {}", names.join(", ")))
+    .bind(format!(
+        "{}: ${:.2} ({})",
+        description, calculation.amount, calculation.explanation
+    ))
+    .bind(lease.property_id)
+    .bind(lease.unit_id)
+    .bind(charge_date)
+    .execute(&mut **tx)
+    .await?
+    .last_insert_rowid();
+
+    sqlx::query("UPDATE tenant_charges SET event_id = ? WHERE id = ?")
+        .bind(event_id)
+        .bind(charge.id)
+        .execute(&mut **tx)
+        .await?;
+    charge.event_id = Some(event_id);
+
+    Ok(Some(charge))
+}
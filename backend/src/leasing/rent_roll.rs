@@ -0,0 +1,174 @@
+// Rent roll: every unit with its lease, rent, deposit and balance on a date
+use crate::error::Result;
+use crate::leasing::deposits::held_as_of;
+use crate::leasing::escalation::rent_on;
+use crate::ledger::tenant_ledger;
+use crate::models::{Lease, RentRoll, RentRollRow, RentRollTotals};
+use crate::tax::round_cents;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+
+#[derive(FromRow)]
+struct UnitRow {
+    id: i64,
+    property_id: i64,
+    property_title: String,
+    address: String,
+    city: String,
+    state: String,
+    zip_code: String,
+    unit_number: String,
+    bedrooms: Option<i32>,
+    bathrooms: Option<f32>,
+    square_feet: Option<i32>,
+    market_rent: Option<f64>,
+}
+
+/// Whether a lease was in force on `as_of`. An active lease past its end
+/// date is a holdover and still counts.
+pub fn in_force(lease: &Lease, as_of: DateTime<Utc>) -> bool {
+    lease.start_date <= as_of
+        && (lease.end_date.is_none_or(|end| as_of <= end) || lease.status == "active")
+}
+
+/// Units are listed by property then unit number. Where two leases cover the
+/// date (a holdover and its renewal) the later one wins; a lease without a
+/// unit appears on its property's first unit.
+pub async fn build_rent_roll(
+    pool: &SqlitePool,
+    as_of: DateTime<Utc>,
+    property_id: Option<i64>,
+) -> Result<RentRoll> {
+    let units = sqlx::query_as::<_, UnitRow>(
+        r#"
+        SELECT u.id, u.property_id, p.title AS property_title, p.address, p.city, p.state,
+               p.zip_code, u.unit_number, u.bedrooms, u.bathrooms, u.square_feet, u.market_rent
+        FROM units u
+        JOIN properties p ON p.id = u.property_id
+        WHERE (?1 IS NULL OR u.property_id = ?1)
+        ORDER BY p.title, u.property_id, u.unit_number
+        "#,
+    )
+    .bind(property_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut leases = sqlx::query_as::<_, Lease>(
+        "SELECT * FROM leases WHERE (?1 IS NULL OR property_id = ?1) ORDER BY start_date DESC",
+    )
+    .bind(property_id)
+    .fetch_all(pool)
+    .await?;
+    leases.retain(|l| in_force(l, as_of));
+
+    let mut rows = Vec::with_capacity(units.len());
+    let mut placed_unitless = Vec::new();
+
+    for unit in units {
+        let lease = leases
+            .iter()
+            .find(|l| l.unit_id == Some(unit.id))
+            .or_else(|| {
+                let lease = leases
+                    .iter()
+                    .find(|l| l.unit_id.is_none() && l.property_id == unit.property_id)?;
+                (!placed_unitless.contains(&lease.id)).then_some(lease)
+            });
+
+        let mut row = RentRollRow {
+            property_id: unit.property_id,
+            property_title: unit.property_title,
+            address: format!(
+                "{}, {}, {} {}",
+                unit.address, unit.city, unit.state, unit.zip_code
+            ),
+            unit_id: unit.id,
+            unit_number: unit.unit_number,
+            bedrooms: unit.bedrooms,
+            bathrooms: unit.bathrooms,
+            square_feet: unit.square_feet,
+            status: "vacant".to_string(),
+            lease_id: None,
+            lease_type: None,
+            tenants: Vec::new(),
+            lease_start: None,
+            lease_end: None,
+            market_rent: unit.market_rent,
+            monthly_rent: None,
+            deposit_held: None,
+            balance_due: 0.0,
+        };
+
+        if let Some(lease) = lease {
+            if lease.unit_id.is_none() {
+                placed_unitless.push(lease.id);
+            }
+
+            let tenants = sqlx::query_as::<_, (i64, String)>(
+                r#"
+                SELECT t.id, t.first_name || ' ' || t.last_name
+                FROM lease_occupants o
+                JOIN tenants t ON t.id = o.tenant_id
+                WHERE o.lease_id = ?
+                ORDER BY CASE o.role WHEN 'primary' THEN 0 WHEN 'co_tenant' THEN 1 ELSE 2 END, t.last_name
+                "#,
+            )
+            .bind(lease.id)
+            .fetch_all(pool)
+            .await?;
+
+            // Occupants share one ledger, so the primary tenant's covers them all
+            let balance = match tenants.first() {
+                Some((tenant_id, _)) => {
+                    tenant_ledger(pool, *tenant_id, None, Some(as_of))
+                        .await?
+                        .balance
+                }
+                None => 0.0,
+            };
+
+            row.status = "occupied".to_string();
+            row.lease_id = Some(lease.id);
+            row.lease_type = Some(lease.lease_type.clone());
+            row.tenants = tenants.into_iter().map(|(_, name)| name).collect();
+            row.lease_start = Some(lease.start_date);
+            row.lease_end = lease.end_date;
+            row.monthly_rent = Some(rent_on(pool, lease, as_of).await?);
+            row.deposit_held = Some(held_as_of(pool, lease.id, as_of).await?);
+            row.balance_due = round_cents(balance);
+        }
+
+        rows.push(row);
+    }
+
+    let occupied: Vec<&RentRollRow> = rows.iter().filter(|r| r.lease_id.is_some()).collect();
+    let units = rows.len() as i64;
+    let occupied_units = occupied.len() as i64;
+
+    let totals = RentRollTotals {
+        units,
+        occupied_units,
+        vacant_units: units - occupied_units,
+        occupancy_rate: if units > 0 {
+            round_cents(occupied_units as f64 / units as f64 * 100.0)
+        } else {
+            0.0
+        },
+        market_rent: round_cents(rows.iter().filter_map(|r| r.market_rent).sum()),
+        monthly_rent: round_cents(occupied.iter().filter_map(|r| r.monthly_rent).sum()),
+        loss_to_lease: round_cents(
+            occupied
+                .iter()
+                .map(|r| (r.market_rent.unwrap_or(0.0) - r.monthly_rent.unwrap_or(0.0)).max(0.0))
+                .sum(),
+        ),
+        deposits_held: round_cents(occupied.iter().filter_map(|r| r.deposit_held).sum()),
+        balance_due: round_cents(rows.iter().map(|r| r.balance_due).sum()),
+    };
+
+    Ok(RentRoll {
+        as_of,
+        rows,
+        totals,
+    })
+}
@@ -0,0 +1,150 @@
+// Tenant ledger
+// Charges and credits come from tenant_charges, payments from rent_payments
+use crate::error::Result;
+use crate::models::{LedgerEntry, TenantLedger};
+use crate::tax::round_cents;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+pub mod statement;
+
+pub const CHARGE_TYPES: &[&str] = &[
+    "rent",
+    "late_fee",
+    "maintenance",
+    "utility",
+    "credit",
+    "other",
+];
+
+/// Ledger entries between `from` and `to` (inclusive), with the balance
+/// carried in from everything before `from`. Covers everyone on the tenant's
+/// leases, since rent is billed to the primary tenant but any occupant may
+/// pay it.
+pub async fn tenant_ledger(
+    pool: &SqlitePool,
+    tenant_id: i64,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<TenantLedger> {
+    let charges = sqlx::query_as::<
+        _,
+        (
+            i64,
+            String,
+            f64,
+            DateTime<Utc>,
+            Option<String>,
+            Option<String>,
+        ),
+    >(
+        r#"
+        SELECT id, charge_type, amount, charge_date, description, calculation
+        FROM tenant_charges
+        WHERE tenant_id = ?1
+           OR tenant_id IN (
+               SELECT o.tenant_id FROM lease_occupants o
+               JOIN lease_occupants mine ON mine.lease_id = o.lease_id
+               WHERE mine.tenant_id = ?1
+           )
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await?;
+
+    let payments = sqlx::query_as::<_, (i64, f64, DateTime<Utc>, Option<String>, Option<String>)>(
+        r#"
+        SELECT id, amount, payment_date, payment_method, reference
+        FROM rent_payments
+        WHERE tenant_id = ?1
+           OR tenant_id IN (
+               SELECT o.tenant_id FROM lease_occupants o
+               JOIN lease_occupants mine ON mine.lease_id = o.lease_id
+               WHERE mine.tenant_id = ?1
+           )
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut entries: Vec<LedgerEntry> = charges
+        .into_iter()
+        .map(
+            |(id, charge_type, amount, date, description, calculation)| LedgerEntry {
+                date,
+                entry_type: if amount < 0.0 { "credit" } else { "charge" }.to_string(),
+                category: charge_type,
+                description,
+                calculation,
+                charge_id: Some(id),
+                payment_id: None,
+                amount,
+                balance: 0.0,
+            },
+        )
+        .chain(
+            payments
+                .into_iter()
+                .map(|(id, amount, date, method, reference)| LedgerEntry {
+                    date,
+                    entry_type: "payment".to_string(),
+                    category: method.unwrap_or_else(|| "payment".to_string()),
+                    description: reference.map(|r| format!("Payment ref {}", r)),
+                    calculation: None,
+                    charge_id: None,
+                    payment_id: Some(id),
+                    amount: -amount,
+                    balance: 0.0,
+                }),
+        )
+        .collect();
+
+    // Charges post before payments on the same instant
+    entries.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.payment_id.is_some().cmp(&b.payment_id.is_some()))
+    });
+
+    let mut opening_balance = 0.0;
+    let mut balance = 0.0;
+    let mut in_range = Vec::new();
+
+    for mut entry in entries {
+        balance += entry.amount;
+        if from.is_some_and(|from| entry.date < from) {
+            opening_balance = balance;
+            continue;
+        }
+        if to.is_some_and(|to| entry.date > to) {
+            break;
+        }
+        entry.balance = round_cents(balance);
+        in_range.push(entry);
+    }
+
+    let sum = |entry_type: &str| {
+        round_cents(
+            in_range
+                .iter()
+                .filter(|e| e.entry_type == entry_type)
+                .map(|e| e.amount.abs())
+                .sum(),
+        )
+    };
+
+    Ok(TenantLedger {
+        tenant_id,
+        opening_balance: round_cents(opening_balance),
+        total_charges: sum("charge"),
+        total_credits: sum("credit"),
+        total_payments: sum("payment"),
+        balance: in_range
+            .last()
+            .map(|e| e.balance)
+            .unwrap_or(round_cents(opening_balance)),
+        entries: in_range,
+    })
+}
@@ -0,0 +1,428 @@
+// Tenant statement PDF
+// Laid out by hand with pdf-writer using the standard Helvetica fonts, which
+// every PDF reader provides, so nothing is embedded and no external tools run
+use crate::models::TenantLedger;
+use chrono::{DateTime, Utc};
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+/// Company details printed at the top of every statement
+#[derive(Debug, Clone)]
+pub struct Letterhead {
+    pub name: String,
+    pub address_lines: Vec<String>,
+    pub contact: Option<String>,
+}
+
+/// Who and what the statement is for
+pub struct StatementParty {
+    pub tenant_name: String,
+    pub tenant_email: Option<String>,
+    pub property_title: String,
+    pub property_address: Vec<String>,
+    pub unit_number: Option<String>,
+}
+
+// US Letter, in points
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 50.0;
+const ROW_HEIGHT: f32 = 16.0;
+
+// Table columns: left edges for text, right edges for amounts
+const COL_DATE: f32 = MARGIN;
+const COL_DESCRIPTION: f32 = MARGIN + 70.0;
+const COL_CHARGES: f32 = 400.0;
+const COL_PAYMENTS: f32 = 482.0;
+const COL_BALANCE: f32 = PAGE_WIDTH - MARGIN;
+const DESCRIPTION_WIDTH: f32 = 250.0;
+
+const REGULAR: Name = Name(b"F1");
+const BOLD: Name = Name(b"F2");
+
+/// Advance widths (per 1000 em) of the printable ASCII characters
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+#[rustfmt::skip]
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Encode text for the fonts' WinAnsiEncoding; anything it can't show
+/// becomes '?'
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        })
+        .collect()
+}
+
+fn text_width(text: &str, font: Name, size: f32) -> f32 {
+    let widths = if font == BOLD {
+        &HELVETICA_BOLD_WIDTHS
+    } else {
+        &HELVETICA_WIDTHS
+    };
+    let units: u32 = win_ansi(text)
+        .iter()
+        .map(|&b| match b {
+            b' '..=b'~' => widths[(b - b' ') as usize] as u32,
+            _ => 556,
+        })
+        .sum();
+
+    units as f32 * size / 1000.0
+}
+
+/// Shorten text with an ellipsis until it fits in `width`
+fn fit(text: &str, font: Name, size: f32, width: f32) -> String {
+    if text_width(text, font, size) <= width {
+        return text.to_string();
+    }
+    let mut chars: Vec<char> = text.chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let candidate = format!("{}...", chars.iter().collect::<String>().trim_end());
+        if text_width(&candidate, font, size) <= width {
+            return candidate;
+        }
+    }
+    String::new()
+}
+
+fn money(amount: f64) -> String {
+    let cents = (amount.abs() * 100.0).round() as i64;
+    let dollars = (cents / 100).to_string();
+    let mut grouped = String::new();
+    for (i, digit) in dollars.chars().enumerate() {
+        if i > 0 && (dollars.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if amount < 0.0 && cents > 0 { "-" } else { "" };
+    format!("{}${}.{:02}", sign, grouped, cents % 100)
+}
+
+fn date(value: DateTime<Utc>) -> String {
+    value.format("%m/%d/%Y").to_string()
+}
+
+fn draw_text(page: &mut Content, x: f32, y: f32, font: Name, size: f32, gray: f32, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let encoded = win_ansi(text);
+    page.set_fill_gray(gray);
+    page.begin_text();
+    page.set_font(font, size);
+    page.next_line(x, y);
+    page.show(Str(&encoded));
+    page.end_text();
+}
+
+/// Pages built up top to bottom, breaking onto a new page (with the table
+/// header repeated) when a row would run into the footer
+struct Layout {
+    pages: Vec<Content>,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Layout {
+            pages: vec![Content::new()],
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn page(&mut self) -> &mut Content {
+        self.pages.last_mut().expect("layout always has a page")
+    }
+
+    fn text(&mut self, x: f32, y: f32, font: Name, size: f32, gray: f32, text: &str) {
+        draw_text(self.page(), x, y, font, size, gray, text);
+    }
+
+    fn text_right(&mut self, right: f32, y: f32, font: Name, size: f32, gray: f32, text: &str) {
+        let x = right - text_width(text, font, size);
+        self.text(x, y, font, size, gray, text);
+    }
+
+    fn rule(&mut self, y: f32, gray: f32, width: f32) {
+        let page = self.page();
+        page.set_stroke_gray(gray);
+        page.set_line_width(width);
+        page.move_to(MARGIN, y);
+        page.line_to(PAGE_WIDTH - MARGIN, y);
+        page.stroke();
+    }
+
+    fn table_header(&mut self) {
+        let y = self.y;
+        let page = self.page();
+        page.set_fill_gray(0.92);
+        page.rect(MARGIN, y - 5.0, PAGE_WIDTH - 2.0 * MARGIN, ROW_HEIGHT + 2.0);
+        page.fill_nonzero();
+
+        self.text(COL_DATE + 4.0, y, BOLD, 9.0, 0.0, "Date");
+        self.text(COL_DESCRIPTION, y, BOLD, 9.0, 0.0, "Description");
+        self.text_right(COL_CHARGES, y, BOLD, 9.0, 0.0, "Charges");
+        self.text_right(COL_PAYMENTS, y, BOLD, 9.0, 0.0, "Payments/Credits");
+        self.text_right(COL_BALANCE - 4.0, y, BOLD, 9.0, 0.0, "Balance");
+        self.y -= ROW_HEIGHT + 6.0;
+    }
+
+    /// Start a new page if `height` more points won't fit
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < MARGIN + 30.0 {
+            self.pages.push(Content::new());
+            self.y = PAGE_HEIGHT - MARGIN;
+            self.table_header();
+        }
+    }
+
+    fn row(&mut self, date: &str, description: &str, detail: Option<&str>, amounts: [&str; 3]) {
+        let height = if detail.is_some() {
+            ROW_HEIGHT + 10.0
+        } else {
+            ROW_HEIGHT
+        };
+        self.reserve(height);
+
+        let y = self.y;
+        self.text(COL_DATE + 4.0, y, REGULAR, 9.0, 0.0, date);
+        let description = fit(description, REGULAR, 9.0, DESCRIPTION_WIDTH);
+        self.text(COL_DESCRIPTION, y, REGULAR, 9.0, 0.0, &description);
+        if let Some(detail) = detail {
+            let detail = fit(detail, REGULAR, 7.0, COL_BALANCE - COL_DESCRIPTION);
+            self.text(COL_DESCRIPTION, y - 10.0, REGULAR, 7.0, 0.4, &detail);
+        }
+        self.text_right(COL_CHARGES, y, REGULAR, 9.0, 0.0, amounts[0]);
+        self.text_right(COL_PAYMENTS, y, REGULAR, 9.0, 0.0, amounts[1]);
+        self.text_right(COL_BALANCE - 4.0, y, REGULAR, 9.0, 0.0, amounts[2]);
+
+        self.y -= height;
+        self.rule(self.y + 11.0, 0.85, 0.5);
+    }
+}
+
+/// Render the tenant's ledger for the period as a statement PDF
+pub fn render_statement(
+    letterhead: &Letterhead,
+    party: &StatementParty,
+    ledger: &TenantLedger,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    generated_at: DateTime<Utc>,
+) -> Vec<u8> {
+    let mut layout = Layout::new();
+    let top = PAGE_HEIGHT - MARGIN;
+
+    // Letterhead on the left, statement title and period on the right
+    layout.text(MARGIN, top - 12.0, BOLD, 16.0, 0.0, &letterhead.name);
+    let mut y = top - 28.0;
+    for line in letterhead.address_lines.iter().chain(&letterhead.contact) {
+        layout.text(MARGIN, y, REGULAR, 9.0, 0.3, line);
+        y -= 11.0;
+    }
+
+    let right = PAGE_WIDTH - MARGIN;
+    layout.text_right(right, top - 12.0, BOLD, 18.0, 0.0, "STATEMENT");
+    layout.text_right(
+        right,
+        top - 28.0,
+        REGULAR,
+        9.0,
+        0.3,
+        &format!("Statement date: {}", date(generated_at)),
+    );
+    let period = match (from, to) {
+        (Some(from), Some(to)) => format!("Period: {} - {}", date(from), date(to)),
+        (Some(from), None) => format!("Period: from {}", date(from)),
+        (None, Some(to)) => format!("Period: through {}", date(to)),
+        (None, None) => "Period: all activity".to_string(),
+    };
+    layout.text_right(right, top - 39.0, REGULAR, 9.0, 0.3, &period);
+
+    y = y.min(top - 50.0) - 8.0;
+    layout.rule(y, 0.0, 1.0);
+
+    // Tenant and property
+    y -= 20.0;
+    layout.text(MARGIN, y, BOLD, 9.0, 0.4, "STATEMENT FOR");
+    layout.text(320.0, y, BOLD, 9.0, 0.4, "PROPERTY");
+    y -= 14.0;
+    let mut left = vec![party.tenant_name.clone()];
+    left.extend(party.tenant_email.clone());
+    let mut right_lines = vec![party.property_title.clone()];
+    right_lines.extend(party.property_address.iter().cloned());
+    right_lines.extend(party.unit_number.as_ref().map(|u| format!("Unit {}", u)));
+    for i in 0..left.len().max(right_lines.len()) {
+        let font = if i == 0 { BOLD } else { REGULAR };
+        if let Some(line) = left.get(i) {
+            layout.text(MARGIN, y, font, 10.0, 0.0, line);
+        }
+        if let Some(line) = right_lines.get(i) {
+            layout.text(320.0, y, font, 10.0, 0.0, line);
+        }
+        y -= 13.0;
+    }
+
+    // Summary
+    y -= 10.0;
+    let summary = [
+        ("Opening balance", ledger.opening_balance),
+        ("Charges", ledger.total_charges),
+        ("Payments", -ledger.total_payments),
+        ("Credits", -ledger.total_credits),
+    ];
+    let box_height = 16.0 * (summary.len() as f32 + 1.0) + 10.0;
+    let page = layout.page();
+    page.set_stroke_gray(0.7);
+    page.set_line_width(0.75);
+    page.rect(320.0, y - box_height + 12.0, right - 320.0, box_height);
+    page.stroke();
+    for (label, amount) in summary {
+        layout.text(330.0, y, REGULAR, 9.0, 0.0, label);
+        layout.text_right(right - 10.0, y, REGULAR, 9.0, 0.0, &money(amount));
+        y -= 16.0;
+    }
+    layout.text(330.0, y, BOLD, 10.0, 0.0, "Balance due");
+    layout.text_right(right - 10.0, y, BOLD, 10.0, 0.0, &money(ledger.balance));
+    y -= box_height - 16.0 * summary.len() as f32;
+
+    // Activity
+    layout.y = y - 10.0;
+    layout.table_header();
+    layout.row(
+        &from.map(date).unwrap_or_default(),
+        "Balance forward",
+        None,
+        ["", "", &money(ledger.opening_balance)],
+    );
+    for entry in &ledger.entries {
+        let (charge, payment) = if entry.amount >= 0.0 {
+            (money(entry.amount), String::new())
+        } else {
+            (String::new(), money(-entry.amount))
+        };
+        let description = match (&entry.description, entry.entry_type.as_str()) {
+            (Some(d), _) => d.clone(),
+            (None, "payment") => format!("Payment ({})", entry.category.replace('_', " ")),
+            (None, _) => entry.category.replace('_', " "),
+        };
+        layout.row(
+            &date(entry.date),
+            &description,
+            entry.calculation.as_deref(),
+            [&charge, &payment, &money(entry.balance)],
+        );
+    }
+
+    layout.reserve(24.0);
+    layout.y -= 6.0;
+    let y = layout.y;
+    layout.text(COL_DESCRIPTION, y, BOLD, 10.0, 0.0, "Balance due");
+    layout.text_right(
+        COL_BALANCE - 4.0,
+        y,
+        BOLD,
+        10.0,
+        0.0,
+        &money(ledger.balance),
+    );
+
+    // Footers now that the page count is known
+    let count = layout.pages.len();
+    for (i, page) in layout.pages.iter_mut().enumerate() {
+        let footer = format!("{} - Page {} of {}", letterhead.name, i + 1, count);
+        let x = right - text_width(&footer, REGULAR, 8.0);
+        draw_text(page, x, MARGIN - 20.0, REGULAR, 8.0, 0.5, &footer);
+    }
+
+    write_pdf(layout.pages, &party.tenant_name)
+}
+
+fn write_pdf(pages: Vec<Content>, tenant_name: &str) -> Vec<u8> {
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let regular_id = Ref::new(3);
+    let bold_id = Ref::new(4);
+    let info_id = Ref::new(5);
+    let page_ids: Vec<Ref> = (0..pages.len() as i32)
+        .map(|i| Ref::new(6 + 2 * i))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(pages.len() as i32);
+    pdf.type1_font(regular_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(bold_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.document_info(info_id)
+        .title(TextStr(&format!("Statement for {}", tenant_name)))
+        .producer(TextStr("Real Estate Dashboard"));
+
+    for (content, page_id) in pages.into_iter().zip(page_ids) {
+        let content_id = Ref::new(page_id.get() + 1);
+        let mut page = pdf.page(page_id);
+        page.parent(page_tree_id)
+            .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+            .contents(content_id);
+        page.resources()
+            .fonts()
+            .pair(REGULAR, regular_id)
+            .pair(BOLD, bold_id);
+        page.finish();
+        pdf.stream(content_id, &content.finish());
+    }
+
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn money_groups_thousands_and_signs_negatives() {
+        assert_eq!(money(1_234_567.89), "$1,234,567.89");
+        assert_eq!(money(-1_234.5), "-$1,234.50");
+        assert_eq!(money(12.0), "$12.00");
+    }
+
+    #[test]
+    fn money_rounds_to_cents() {
+        assert_eq!(money(999.999), "$1,000.00");
+        assert_eq!(money(-0.004), "$0.00");
+    }
+}
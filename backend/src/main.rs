@@ -4,7 +4,7 @@ use axum::{
         HeaderValue, Method,
     },
     routing::{delete, get, post, put},
-    Router,
+    Extension, Router,
 };
 use sqlx::sqlite::SqlitePool;
 use std::net::SocketAddr;
@@ -15,12 +15,23 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 mod config;
 mod db;
 mod error;
+mod jobs;
+mod leasing;
+mod ledger;
 mod models;
+mod occupancy;
 mod routes;
 mod scraper;
+mod sla;
+mod storage;
+mod tax;
 
 use config::Config;
 use error::Result;
+use jobs::lease_renewals::RenewalWindow;
+use routes::attachments::{MaintenanceFiles, PropertyFiles, TenantFiles, UploadLimit};
+use std::sync::Arc;
+use storage::{LocalStore, SharedStore};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -44,6 +55,28 @@ async fn main() -> Result<()> {
 
     tracing::info!("Database migrations completed");
 
+    if config.seed_demo_data {
+        let applied = db::seeds::apply_demo_seeds(&pool).await?;
+        tracing::info!("Applied {} demo data sets", applied);
+    }
+
+    // Start background jobs (preventive maintenance, lease renewals, occupancy, rent, etc.)
+    let renewal_window = RenewalWindow(config.renewal_window_days);
+    let proration_method = config.proration_method;
+    let letterhead = config.letterhead.clone();
+    jobs::spawn(
+        pool.clone(),
+        config.job_interval_secs,
+        renewal_window,
+        proration_method,
+    );
+
+    // Attachment storage
+    let store: SharedStore = Arc::new(LocalStore::new(&config.attachments_dir));
+    // Finish releasing blobs queued before the last shutdown
+    routes::attachments::release_owner_blobs(&pool, &store).await;
+    let upload_limit = UploadLimit(config.max_upload_bytes);
+
     // Configure CORS
     let cors = CorsLayer::new()
         .allow_origin(config.cors_origin.parse::<HeaderValue>().unwrap())
@@ -54,7 +87,12 @@ async fn main() -> Result<()> {
     let app = Router::new()
         .route("/", get(root))
         .route("/health", get(health_check))
-        .nest("/api", api_routes())
+        .nest("/api", api_routes(upload_limit))
+        .layer(Extension(store))
+        .layer(Extension(upload_limit))
+        .layer(Extension(renewal_window))
+        .layer(Extension(proration_method))
+        .layer(Extension(letterhead))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
         .with_state(pool);
@@ -77,7 +115,7 @@ async fn health_check() -> &'static str {
     "OK"
 }
 
-fn api_routes() -> Router<SqlitePool> {
+fn api_routes(upload_limit: UploadLimit) -> Router<SqlitePool> {
     Router::new()
         // Property routes
         .route("/properties", get(routes::properties::list_properties))
@@ -88,12 +126,222 @@ fn api_routes() -> Router<SqlitePool> {
             "/properties/:id",
             delete(routes::properties::delete_property),
         )
+        .route(
+            "/properties/:id/units",
+            get(routes::units::list_property_units),
+        )
+        .route("/properties/:id/units", post(routes::units::create_unit))
+        .route(
+            "/properties/:id/occupancy-history",
+            get(routes::occupancy::list_property_occupancy),
+        )
+        .route(
+            "/properties/:id/cover-photo",
+            put(routes::properties::set_cover_photo),
+        )
+        .route(
+            "/properties/:id/cover-photo",
+            delete(routes::properties::clear_cover_photo),
+        )
+        .route(
+            "/properties/:id/maintenance",
+            get(routes::maintenance::list_property_maintenance),
+        )
+        .route(
+            "/properties/:id/attachments",
+            get(routes::attachments::list_attachments::<PropertyFiles>)
+                .post(routes::attachments::upload_attachments::<PropertyFiles>)
+                .layer(upload_limit.body_limit()),
+        )
+        .route(
+            "/properties/:id/attachments/:attachment_id",
+            get(routes::attachments::download_attachment::<PropertyFiles>)
+                .delete(routes::attachments::delete_attachment::<PropertyFiles>),
+        )
+        .route(
+            "/properties/:id/attachments/:attachment_id/variants/:variant",
+            get(routes::attachments::download_variant::<PropertyFiles>),
+        )
+        .route(
+            "/properties/:id/depreciation",
+            get(routes::tax::get_property_depreciation),
+        )
+        // Unit routes
+        .route("/units/:id", get(routes::units::get_unit))
+        .route("/units/:id", put(routes::units::update_unit))
+        .route("/units/:id", delete(routes::units::delete_unit))
+        .route("/units/:id/leases", get(routes::leases::list_unit_leases))
+        .route(
+            "/units/:id/occupancy-history",
+            get(routes::occupancy::list_unit_occupancy),
+        )
+        // Lease routes
+        .route("/leases", get(routes::leases::list_leases))
+        .route("/leases", post(routes::leases::create_lease))
+        .route("/leases/:id", get(routes::leases::get_lease))
+        .route("/leases/:id", put(routes::leases::update_lease))
+        .route("/leases/:id", delete(routes::leases::delete_lease))
+        .route(
+            "/leases/:id/occupants",
+            post(routes::leases::add_lease_occupant),
+        )
+        .route(
+            "/leases/:id/occupants/:tenant_id",
+            delete(routes::leases::remove_lease_occupant),
+        )
+        .route("/leases/:id/renewal", post(routes::renewals::offer_renewal))
+        .route(
+            "/leases/:id/deposit",
+            get(routes::deposits::get_deposit_ledger),
+        )
+        .route(
+            "/leases/:id/deposit/transactions",
+            post(routes::deposits::create_deposit_transaction),
+        )
+        .route(
+            "/leases/:id/deposit/transactions/:transaction_id",
+            delete(routes::deposits::delete_deposit_transaction),
+        )
+        .route(
+            "/leases/:id/deposit/interest",
+            post(routes::deposits::accrue_deposit_interest),
+        )
+        .route(
+            "/leases/:id/rent-charges",
+            get(routes::ledger::list_lease_rent_charges),
+        )
+        .route(
+            "/leases/:id/rent-charges",
+            post(routes::ledger::generate_rent_charge),
+        )
+        .route(
+            "/leases/:id/rent-schedule",
+            get(routes::escalations::get_rent_schedule),
+        )
+        .route(
+            "/leases/:id/rent-escalations",
+            post(routes::escalations::create_rent_escalation),
+        )
+        .route(
+            "/leases/:id/rent-escalations/:escalation_id",
+            delete(routes::escalations::delete_rent_escalation),
+        )
+        .route(
+            "/leases/:id/move-out",
+            post(routes::deposits::record_move_out),
+        )
+        .route(
+            "/leases/:id/disposition",
+            get(routes::deposits::get_disposition),
+        )
+        // Lease renewal routes
+        .route("/lease-renewals", get(routes::renewals::list_renewals))
+        .route("/lease-renewals/run", post(routes::renewals::run_renewals))
+        .route("/lease-renewals/:id", get(routes::renewals::get_renewal))
+        .route(
+            "/lease-renewals/:id/accept",
+            post(routes::renewals::accept_renewal),
+        )
+        .route(
+            "/lease-renewals/:id/decline",
+            post(routes::renewals::decline_renewal),
+        )
+        .route(
+            "/lease-renewals/:id/sign",
+            post(routes::renewals::sign_renewal),
+        )
+        // CPI index for rent escalations
+        .route("/cpi-index", get(routes::escalations::list_cpi_index))
+        .route(
+            "/cpi-index/:period",
+            put(routes::escalations::upsert_cpi_index),
+        )
+        .route(
+            "/cpi-index/:period",
+            delete(routes::escalations::delete_cpi_index),
+        )
+        // Security deposit routes
+        .route(
+            "/deposit-dispositions",
+            get(routes::deposits::list_dispositions),
+        )
+        .route("/deposit-rules", get(routes::deposits::list_deposit_rules))
+        .route(
+            "/deposit-rules/:state",
+            put(routes::deposits::upsert_deposit_rule),
+        )
         // Tenant routes
         .route("/tenants", get(routes::tenants::list_tenants))
         .route("/tenants", post(routes::tenants::create_tenant))
         .route("/tenants/:id", get(routes::tenants::get_tenant))
         .route("/tenants/:id", put(routes::tenants::update_tenant))
         .route("/tenants/:id", delete(routes::tenants::delete_tenant))
+        .route(
+            "/tenants/:id/leases",
+            get(routes::leases::list_tenant_leases),
+        )
+        .route(
+            "/tenants/:id/deposit-disposition",
+            get(routes::deposits::get_tenant_disposition),
+        )
+        .route(
+            "/tenants/:id/attachments",
+            get(routes::attachments::list_attachments::<TenantFiles>)
+                .post(routes::attachments::upload_attachments::<TenantFiles>)
+                .layer(upload_limit.body_limit()),
+        )
+        .route(
+            "/tenants/:id/attachments/:attachment_id",
+            get(routes::attachments::download_attachment::<TenantFiles>)
+                .delete(routes::attachments::delete_attachment::<TenantFiles>),
+        )
+        .route(
+            "/tenants/:id/attachments/:attachment_id/variants/:variant",
+            get(routes::attachments::download_variant::<TenantFiles>),
+        )
+        .route(
+            "/tenants/:id/portal-link",
+            get(routes::requests::get_portal_link),
+        )
+        .route(
+            "/tenants/:id/portal-link",
+            post(routes::requests::rotate_portal_link),
+        )
+        .route(
+            "/tenants/:id/ledger",
+            get(routes::ledger::get_tenant_ledger),
+        )
+        .route(
+            "/tenants/:id/statement",
+            get(routes::ledger::get_tenant_statement),
+        )
+        .route(
+            "/tenants/:id/charges",
+            get(routes::ledger::list_tenant_charges),
+        )
+        .route(
+            "/tenants/:id/charges",
+            post(routes::ledger::create_tenant_charge),
+        )
+        // Rental application routes
+        .route(
+            "/applications",
+            get(routes::applications::list_rental_applications)
+                .post(routes::applications::create_rental_application),
+        )
+        .route(
+            "/applications/:id",
+            get(routes::applications::get_rental_application)
+                .delete(routes::applications::delete_rental_application),
+        )
+        .route(
+            "/applications/:id/screening",
+            post(routes::applications::screen_rental_application),
+        )
+        .route(
+            "/applications/:id/decision",
+            post(routes::applications::decide_rental_application),
+        )
         // Calendar/Events routes
         .route("/events", get(routes::events::list_events))
         .route("/events", post(routes::events::create_event))
@@ -106,12 +354,182 @@ fn api_routes() -> Router<SqlitePool> {
             "/maintenance",
             post(routes::maintenance::create_maintenance),
         )
+        .route(
+            "/maintenance/:id",
+            get(routes::maintenance::get_maintenance),
+        )
         .route(
             "/maintenance/:id",
             put(routes::maintenance::update_maintenance),
         )
+        .route(
+            "/maintenance/:id",
+            delete(routes::maintenance::delete_maintenance),
+        )
+        .route(
+            "/maintenance/:id/transition",
+            post(routes::maintenance::transition_maintenance),
+        )
+        .route(
+            "/maintenance/:id/history",
+            get(routes::maintenance::get_maintenance_history),
+        )
+        .route(
+            "/maintenance/:id/attachments",
+            get(routes::attachments::list_attachments::<MaintenanceFiles>)
+                .post(routes::attachments::upload_attachments::<MaintenanceFiles>)
+                .layer(upload_limit.body_limit()),
+        )
+        .route(
+            "/maintenance/:id/attachments/:attachment_id",
+            get(routes::attachments::download_attachment::<MaintenanceFiles>)
+                .delete(routes::attachments::delete_attachment::<MaintenanceFiles>),
+        )
+        .route(
+            "/maintenance/:id/attachments/:attachment_id/variants/:variant",
+            get(routes::attachments::download_variant::<MaintenanceFiles>),
+        )
+        .route(
+            "/maintenance/:id/line-items",
+            get(routes::line_items::list_line_items),
+        )
+        .route(
+            "/maintenance/:id/line-items",
+            post(routes::line_items::create_line_item),
+        )
+        .route(
+            "/maintenance/:id/line-items/:item_id",
+            delete(routes::line_items::delete_line_item),
+        )
+        .route(
+            "/maintenance/:id/costs",
+            get(routes::line_items::get_cost_summary),
+        )
+        .route(
+            "/maintenance/:id/charge-back",
+            post(routes::line_items::charge_back),
+        )
+        // Tenant-submitted maintenance request routes
+        .route(
+            "/maintenance-requests",
+            get(routes::requests::list_maintenance_requests),
+        )
+        .route(
+            "/maintenance-requests/:id",
+            get(routes::requests::get_maintenance_request),
+        )
+        .route(
+            "/maintenance-requests/:id/triage",
+            post(routes::requests::triage_maintenance_request),
+        )
+        .route(
+            "/portal/:token/maintenance-requests",
+            get(routes::requests::list_portal_requests),
+        )
+        .route(
+            "/portal/:token/maintenance-requests",
+            post(routes::requests::submit_portal_request),
+        )
+        .route(
+            "/portal/:token/maintenance-requests/:id",
+            get(routes::requests::get_portal_request),
+        )
+        // Preventive maintenance plan routes
+        .route("/maintenance-plans", get(routes::plans::list_plans))
+        .route("/maintenance-plans", post(routes::plans::create_plan))
+        .route("/maintenance-plans/run", post(routes::plans::run_plans))
+        .route("/maintenance-plans/:id", get(routes::plans::get_plan))
+        .route("/maintenance-plans/:id", put(routes::plans::update_plan))
+        .route("/maintenance-plans/:id", delete(routes::plans::delete_plan))
+        // Maintenance SLA routes
+        .route(
+            "/maintenance/:id/sla",
+            get(routes::sla::get_maintenance_sla),
+        )
+        .route(
+            "/maintenance/sla/targets",
+            get(routes::sla::list_sla_targets),
+        )
+        .route(
+            "/maintenance/sla/targets/:priority",
+            put(routes::sla::update_sla_target),
+        )
+        .route(
+            "/maintenance/sla/breaches",
+            get(routes::sla::list_sla_breaches),
+        )
+        .route(
+            "/maintenance/sla/compliance",
+            get(routes::sla::get_sla_compliance),
+        )
         // Market data routes
         .route("/market/trends", get(routes::market::get_trends))
         .route("/market/analytics", get(routes::market::get_analytics))
+        // Portfolio analytics routes
+        .route("/analytics/units", get(routes::units::get_unit_analytics))
+        .route(
+            "/analytics/buildings",
+            get(routes::units::get_building_analytics),
+        )
+        .route(
+            "/analytics/occupancy",
+            get(routes::occupancy::get_occupancy_report),
+        )
+        .route(
+            "/analytics/vacancy",
+            get(routes::occupancy::get_vacancy_report),
+        )
+        .route("/occupancy/run", post(routes::occupancy::run_occupancy))
+        .route("/vacancies", get(routes::occupancy::list_vacancies))
         .route("/market/scrape", post(routes::market::trigger_scrape))
+        // Contractor routes
+        .route("/contractors", get(routes::contractors::list_contractors))
+        .route("/contractors", post(routes::contractors::create_contractor))
+        .route(
+            "/contractors/stats",
+            get(routes::contractors::list_contractor_stats),
+        )
+        .route("/contractors/:id", get(routes::contractors::get_contractor))
+        .route(
+            "/contractors/:id",
+            put(routes::contractors::update_contractor),
+        )
+        .route(
+            "/contractors/:id",
+            delete(routes::contractors::delete_contractor),
+        )
+        .route(
+            "/contractors/:id/stats",
+            get(routes::contractors::get_contractor_stats),
+        )
+        // Rent payment routes
+        .route("/payments", get(routes::payments::list_payments))
+        .route("/payments", post(routes::payments::create_payment))
+        .route("/payments/:id", get(routes::payments::get_payment))
+        .route("/payments/:id", delete(routes::payments::delete_payment))
+        .route("/rent-charges/run", post(routes::ledger::run_rent_charges))
+        // Expense routes
+        .route("/expenses", get(routes::expenses::list_expenses))
+        .route("/expenses", post(routes::expenses::create_expense))
+        .route("/expenses/:id", get(routes::expenses::get_expense))
+        .route("/expenses/:id", put(routes::expenses::update_expense))
+        .route("/expenses/:id", delete(routes::expenses::delete_expense))
+        // Loan routes
+        .route("/loans", get(routes::loans::list_loans))
+        .route("/loans", post(routes::loans::create_loan))
+        .route("/loans/:id", get(routes::loans::get_loan))
+        .route("/loans/:id", delete(routes::loans::delete_loan))
+        // Tax reporting routes
+        .route(
+            "/tax/depreciation",
+            get(routes::tax::get_portfolio_depreciation),
+        )
+        // Report routes
+        .route("/reports/schedule-e", get(routes::reports::get_schedule_e))
+        .route("/reports/1099-nec", get(routes::reports::get_1099_nec))
+        .route("/reports/rent-roll", get(routes::reports::get_rent_roll))
+        .route(
+            "/reports/cash-flow",
+            get(routes::reports::get_cash_flow_projection),
+        )
 }
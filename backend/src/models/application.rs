@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RentalApplication {
+    pub id: i64,
+    pub property_id: i64,
+    pub unit_id: Option<i64>,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub date_of_birth: Option<DateTime<Utc>>,
+    pub current_address: Option<String>,
+    pub monthly_income: f64,
+    pub employer: Option<String>,
+    pub job_title: Option<String>,
+    pub employment_start: Option<DateTime<Utc>>,
+    pub desired_move_in: DateTime<Utc>,
+    pub desired_rent: Option<f64>,
+    pub occupants: i32,
+    pub pets: Option<String>,
+    pub notes: Option<String>,
+    pub status: String, // submitted, screened, approved, rejected
+    pub credit_score: Option<i32>,
+    pub background_check: Option<String>, // clear, flagged, pending
+    pub eviction_history: Option<bool>,
+    pub income_verified: bool,
+    pub screened_by: Option<String>,
+    pub screening_notes: Option<String>,
+    pub screened_at: Option<DateTime<Utc>>,
+    pub decided_by: Option<String>,
+    pub decision_notes: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub tenant_id: Option<i64>,
+    pub lease_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApplicationReference {
+    pub id: i64,
+    pub application_id: i64,
+    pub name: String,
+    pub relationship: String, // landlord, employer, personal
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub verified: bool,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApplicationReference {
+    pub name: String,
+    pub relationship: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Monthly rent against monthly income
+#[derive(Debug, Serialize)]
+pub struct IncomeCheck {
+    pub monthly_rent: Option<f64>,
+    pub monthly_income: f64,
+    pub rent_to_income: Option<f64>, // percent of income going to rent
+    pub income_multiple: Option<f64>, // income as a multiple of rent
+    pub meets_requirement: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RentalApplicationDetail {
+    #[serde(flatten)]
+    pub application: RentalApplication,
+    pub references: Vec<ApplicationReference>,
+    pub income_check: IncomeCheck,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRentalApplication {
+    pub property_id: i64,
+    pub unit_id: Option<i64>,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub date_of_birth: Option<DateTime<Utc>>,
+    pub current_address: Option<String>,
+    pub monthly_income: f64,
+    pub employer: Option<String>,
+    pub job_title: Option<String>,
+    pub employment_start: Option<DateTime<Utc>>,
+    pub desired_move_in: DateTime<Utc>,
+    pub desired_rent: Option<f64>,
+    pub occupants: Option<i32>,
+    pub pets: Option<String>,
+    pub notes: Option<String>,
+    pub references: Option<Vec<CreateApplicationReference>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordScreening {
+    pub credit_score: Option<i32>,
+    pub background_check: Option<String>,
+    pub eviction_history: Option<bool>,
+    pub income_verified: Option<bool>,
+    pub verified_reference_ids: Option<Vec<i64>>,
+    pub screened_by: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Staff decision on an application. Approving creates the tenant and a
+/// pending lease from the optional terms; rejecting only records the notes.
+#[derive(Debug, Deserialize)]
+pub struct DecideRentalApplication {
+    pub decision: String, // approve, reject
+    pub unit_id: Option<i64>,
+    pub lease_start: Option<DateTime<Utc>>,
+    pub lease_end: Option<DateTime<Utc>>,
+    pub term_months: Option<u32>,
+    pub monthly_rent: Option<f64>,
+    pub deposit_amount: Option<f64>,
+    pub decided_by: Option<String>,
+    pub notes: Option<String>,
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+pub const ATTACHMENT_CATEGORIES: &[&str] = &["photo", "lease", "invoice", "inspection", "other"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Attachment {
+    pub id: i64,
+    pub entity_type: String, // property, tenant, maintenance
+    pub entity_id: i64,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub category: String,
+    pub description: Option<String>,
+    pub uploaded_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AttachmentVariant {
+    pub id: i64,
+    pub attachment_id: i64,
+    pub variant: String, // thumb, web
+    pub content_type: String,
+    pub width: i64,
+    pub height: i64,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetCoverPhoto {
+    pub attachment_id: i64,
+}
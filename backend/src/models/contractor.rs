@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Contractor {
+    pub id: i64,
+    pub name: String,
+    pub entity_type: String,   // individual, llc, partnership, corporation
+    pub trade: Option<String>, // plumbing, electrical, hvac, roofing, painting, general
+    pub contact_name: Option<String>,
+    pub tax_id: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip_code: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub license_number: Option<String>,
+    pub license_expiry: Option<DateTime<Utc>>,
+    pub insurance_expiry: Option<DateTime<Utc>>,
+    pub hourly_rate: Option<f64>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateContractor {
+    pub name: String,
+    pub entity_type: Option<String>,
+    pub trade: Option<String>,
+    pub contact_name: Option<String>,
+    pub tax_id: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip_code: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub license_number: Option<String>,
+    pub license_expiry: Option<DateTime<Utc>>,
+    pub insurance_expiry: Option<DateTime<Utc>>,
+    pub hourly_rate: Option<f64>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateContractor {
+    pub name: Option<String>,
+    pub entity_type: Option<String>,
+    pub trade: Option<String>,
+    pub contact_name: Option<String>,
+    pub tax_id: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip_code: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub license_number: Option<String>,
+    pub license_expiry: Option<DateTime<Utc>>,
+    pub insurance_expiry: Option<DateTime<Utc>>,
+    pub hourly_rate: Option<f64>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ContractorStats {
+    pub contractor_id: i64,
+    pub name: String,
+    pub trade: Option<String>,
+    pub hourly_rate: Option<f64>,
+    pub jobs_total: i64,
+    pub jobs_completed: i64,
+    pub jobs_cancelled: i64,
+    pub total_cost: f64,
+    pub average_cost: Option<f64>,
+    pub average_days_to_complete: Option<f64>,
+    pub license_expired: bool,
+    pub insurance_expired: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VendorPaymentSummary {
+    pub contractor_id: i64,
+    pub name: String,
+    pub entity_type: String,
+    pub tax_id: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip_code: Option<String>,
+    pub maintenance_total: f64,
+    pub expense_total: f64,
+    pub total_paid: f64,
+    pub missing_tax_id: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Form1099Report {
+    pub tax_year: i32,
+    pub threshold: f64,
+    pub vendors: Vec<VendorPaymentSummary>,
+}
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DepositRule {
+    pub state: String, // '*' is the default
+    pub return_days: i32,
+    pub interest_rate: Option<f64>,
+    pub notes: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertDepositRule {
+    pub return_days: i32,
+    pub interest_rate: Option<f64>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DepositTransaction {
+    pub id: i64,
+    pub lease_id: i64,
+    pub entry_type: String, // received, interest, deduction, refund, transfer_in, transfer_out
+    pub amount: f64,
+    pub description: Option<String>,
+    pub maintenance_id: Option<i64>,
+    pub transaction_date: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDepositTransaction {
+    pub entry_type: String,
+    pub amount: f64,
+    pub description: Option<String>,
+    pub maintenance_id: Option<i64>,
+    pub transaction_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccrueDepositInterest {
+    pub through: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepositLedger {
+    pub lease_id: i64,
+    pub required: Option<f64>, // the lease's deposit_amount
+    pub received: f64,
+    pub interest: f64,
+    pub deductions: f64,
+    pub refunded: f64,
+    pub transferred: f64, // net moved in from (positive) or out to a renewal
+    pub balance: f64,
+    pub transactions: Vec<DepositTransaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DepositDisposition {
+    pub id: i64,
+    pub lease_id: i64,
+    pub move_out_date: DateTime<Utc>,
+    pub return_deadline: DateTime<Utc>,
+    pub forwarding_address: Option<String>,
+    pub status: String, // open, settled
+    pub settled_at: Option<DateTime<Utc>>,
+    pub event_id: Option<i64>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordMoveOut {
+    pub move_out_date: DateTime<Utc>,
+    pub forwarding_address: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DispositionDeduction {
+    pub date: DateTime<Utc>,
+    pub reason: Option<String>,
+    pub amount: f64,
+    pub maintenance_id: Option<i64>,
+    pub maintenance_title: Option<String>,
+}
+
+/// Itemised statement of what happened to a tenant's deposit at move-out
+#[derive(Debug, Serialize)]
+pub struct DispositionStatement {
+    pub disposition: DepositDisposition,
+    pub property_title: String,
+    pub property_address: String,
+    pub unit_number: Option<String>,
+    pub tenants: Vec<String>,
+    pub deposit_received: f64,
+    pub interest: f64,
+    pub deductions: Vec<DispositionDeduction>,
+    pub total_deductions: f64,
+    pub refund_due: f64, // what the tenant is owed back
+    pub refunded: f64,
+    pub outstanding: f64,         // refund still to be paid
+    pub days_until_deadline: i64, // negative once overdue
+    pub overdue: bool,
+}
+
+/// A disposition on the deadline tracker
+#[derive(Debug, Serialize)]
+pub struct DispositionDeadline {
+    #[serde(flatten)]
+    pub disposition: DepositDisposition,
+    pub property_id: i64,
+    pub tenants: Vec<String>,
+    pub outstanding: f64,
+    pub days_until_deadline: i64,
+    pub overdue: bool,
+}
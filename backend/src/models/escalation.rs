@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RentEscalation {
+    pub id: i64,
+    pub lease_id: i64,
+    pub escalation_type: String, // fixed, percent, cpi
+    pub effective_date: DateTime<Utc>,
+    pub amount: Option<f64>,  // new monthly rent for fixed steps
+    pub percent: Option<f64>, // increase for percent escalators
+    pub interval_months: Option<i32>,
+    pub cpi_floor: Option<f64>,
+    pub cpi_cap: Option<f64>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRentEscalation {
+    pub escalation_type: String,
+    pub effective_date: DateTime<Utc>,
+    pub amount: Option<f64>,
+    pub percent: Option<f64>,
+    pub interval_months: Option<i32>,
+    pub cpi_floor: Option<f64>,
+    pub cpi_cap: Option<f64>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CpiIndex {
+    pub period: String, // YYYY-MM
+    pub index_value: f64,
+    pub notes: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertCpiIndex {
+    pub index_value: f64,
+    pub notes: Option<String>,
+}
+
+/// A change in the rent in effect
+#[derive(Debug, Clone, Serialize)]
+pub struct RentStep {
+    pub effective_date: DateTime<Utc>,
+    pub monthly_rent: f64,
+    pub change: f64,
+    pub escalation_id: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RentSchedule {
+    pub lease_id: i64,
+    pub base_rent: f64,
+    pub current_rent: f64,
+    pub escalations: Vec<RentEscalation>,
+    pub steps: Vec<RentStep>,
+    pub warnings: Vec<String>, // CPI adjustments skipped for want of an index
+}
+
+#[derive(Debug, Serialize)]
+pub struct CashFlowMonth {
+    pub month: String, // YYYY-MM
+    pub scheduled_rent: f64,
+    pub debt_service: f64,
+    pub net: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CashFlowProjection {
+    pub property_id: Option<i64>,
+    pub months: Vec<CashFlowMonth>,
+    pub scheduled_rent: f64,
+    pub debt_service: f64,
+    pub net: f64,
+}
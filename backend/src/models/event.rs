@@ -7,8 +7,9 @@ pub struct CalendarEvent {
     pub id: i64,
     pub title: String,
     pub description: Option<String>,
-    pub event_type: String, // maintenance, rent_due, inspection, lease_renewal
+    pub event_type: String, // maintenance, rent_due, inspection, lease_renewal, deposit_return
     pub property_id: Option<i64>,
+    pub unit_id: Option<i64>,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
     pub reminder_minutes: Option<i32>,
@@ -23,6 +24,7 @@ pub struct CreateEvent {
     pub description: Option<String>,
     pub event_type: String,
     pub property_id: Option<i64>,
+    pub unit_id: Option<i64>,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
     pub reminder_minutes: Option<i32>,
@@ -34,6 +36,7 @@ pub struct UpdateEvent {
     pub description: Option<String>,
     pub event_type: Option<String>,
     pub property_id: Option<i64>,
+    pub unit_id: Option<i64>,
     pub start_time: Option<DateTime<Utc>>,
     pub end_time: Option<DateTime<Utc>>,
     pub reminder_minutes: Option<i32>,
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Expense categories, one per Schedule E expense line
+pub const EXPENSE_CATEGORIES: &[&str] = &[
+    "advertising",
+    "auto_travel",
+    "cleaning_maintenance",
+    "commissions",
+    "insurance",
+    "legal_professional",
+    "management_fees",
+    "mortgage_interest",
+    "other_interest",
+    "repairs",
+    "supplies",
+    "taxes",
+    "utilities",
+    "other",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Expense {
+    pub id: i64,
+    pub property_id: i64,
+    pub category: String,
+    pub amount: f64,
+    pub expense_date: DateTime<Utc>,
+    pub payee: Option<String>,
+    pub contractor_id: Option<i64>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateExpense {
+    pub property_id: i64,
+    pub category: String,
+    pub amount: f64,
+    pub expense_date: DateTime<Utc>,
+    pub payee: Option<String>,
+    pub contractor_id: Option<i64>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateExpense {
+    pub category: Option<String>,
+    pub amount: Option<f64>,
+    pub expense_date: Option<DateTime<Utc>>,
+    pub payee: Option<String>,
+    pub contractor_id: Option<i64>,
+    pub description: Option<String>,
+}
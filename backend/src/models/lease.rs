@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Lease {
+    pub id: i64,
+    pub property_id: i64,
+    pub unit_id: Option<i64>,
+    pub lease_type: String, // fixed, month_to_month
+    pub start_date: DateTime<Utc>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub monthly_rent: f64,
+    pub deposit_amount: Option<f64>,
+    pub status: String, // pending, active, ended
+    pub previous_lease_id: Option<i64>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub rent_due_day: i32, // day of the month rent is charged, 1-28
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LeaseOccupant {
+    pub tenant_id: i64,
+    pub role: String, // primary, co_tenant, occupant
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaseDetail {
+    #[serde(flatten)]
+    pub lease: Lease,
+    pub occupants: Vec<LeaseOccupant>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddLeaseOccupant {
+    pub tenant_id: i64,
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLease {
+    pub property_id: i64,
+    pub unit_id: Option<i64>,
+    pub lease_type: Option<String>,
+    pub start_date: DateTime<Utc>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub monthly_rent: f64,
+    pub deposit_amount: Option<f64>,
+    pub rent_due_day: Option<i32>,
+    pub status: Option<String>,
+    pub notes: Option<String>,
+    pub occupants: Vec<AddLeaseOccupant>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateLease {
+    pub unit_id: Option<i64>,
+    pub lease_type: Option<String>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub monthly_rent: Option<f64>,
+    pub deposit_amount: Option<f64>,
+    pub rent_due_day: Option<i32>,
+    pub status: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// The existing lease a new or changed lease would double-book
+#[derive(Debug, Serialize)]
+pub struct LeaseConflict {
+    pub lease_id: i64,
+    pub unit_id: Option<i64>,
+    pub status: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: Option<DateTime<Utc>>,
+    pub tenant_id: Option<i64>,
+    pub tenant_name: Option<String>,
+}
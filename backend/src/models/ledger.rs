@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TenantCharge {
+    pub id: i64,
+    pub tenant_id: i64,
+    pub property_id: i64,
+    pub charge_type: String, // rent, late_fee, maintenance, utility, credit, other
+    pub amount: f64,         // credits are negative
+    pub charge_date: DateTime<Utc>,
+    pub description: Option<String>,
+    pub maintenance_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub lease_id: Option<i64>,
+    pub billing_period: Option<String>, // YYYY-MM, for rent generated from a lease
+    pub calculation: Option<String>,    // how a generated rent charge was worked out
+    pub event_id: Option<i64>,          // rent_due event for a generated rent charge
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTenantCharge {
+    pub charge_type: String,
+    pub amount: Option<f64>, // rent defaults to the lease rent in effect on charge_date
+    pub charge_date: DateTime<Utc>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub date: DateTime<Utc>,
+    pub entry_type: String, // charge, credit, payment
+    pub category: String,
+    pub description: Option<String>,
+    pub calculation: Option<String>,
+    pub charge_id: Option<i64>,
+    pub payment_id: Option<i64>,
+    pub amount: f64, // positive increases the balance owed
+    pub balance: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TenantLedger {
+    pub tenant_id: i64,
+    pub opening_balance: f64,
+    pub entries: Vec<LedgerEntry>,
+    pub total_charges: f64,
+    pub total_credits: f64,
+    pub total_payments: f64,
+    pub balance: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GenerateRentCharge {
+    pub period: String,         // YYYY-MM
+    pub method: Option<String>, // actual_days, thirty_day, 365_day
+}
+
+#[derive(Debug, Serialize)]
+pub struct RentChargeRunSummary {
+    pub period: String,
+    pub leases_checked: i64,
+    pub charges_posted: i64,
+    pub charged: f64,
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Loan {
+    pub id: i64,
+    pub property_id: i64,
+    pub lender: String,
+    pub original_principal: f64,
+    pub interest_rate: f64, // annual percentage, e.g. 6.5
+    pub term_months: i32,
+    pub first_payment_date: DateTime<Utc>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLoan {
+    pub property_id: i64,
+    pub lender: String,
+    pub original_principal: f64,
+    pub interest_rate: f64,
+    pub term_months: i32,
+    pub first_payment_date: DateTime<Utc>,
+    pub notes: Option<String>,
+}
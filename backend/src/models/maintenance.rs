@@ -6,15 +6,19 @@ use sqlx::FromRow;
 pub struct MaintenanceRecord {
     pub id: i64,
     pub property_id: i64,
+    pub unit_id: Option<i64>,
     pub title: String,
     pub description: Option<String>,
-    pub priority: String, // low, medium, high, urgent
-    pub status: String,   // pending, in_progress, completed, cancelled
-    pub cost: Option<f64>,
+    pub priority: String,  // low, medium, high, urgent
+    pub status: String,    // pending, scheduled, in_progress, completed, cancelled
+    pub cost: Option<f64>, // actual total; derived from actual line items when present
+    pub estimated_cost: Option<f64>,
     pub scheduled_date: Option<DateTime<Utc>>,
     pub completed_date: Option<DateTime<Utc>>,
     pub contractor: Option<String>,
+    pub contractor_id: Option<i64>,
     pub notes: Option<String>,
+    pub capital_improvement: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -22,25 +26,104 @@ pub struct MaintenanceRecord {
 #[derive(Debug, Deserialize)]
 pub struct CreateMaintenance {
     pub property_id: i64,
+    pub unit_id: Option<i64>,
     pub title: String,
     pub description: Option<String>,
     pub priority: String,
     pub status: String,
     pub cost: Option<f64>,
+    pub estimated_cost: Option<f64>,
     pub scheduled_date: Option<DateTime<Utc>>,
     pub contractor: Option<String>,
+    pub contractor_id: Option<i64>,
     pub notes: Option<String>,
+    pub capital_improvement: Option<bool>,
+    pub changed_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateMaintenance {
+    pub unit_id: Option<i64>,
     pub title: Option<String>,
     pub description: Option<String>,
     pub priority: Option<String>,
     pub status: Option<String>,
     pub cost: Option<f64>,
+    pub estimated_cost: Option<f64>,
     pub scheduled_date: Option<DateTime<Utc>>,
     pub completed_date: Option<DateTime<Utc>>,
     pub contractor: Option<String>,
+    pub contractor_id: Option<i64>,
     pub notes: Option<String>,
+    pub capital_improvement: Option<bool>,
+    pub changed_by: Option<String>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceTransition {
+    pub status: String,
+    pub completed_date: Option<DateTime<Utc>>,
+    pub changed_by: Option<String>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MaintenanceStatusChange {
+    pub id: i64,
+    pub maintenance_id: i64,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub changed_by: Option<String>,
+    pub reason: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MaintenanceLineItem {
+    pub id: i64,
+    pub maintenance_id: i64,
+    pub kind: String,      // estimate, actual
+    pub item_type: String, // labor, materials, fee
+    pub description: String,
+    pub quantity: f64,
+    pub unit_cost: f64,
+    pub amount: f64,
+    pub invoice_number: Option<String>,
+    pub tenant_chargeable: bool,
+    pub tenant_charge_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLineItem {
+    pub kind: Option<String>,
+    pub item_type: String,
+    pub description: String,
+    pub quantity: Option<f64>,
+    pub unit_cost: f64,
+    pub invoice_number: Option<String>,
+    pub tenant_chargeable: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaintenanceCostSummary {
+    pub maintenance_id: i64,
+    pub estimate_total: f64,
+    pub actual_total: f64,
+    pub variance: f64,
+    pub labor: f64,
+    pub materials: f64,
+    pub fees: f64,
+    pub tenant_chargeable: f64,
+    pub owner_paid: f64,
+    pub charged_back: f64,
+    pub invoice_numbers: Vec<String>,
+    pub line_items: Vec<MaintenanceLineItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChargeBackRequest {
+    pub tenant_id: Option<i64>,
+    pub charge_date: Option<DateTime<Utc>>,
 }
@@ -1,11 +1,47 @@
+pub mod application;
+pub mod attachment;
+pub mod contractor;
+pub mod deposit;
+pub mod escalation;
 pub mod event;
+pub mod expense;
+pub mod lease;
+pub mod ledger;
+pub mod loan;
 pub mod maintenance;
 pub mod market;
+pub mod occupancy;
+pub mod payment;
+pub mod plan;
 pub mod property;
+pub mod renewal;
+pub mod rent_roll;
+pub mod request;
+pub mod sla;
+pub mod tax;
 pub mod tenant;
+pub mod unit;
 
+pub use application::*;
+pub use attachment::*;
+pub use contractor::*;
+pub use deposit::*;
+pub use escalation::*;
 pub use event::*;
+pub use expense::*;
+pub use lease::*;
+pub use ledger::*;
+pub use loan::*;
 pub use maintenance::*;
 pub use market::*;
+pub use occupancy::*;
+pub use payment::*;
+pub use plan::*;
 pub use property::*;
+pub use renewal::*;
+pub use rent_roll::*;
+pub use request::*;
+pub use sla::*;
+pub use tax::*;
 pub use tenant::*;
+pub use unit::*;
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OccupancySpan {
+    pub id: i64,
+    pub unit_id: i64,
+    pub property_id: i64,
+    pub status: String, // occupied, vacant, maintenance
+    pub lease_id: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OccupancyRunSummary {
+    pub leases_started: i64,
+    pub leases_ended: i64,
+    pub units_changed: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PropertyOccupancy {
+    pub property_id: i64,
+    pub property_title: String,
+    pub total_units: i64,
+    pub unit_days: f64,
+    pub occupied_unit_days: f64,
+    pub physical_occupancy: f64, // percent of unit-days occupied
+    pub potential_rent: f64,     // market rent for every unit-day
+    pub scheduled_rent: f64,     // lease rent for occupied unit-days
+    pub collected_rent: f64,     // rent payments received in the period
+    pub economic_occupancy: f64, // collected as a percent of potential
+}
+
+#[derive(Debug, Serialize)]
+pub struct OccupancyReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub days: f64,
+    pub total_units: i64,
+    pub unit_days: f64,
+    pub occupied_unit_days: f64,
+    pub physical_occupancy: f64,
+    pub potential_rent: f64,
+    pub scheduled_rent: f64,
+    pub collected_rent: f64,
+    pub economic_occupancy: f64,
+    pub properties: Vec<PropertyOccupancy>,
+}
+
+/// Time a unit spent without a lease, from move-out to the next move-in.
+/// Maintenance spans in between count as vacant.
+#[derive(Debug, Clone, Serialize)]
+pub struct VacancyPeriod {
+    pub unit_id: i64,
+    pub unit_number: String,
+    pub property_id: i64,
+    pub property_title: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>, // None while still vacant
+    pub days_vacant: f64,
+    pub market_rent: Option<f64>,
+    pub lost_rent: f64,
+    pub previous_lease_id: Option<i64>,
+    pub next_lease_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VacancyReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub completed_periods: i64,   // vacancies that ended within the period
+    pub average_days_vacant: f64, // across those completed vacancies
+    pub vacant_unit_days: f64,
+    pub lost_rent: f64, // market rent forgone on vacant days within the period
+    pub currently_vacant: Vec<VacancyPeriod>, // longest vacancy first
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RentPayment {
+    pub id: i64,
+    pub tenant_id: Option<i64>,
+    pub property_id: i64,
+    pub amount: f64,
+    pub payment_date: DateTime<Utc>,
+    pub payment_method: Option<String>, // check, ach, cash, card
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRentPayment {
+    pub tenant_id: Option<i64>,
+    pub property_id: i64,
+    pub amount: f64,
+    pub payment_date: DateTime<Utc>,
+    pub payment_method: Option<String>,
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+}
@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MaintenancePlan {
+    pub id: i64,
+    pub property_id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: String,
+    pub contractor_id: Option<i64>,
+    pub estimated_cost: Option<f64>,
+    pub interval_value: i32,
+    pub interval_unit: String, // days, weeks, months, years
+    pub lead_days: i32,
+    pub next_due_date: DateTime<Utc>,
+    pub active: bool,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMaintenancePlan {
+    pub property_id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<String>,
+    pub contractor_id: Option<i64>,
+    pub estimated_cost: Option<f64>,
+    pub interval_value: i32,
+    pub interval_unit: String,
+    pub lead_days: Option<i32>,
+    pub next_due_date: DateTime<Utc>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMaintenancePlan {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub priority: Option<String>,
+    pub contractor_id: Option<i64>,
+    pub estimated_cost: Option<f64>,
+    pub interval_value: Option<i32>,
+    pub interval_unit: Option<String>,
+    pub lead_days: Option<i32>,
+    pub next_due_date: Option<DateTime<Utc>>,
+    pub active: Option<bool>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlanRunSummary {
+    pub plans_checked: i64,
+    pub work_orders_created: i64,
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use super::unit::CreateUnit;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Property {
     pub id: i64,
@@ -19,10 +21,28 @@ pub struct Property {
     pub monthly_rent: Option<f64>,
     pub status: String, // occupied, vacant, maintenance
     pub notes: Option<String>,
+    pub land_value: Option<f64>,
+    pub placed_in_service_date: Option<DateTime<Utc>>,
+    pub cover_attachment_id: Option<i64>,
+    #[sqlx(skip)]
+    pub cover_photo_url: Option<String>, // thumbnail of the cover photo
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Property {
+    /// Fill in `cover_photo_url` from the cover attachment
+    pub fn with_cover_url(mut self) -> Self {
+        self.cover_photo_url = self.cover_attachment_id.map(|attachment_id| {
+            format!(
+                "/api/properties/{}/attachments/{}/variants/thumb",
+                self.id, attachment_id
+            )
+        });
+        self
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateProperty {
     pub title: String,
@@ -39,6 +59,9 @@ pub struct CreateProperty {
     pub monthly_rent: Option<f64>,
     pub status: String,
     pub notes: Option<String>,
+    pub land_value: Option<f64>,
+    pub placed_in_service_date: Option<DateTime<Utc>>,
+    pub units: Option<Vec<CreateUnit>>, // defaults to a single unit mirroring the property
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,4 +80,6 @@ pub struct UpdateProperty {
     pub monthly_rent: Option<f64>,
     pub status: Option<String>,
     pub notes: Option<String>,
+    pub land_value: Option<f64>,
+    pub placed_in_service_date: Option<DateTime<Utc>>,
 }
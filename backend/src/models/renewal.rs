@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use super::lease::LeaseDetail;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LeaseRenewal {
+    pub id: i64,
+    pub lease_id: i64,
+    pub status: String, // pending, offered, accepted, declined, signed, expired
+    pub proposed_rent: Option<f64>,
+    pub proposed_lease_type: Option<String>,
+    pub proposed_term_months: Option<i32>,
+    pub proposed_start_date: Option<DateTime<Utc>>,
+    pub proposed_end_date: Option<DateTime<Utc>>,
+    pub new_lease_id: Option<i64>,
+    pub event_id: Option<i64>,
+    pub offered_at: Option<DateTime<Utc>>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub signed_at: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaseRenewalDetail {
+    #[serde(flatten)]
+    pub renewal: LeaseRenewal,
+    pub lease: LeaseDetail,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OfferRenewal {
+    pub proposed_rent: f64,
+    pub lease_type: Option<String>,
+    // Required for fixed-term offers
+    pub term_months: Option<i32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenewalResponse {
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenewalRunSummary {
+    pub window_days: i64,
+    pub leases_checked: i64,
+    pub renewals_created: i64,
+    pub leases_started: i64,
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct RentRollRow {
+    pub property_id: i64,
+    pub property_title: String,
+    pub address: String,
+    pub unit_id: i64,
+    pub unit_number: String,
+    pub bedrooms: Option<i32>,
+    pub bathrooms: Option<f32>,
+    pub square_feet: Option<i32>,
+    pub status: String, // occupied or vacant as of the report date
+    pub lease_id: Option<i64>,
+    pub lease_type: Option<String>,
+    pub tenants: Vec<String>,
+    pub lease_start: Option<DateTime<Utc>>,
+    pub lease_end: Option<DateTime<Utc>>,
+    pub market_rent: Option<f64>,
+    pub monthly_rent: Option<f64>,
+    pub deposit_held: Option<f64>,
+    pub balance_due: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RentRollTotals {
+    pub units: i64,
+    pub occupied_units: i64,
+    pub vacant_units: i64,
+    pub occupancy_rate: f64, // percent of units occupied
+    pub market_rent: f64,
+    pub monthly_rent: f64,
+    pub loss_to_lease: f64, // market rent above contract rent on occupied units
+    pub deposits_held: f64,
+    pub balance_due: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RentRoll {
+    pub as_of: DateTime<Utc>,
+    pub rows: Vec<RentRollRow>,
+    pub totals: RentRollTotals,
+}
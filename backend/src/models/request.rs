@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MaintenanceRequest {
+    pub id: i64,
+    pub tenant_id: i64,
+    pub property_id: i64,
+    pub unit_id: Option<i64>,
+    pub title: String,
+    pub description: String,
+    pub urgency: String, // low, medium, high, urgent
+    pub permission_to_enter: bool,
+    pub entry_notes: Option<String>,
+    pub status: String, // submitted, converted, declined
+    pub maintenance_id: Option<i64>,
+    pub triaged_by: Option<String>,
+    pub triage_notes: Option<String>,
+    pub triaged_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccessWindow {
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MaintenanceRequestDetail {
+    #[serde(flatten)]
+    pub request: MaintenanceRequest,
+    pub access_windows: Vec<AccessWindow>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMaintenanceRequest {
+    pub title: String,
+    pub description: String,
+    pub urgency: Option<String>,
+    pub permission_to_enter: Option<bool>,
+    pub entry_notes: Option<String>,
+    pub access_windows: Option<Vec<AccessWindow>>,
+}
+
+/// Staff decision on a submitted request. Converting creates a work order
+/// using the optional overrides; declining only records the notes.
+#[derive(Debug, Deserialize)]
+pub struct TriageMaintenanceRequest {
+    pub decision: String, // convert, decline
+    pub title: Option<String>,
+    pub priority: Option<String>,
+    pub scheduled_date: Option<DateTime<Utc>>,
+    pub contractor_id: Option<i64>,
+    pub estimated_cost: Option<f64>,
+    pub triaged_by: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PortalLink {
+    pub tenant_id: i64,
+    pub token: String,
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SlaTarget {
+    pub priority: String,
+    pub response_hours: f64,
+    pub resolution_hours: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSlaTarget {
+    pub response_hours: Option<f64>,
+    pub resolution_hours: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceSla {
+    pub maintenance_id: i64,
+    pub property_id: i64,
+    pub contractor_id: Option<i64>,
+    pub title: String,
+    pub priority: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub response_due: Option<DateTime<Utc>>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub response_status: String, // met, on_track, at_risk, breached
+    pub resolution_due: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolution_status: String,
+    pub sla_status: String, // worst of the response and resolution statuses
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SlaCompliance {
+    pub id: Option<i64>,
+    pub name: String,
+    pub evaluated: i64,
+    pub met: i64,
+    pub breached: i64,
+    pub compliance_rate: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SlaComplianceReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub overall: SlaCompliance,
+    pub by_property: Vec<SlaCompliance>,
+    pub by_contractor: Vec<SlaCompliance>,
+}
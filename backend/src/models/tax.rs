@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepreciableAsset {
+    pub description: String,
+    pub maintenance_record_id: Option<i64>, // set for capital improvements
+    pub basis: f64,
+    pub recovery_period_years: f64,
+    pub placed_in_service_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepreciationYear {
+    pub year: i32,
+    pub building: f64,
+    pub improvements: f64,
+    pub total: f64,
+    pub accumulated: f64,
+    pub remaining_basis: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepreciationSchedule {
+    pub property_id: i64,
+    pub property_title: String,
+    pub property_type: String,
+    pub purchase_price: f64,
+    pub land_value: f64,
+    pub assets: Vec<DepreciableAsset>,
+    pub years: Vec<DepreciationYear>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SkippedProperty {
+    pub property_id: i64,
+    pub property_title: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PortfolioDepreciation {
+    pub properties: Vec<DepreciationSchedule>,
+    pub skipped: Vec<SkippedProperty>,
+    pub years: Vec<DepreciationYear>,
+}
+
+/// Totals for each Schedule E income and expense line
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleELines {
+    pub rents_received: f64,
+    pub advertising: f64,
+    pub auto_travel: f64,
+    pub cleaning_maintenance: f64,
+    pub commissions: f64,
+    pub insurance: f64,
+    pub legal_professional: f64,
+    pub management_fees: f64,
+    pub mortgage_interest: f64,
+    pub other_interest: f64,
+    pub repairs: f64,
+    pub supplies: f64,
+    pub taxes: f64,
+    pub utilities: f64,
+    pub depreciation: f64,
+    pub other: f64,
+    pub total_expenses: f64,
+    pub net_income: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduleEProperty {
+    pub property_id: i64,
+    pub property_title: String,
+    pub address: String,
+    pub property_type: String,
+    #[serde(flatten)]
+    pub lines: ScheduleELines,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduleEReport {
+    pub tax_year: i32,
+    pub properties: Vec<ScheduleEProperty>,
+    pub totals: ScheduleELines,
+}
@@ -6,10 +6,12 @@ use sqlx::FromRow;
 pub struct Tenant {
     pub id: i64,
     pub property_id: i64,
+    pub unit_id: Option<i64>,
     pub first_name: String,
     pub last_name: String,
     pub email: Option<String>,
     pub phone: Option<String>,
+    // Terms of the tenant's current lease, kept in sync from `leases`
     pub lease_start: DateTime<Utc>,
     pub lease_end: DateTime<Utc>,
     pub monthly_rent: f64,
@@ -23,14 +25,18 @@ pub struct Tenant {
 #[derive(Debug, Deserialize)]
 pub struct CreateTenant {
     pub property_id: i64,
+    pub unit_id: Option<i64>,
     pub first_name: String,
     pub last_name: String,
     pub email: Option<String>,
     pub phone: Option<String>,
-    pub lease_start: DateTime<Utc>,
-    pub lease_end: DateTime<Utc>,
-    pub monthly_rent: f64,
+    // Terms for a new lease with this tenant as the primary occupant, or
+    // `lease_id` to join an existing lease as a co-tenant
+    pub lease_start: Option<DateTime<Utc>>,
+    pub lease_end: Option<DateTime<Utc>>,
+    pub monthly_rent: Option<f64>,
     pub deposit_amount: Option<f64>,
+    pub lease_id: Option<i64>,
     pub status: String,
     pub notes: Option<String>,
 }
@@ -38,6 +44,7 @@ pub struct CreateTenant {
 #[derive(Debug, Deserialize)]
 pub struct UpdateTenant {
     pub property_id: Option<i64>,
+    pub unit_id: Option<i64>,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub email: Option<String>,
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Unit {
+    pub id: i64,
+    pub property_id: i64,
+    pub unit_number: String,
+    pub bedrooms: Option<i32>,
+    pub bathrooms: Option<f32>,
+    pub square_feet: Option<i32>,
+    pub market_rent: Option<f64>,
+    pub status: String, // occupied, vacant, maintenance
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateUnit {
+    pub unit_number: String,
+    pub bedrooms: Option<i32>,
+    pub bathrooms: Option<f32>,
+    pub square_feet: Option<i32>,
+    pub market_rent: Option<f64>,
+    pub status: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateUnit {
+    pub unit_number: Option<String>,
+    pub bedrooms: Option<i32>,
+    pub bathrooms: Option<f32>,
+    pub square_feet: Option<i32>,
+    pub market_rent: Option<f64>,
+    pub status: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct UnitAnalytics {
+    pub unit_id: i64,
+    pub property_id: i64,
+    pub property_title: String,
+    pub unit_number: String,
+    pub status: String,
+    pub bedrooms: Option<i32>,
+    pub square_feet: Option<i32>,
+    pub market_rent: Option<f64>,
+    pub current_rent: Option<f64>, // rent on the unit's active leases
+    pub active_tenants: i64,
+    pub open_work_orders: i64,
+    pub maintenance_cost: f64,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct BuildingAnalytics {
+    pub property_id: i64,
+    pub property_title: String,
+    pub address: String,
+    pub total_units: i64,
+    pub occupied_units: i64,
+    pub vacant_units: i64,
+    pub occupancy_rate: f64, // percent of units occupied
+    pub total_square_feet: i64,
+    pub market_rent: f64,
+    pub current_rent: f64,
+    pub open_work_orders: i64,
+    pub maintenance_cost: f64,
+}
@@ -0,0 +1,525 @@
+// Occupancy derived from leases
+// Unit and property status follow active leases; every change is recorded in
+// unit_occupancy_history, which drives physical and economic occupancy
+use crate::error::Result;
+use crate::models::{
+    Lease, OccupancyReport, OccupancySpan, PropertyOccupancy, VacancyPeriod, VacancyReport,
+};
+use crate::tax::round_cents;
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, Sqlite, SqlitePool, Transaction};
+use std::collections::HashMap;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const DAYS_PER_MONTH: f64 = 365.0 / 12.0;
+
+/// Status a unit should have given the property's active leases. A lease
+/// without a unit covers the whole property. Maintenance is set by hand and
+/// only holds while nobody is living there.
+fn derive_status<'a>(
+    unit_id: i64,
+    current: &str,
+    active: &'a [Lease],
+) -> (&'static str, Option<&'a Lease>) {
+    let lease = active
+        .iter()
+        .find(|l| l.unit_id == Some(unit_id))
+        .or_else(|| active.iter().find(|l| l.unit_id.is_none()));
+
+    match lease {
+        Some(lease) => ("occupied", Some(lease)),
+        None if current == "maintenance" => ("maintenance", None),
+        None => ("vacant", None),
+    }
+}
+
+/// Bring a property's unit and property statuses in line with its active
+/// leases, closing and opening history spans as they change. Returns the
+/// number of units whose span changed.
+pub async fn refresh_property(
+    tx: &mut Transaction<'_, Sqlite>,
+    property_id: i64,
+    now: DateTime<Utc>,
+) -> Result<i64> {
+    let units: Vec<(i64, String)> =
+        sqlx::query_as("SELECT id, status FROM units WHERE property_id = ? ORDER BY id")
+            .bind(property_id)
+            .fetch_all(&mut **tx)
+            .await?;
+
+    let active = sqlx::query_as::<_, Lease>(
+        "SELECT * FROM leases WHERE property_id = ? AND status = 'active' ORDER BY start_date",
+    )
+    .bind(property_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let mut changed = 0;
+    let mut statuses = Vec::with_capacity(units.len());
+
+    for (unit_id, current) in &units {
+        let (status, lease) = derive_status(*unit_id, current, &active);
+        let lease_id = lease.map(|l| l.id);
+        statuses.push(status);
+
+        if current != status {
+            sqlx::query("UPDATE units SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+                .bind(status)
+                .bind(unit_id)
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        let open = sqlx::query_as::<_, OccupancySpan>(
+            r#"
+            SELECT * FROM unit_occupancy_history
+            WHERE unit_id = ? AND ended_at IS NULL
+            ORDER BY started_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(unit_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        if open
+            .as_ref()
+            .is_some_and(|span| span.status == status && span.lease_id == lease_id)
+        {
+            continue;
+        }
+
+        // Spans change hands when the lease actually started or ended, even
+        // if this runs later (or a lease is entered after the fact)
+        let changed_at = match &open {
+            Some(span) => {
+                let lease_end: Option<DateTime<Utc>> = match span.lease_id {
+                    Some(id) => sqlx::query_scalar(
+                        "SELECT end_date FROM leases WHERE id = ? AND status = 'ended'",
+                    )
+                    .bind(id)
+                    .fetch_optional(&mut **tx)
+                    .await?
+                    .flatten(),
+                    None => None,
+                };
+                let at = match (lease, lease_end) {
+                    (_, Some(end)) => end,
+                    (Some(lease), None) if span.status != "occupied" => lease.start_date,
+                    _ => now,
+                };
+                let changed_at = at.min(now).max(span.started_at);
+
+                sqlx::query("UPDATE unit_occupancy_history SET ended_at = ? WHERE id = ?")
+                    .bind(changed_at)
+                    .bind(span.id)
+                    .execute(&mut **tx)
+                    .await?;
+
+                changed_at
+            }
+            None => lease.map_or(now, |l| l.start_date.min(now)),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO unit_occupancy_history (unit_id, property_id, status, lease_id, started_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(unit_id)
+        .bind(property_id)
+        .bind(status)
+        .bind(lease_id)
+        .bind(changed_at)
+        .execute(&mut **tx)
+        .await?;
+
+        changed += 1;
+    }
+
+    // A building is occupied while anyone lives there
+    let property_status = if statuses.contains(&"occupied") {
+        "occupied"
+    } else if statuses.contains(&"maintenance") {
+        "maintenance"
+    } else {
+        "vacant"
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE properties SET status = ?, updated_at = CURRENT_TIMESTAMP
+        WHERE id = ? AND status != ?
+        "#,
+    )
+    .bind(property_status)
+    .bind(property_id)
+    .bind(property_status)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(changed)
+}
+
+/// Refresh every property
+pub async fn refresh_all(pool: &SqlitePool, now: DateTime<Utc>) -> Result<i64> {
+    let property_ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM properties")
+        .fetch_all(pool)
+        .await?;
+
+    let mut changed = 0;
+    for property_id in property_ids {
+        let mut tx = pool.begin().await?;
+        changed += refresh_property(&mut tx, property_id, now).await?;
+        tx.commit().await?;
+    }
+
+    Ok(changed)
+}
+
+/// Days of [start, end) that fall inside [from, to)
+fn overlap_days(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> f64 {
+    let seconds = (end.min(to) - start.max(from)).num_seconds();
+    (seconds.max(0) as f64) / SECONDS_PER_DAY
+}
+
+fn percent(part: f64, whole: f64) -> f64 {
+    if whole > 0.0 {
+        round_cents(part / whole * 100.0)
+    } else {
+        0.0
+    }
+}
+
+/// Physical occupancy is occupied unit-days over all unit-days. Economic
+/// occupancy is rent collected over the market rent every unit could have
+/// earned. Spans still open run until `now`, and a lease covering the whole
+/// property spreads its rent across the property's units.
+pub async fn occupancy_report(
+    pool: &SqlitePool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    property_id: Option<i64>,
+    now: DateTime<Utc>,
+) -> Result<OccupancyReport> {
+    let properties = sqlx::query_as::<_, (i64, String, Option<f64>)>(
+        "SELECT id, title, monthly_rent FROM properties WHERE (?1 IS NULL OR id = ?1) ORDER BY id",
+    )
+    .bind(property_id)
+    .fetch_all(pool)
+    .await?;
+
+    let units = sqlx::query_as::<_, (i64, i64, Option<f64>)>(
+        "SELECT id, property_id, market_rent FROM units WHERE (?1 IS NULL OR property_id = ?1)",
+    )
+    .bind(property_id)
+    .fetch_all(pool)
+    .await?;
+
+    let spans =
+        sqlx::query_as::<_, (i64, DateTime<Utc>, Option<DateTime<Utc>>, Option<f64>, bool)>(
+            r#"
+        SELECT h.property_id, h.started_at, h.ended_at, l.monthly_rent,
+               l.id IS NOT NULL AND l.unit_id IS NULL
+        FROM unit_occupancy_history h
+        LEFT JOIN leases l ON l.id = h.lease_id
+        WHERE h.status = 'occupied' AND (?1 IS NULL OR h.property_id = ?1)
+        "#,
+        )
+        .bind(property_id)
+        .fetch_all(pool)
+        .await?;
+
+    let payments = sqlx::query_as::<_, (i64, f64, DateTime<Utc>)>(
+        "SELECT property_id, amount, payment_date FROM rent_payments WHERE (?1 IS NULL OR property_id = ?1)",
+    )
+    .bind(property_id)
+    .fetch_all(pool)
+    .await?;
+
+    let days = overlap_days(from, to, from, to);
+
+    let mut rows: Vec<PropertyOccupancy> = Vec::with_capacity(properties.len());
+    let mut index = HashMap::new();
+    for (id, title, _) in &properties {
+        index.insert(*id, rows.len());
+        rows.push(PropertyOccupancy {
+            property_id: *id,
+            property_title: title.clone(),
+            total_units: 0,
+            unit_days: 0.0,
+            occupied_unit_days: 0.0,
+            physical_occupancy: 0.0,
+            potential_rent: 0.0,
+            scheduled_rent: 0.0,
+            collected_rent: 0.0,
+            economic_occupancy: 0.0,
+        });
+    }
+
+    let mut unit_counts: HashMap<i64, i64> = HashMap::new();
+    for (_, property_id, _) in &units {
+        *unit_counts.entry(*property_id).or_default() += 1;
+    }
+
+    for (_, unit_property_id, market_rent) in &units {
+        let Some(&i) = index.get(unit_property_id) else {
+            continue;
+        };
+        // Units without a market rent share the property's asking rent
+        let monthly = market_rent.unwrap_or_else(|| {
+            let (_, _, property_rent) = &properties[i];
+            property_rent.unwrap_or(0.0) / unit_counts[unit_property_id] as f64
+        });
+        let row = &mut rows[i];
+        row.total_units += 1;
+        row.unit_days += days;
+        row.potential_rent += monthly * days / DAYS_PER_MONTH;
+    }
+
+    for (span_property_id, started_at, ended_at, rent, whole_property) in &spans {
+        let Some(&i) = index.get(span_property_id) else {
+            continue;
+        };
+        let occupied = overlap_days(*started_at, ended_at.unwrap_or(now), from, to);
+        let mut monthly = rent.unwrap_or(0.0);
+        if *whole_property {
+            monthly /= unit_counts.get(span_property_id).copied().unwrap_or(1) as f64;
+        }
+        let row = &mut rows[i];
+        row.occupied_unit_days += occupied;
+        row.scheduled_rent += monthly * occupied / DAYS_PER_MONTH;
+    }
+
+    for (payment_property_id, amount, paid_on) in &payments {
+        if *paid_on < from || *paid_on >= to {
+            continue;
+        }
+        if let Some(&i) = index.get(payment_property_id) {
+            rows[i].collected_rent += amount;
+        }
+    }
+
+    for row in &mut rows {
+        row.physical_occupancy = percent(row.occupied_unit_days, row.unit_days);
+        row.economic_occupancy = percent(row.collected_rent, row.potential_rent);
+    }
+
+    let unit_days: f64 = rows.iter().map(|r| r.unit_days).sum();
+    let occupied_unit_days: f64 = rows.iter().map(|r| r.occupied_unit_days).sum();
+    let potential_rent: f64 = rows.iter().map(|r| r.potential_rent).sum();
+    let scheduled_rent: f64 = rows.iter().map(|r| r.scheduled_rent).sum();
+    let collected_rent: f64 = rows.iter().map(|r| r.collected_rent).sum();
+
+    for row in &mut rows {
+        row.unit_days = round_cents(row.unit_days);
+        row.occupied_unit_days = round_cents(row.occupied_unit_days);
+        row.potential_rent = round_cents(row.potential_rent);
+        row.scheduled_rent = round_cents(row.scheduled_rent);
+        row.collected_rent = round_cents(row.collected_rent);
+    }
+
+    Ok(OccupancyReport {
+        from,
+        to,
+        days: round_cents(days),
+        total_units: units.len() as i64,
+        unit_days: round_cents(unit_days),
+        occupied_unit_days: round_cents(occupied_unit_days),
+        physical_occupancy: percent(occupied_unit_days, unit_days),
+        potential_rent: round_cents(potential_rent),
+        scheduled_rent: round_cents(scheduled_rent),
+        collected_rent: round_cents(collected_rent),
+        economic_occupancy: percent(collected_rent, potential_rent),
+        properties: rows,
+    })
+}
+
+#[derive(FromRow)]
+struct SpanRow {
+    unit_id: i64,
+    unit_number: String,
+    property_id: i64,
+    property_title: String,
+    market_rent: Option<f64>,
+    status: String,
+    lease_id: Option<i64>,
+    lease_rent: Option<f64>,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+}
+
+/// Vacancy periods built from the occupancy history, oldest first. Lost rent
+/// uses the unit's market rent, or the rent of the lease before the vacancy.
+pub async fn vacancy_periods(
+    pool: &SqlitePool,
+    property_id: Option<i64>,
+    unit_id: Option<i64>,
+    now: DateTime<Utc>,
+) -> Result<Vec<VacancyPeriod>> {
+    let spans = sqlx::query_as::<_, SpanRow>(
+        r#"
+        SELECT h.unit_id, u.unit_number, h.property_id, p.title AS property_title,
+               u.market_rent, h.status, h.lease_id, l.monthly_rent AS lease_rent,
+               h.started_at, h.ended_at
+        FROM unit_occupancy_history h
+        JOIN units u ON u.id = h.unit_id
+        JOIN properties p ON p.id = h.property_id
+        LEFT JOIN leases l ON l.id = h.lease_id
+        WHERE (?1 IS NULL OR h.property_id = ?1)
+          AND (?2 IS NULL OR h.unit_id = ?2)
+        ORDER BY h.unit_id, h.started_at, h.id
+        "#,
+    )
+    .bind(property_id)
+    .bind(unit_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut periods = Vec::new();
+    let mut current: Option<VacancyPeriod> = None;
+    let mut last_unit = None;
+    let mut last_lease: Option<(i64, Option<f64>)> = None;
+
+    for span in spans {
+        if last_unit != Some(span.unit_id) {
+            periods.extend(current.take());
+            last_unit = Some(span.unit_id);
+            last_lease = None;
+        }
+
+        if span.status == "occupied" {
+            if let Some(mut period) = current.take() {
+                period.ended_at = Some(span.started_at);
+                period.next_lease_id = span.lease_id;
+                periods.push(period);
+            }
+            last_lease = span.lease_id.map(|id| (id, span.lease_rent));
+            continue;
+        }
+
+        match &mut current {
+            // Consecutive vacant and maintenance spans are one vacancy
+            Some(period) => period.ended_at = span.ended_at,
+            None => {
+                current = Some(VacancyPeriod {
+                    unit_id: span.unit_id,
+                    unit_number: span.unit_number,
+                    property_id: span.property_id,
+                    property_title: span.property_title,
+                    started_at: span.started_at,
+                    ended_at: span.ended_at,
+                    days_vacant: 0.0,
+                    market_rent: span.market_rent.or(last_lease.and_then(|(_, rent)| rent)),
+                    lost_rent: 0.0,
+                    previous_lease_id: last_lease.map(|(id, _)| id),
+                    next_lease_id: None,
+                })
+            }
+        }
+    }
+    periods.extend(current);
+
+    for period in &mut periods {
+        let end = period.ended_at.unwrap_or(now);
+        let days = overlap_days(period.started_at, end, period.started_at, end);
+        period.days_vacant = round_cents(days);
+        period.lost_rent = round_cents(period.market_rent.unwrap_or(0.0) * days / DAYS_PER_MONTH);
+    }
+
+    Ok(periods)
+}
+
+/// Average length of vacancies that ended within [from, to), rent lost to
+/// vacancy within it, and the units vacant right now
+pub async fn vacancy_report(
+    pool: &SqlitePool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    property_id: Option<i64>,
+    now: DateTime<Utc>,
+) -> Result<VacancyReport> {
+    let periods = vacancy_periods(pool, property_id, None, now).await?;
+
+    let completed: Vec<&VacancyPeriod> = periods
+        .iter()
+        .filter(|p| p.ended_at.is_some_and(|end| end >= from && end < to))
+        .collect();
+    let average_days_vacant = if completed.is_empty() {
+        0.0
+    } else {
+        round_cents(completed.iter().map(|p| p.days_vacant).sum::<f64>() / completed.len() as f64)
+    };
+
+    let mut vacant_unit_days = 0.0;
+    let mut lost_rent = 0.0;
+    for period in &periods {
+        let days = overlap_days(period.started_at, period.ended_at.unwrap_or(now), from, to);
+        vacant_unit_days += days;
+        lost_rent += period.market_rent.unwrap_or(0.0) * days / DAYS_PER_MONTH;
+    }
+
+    let mut currently_vacant: Vec<VacancyPeriod> = periods
+        .iter()
+        .filter(|p| p.ended_at.is_none())
+        .cloned()
+        .collect();
+    currently_vacant.sort_by(|a, b| b.days_vacant.total_cmp(&a.days_vacant));
+
+    Ok(VacancyReport {
+        from,
+        to,
+        completed_periods: completed.len() as i64,
+        average_days_vacant,
+        vacant_unit_days: round_cents(vacant_unit_days),
+        lost_rent: round_cents(lost_rent),
+        currently_vacant,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn day(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn overlap_days_counts_only_the_shared_range() {
+        let (from, to) = (day(2025, 1, 1), day(2025, 2, 1));
+
+        assert_eq!(
+            overlap_days(day(2024, 6, 1), day(2025, 6, 1), from, to),
+            31.0
+        );
+        assert_eq!(
+            overlap_days(day(2025, 1, 11), day(2025, 3, 1), from, to),
+            21.0
+        );
+        assert_eq!(
+            overlap_days(day(2024, 1, 1), day(2024, 12, 1), from, to),
+            0.0
+        );
+        assert_eq!(
+            overlap_days(day(2025, 3, 1), day(2025, 4, 1), from, to),
+            0.0
+        );
+    }
+
+    #[test]
+    fn overlap_days_keeps_partial_days() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 30, 12, 0, 0).unwrap();
+        assert_eq!(
+            overlap_days(start, day(2025, 3, 1), day(2025, 1, 1), day(2025, 2, 1)),
+            1.5
+        );
+    }
+}
@@ -0,0 +1,535 @@
+use crate::error::{AppError, Result};
+use crate::models::{
+    ApplicationReference, CreateRentalApplication, DecideRentalApplication, IncomeCheck,
+    RecordScreening, RentalApplication, RentalApplicationDetail,
+};
+use crate::occupancy::refresh_property;
+use crate::routes::leases::{add_occupant, insert_lease, sync_lease_tenants};
+use crate::routes::units::check_unit;
+use crate::tax::round_cents;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Duration, Months, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+/// Gross monthly income an applicant needs, as a multiple of the rent
+const REQUIRED_INCOME_MULTIPLE: f64 = 3.0;
+
+#[derive(Debug, Deserialize)]
+pub struct RentalApplicationQuery {
+    pub status: Option<String>,
+    pub property_id: Option<i64>,
+}
+
+fn validate_relationship(relationship: &str) -> Result<()> {
+    if matches!(relationship, "landlord" | "employer" | "personal") {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown reference relationship '{}'",
+            relationship
+        )))
+    }
+}
+
+fn validate_background_check(result: &str) -> Result<()> {
+    if matches!(result, "clear" | "flagged" | "pending") {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown background check result '{}'",
+            result
+        )))
+    }
+}
+
+async fn fetch_application(pool: &SqlitePool, id: i64) -> Result<RentalApplication> {
+    sqlx::query_as::<_, RentalApplication>("SELECT * FROM rental_applications WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Rental application with id {} not found", id)))
+}
+
+/// Rent the applicant would pay: what they applied for, else the unit's
+/// market rent, else the property's asking rent
+async fn asking_rent(pool: &SqlitePool, application: &RentalApplication) -> Result<Option<f64>> {
+    if application.desired_rent.is_some() {
+        return Ok(application.desired_rent);
+    }
+    let market_rent: Option<f64> = match application.unit_id {
+        Some(unit_id) => sqlx::query_scalar("SELECT market_rent FROM units WHERE id = ?")
+            .bind(unit_id)
+            .fetch_optional(pool)
+            .await?
+            .flatten(),
+        None => None,
+    };
+    if market_rent.is_some() {
+        return Ok(market_rent);
+    }
+    let property_rent: Option<f64> =
+        sqlx::query_scalar("SELECT monthly_rent FROM properties WHERE id = ?")
+            .bind(application.property_id)
+            .fetch_optional(pool)
+            .await?
+            .flatten();
+
+    Ok(property_rent)
+}
+
+fn income_check(monthly_income: f64, monthly_rent: Option<f64>) -> IncomeCheck {
+    let rent = monthly_rent.filter(|rent| *rent > 0.0);
+    let income = (monthly_income > 0.0).then_some(monthly_income);
+    IncomeCheck {
+        monthly_rent: rent,
+        monthly_income,
+        rent_to_income: rent
+            .zip(income)
+            .map(|(rent, income)| round_cents(rent / income * 100.0)),
+        income_multiple: rent.map(|rent| round_cents(monthly_income / rent)),
+        meets_requirement: rent.map(|rent| monthly_income >= rent * REQUIRED_INCOME_MULTIPLE),
+    }
+}
+
+async fn load_detail(
+    pool: &SqlitePool,
+    application: RentalApplication,
+) -> Result<RentalApplicationDetail> {
+    let references = sqlx::query_as::<_, ApplicationReference>(
+        "SELECT * FROM rental_application_references WHERE application_id = ? ORDER BY id",
+    )
+    .bind(application.id)
+    .fetch_all(pool)
+    .await?;
+    let rent = asking_rent(pool, &application).await?;
+
+    Ok(RentalApplicationDetail {
+        income_check: income_check(application.monthly_income, rent),
+        application,
+        references,
+    })
+}
+
+/// Applications are only taken for a vacant unit, or a property with one
+async fn check_vacancy(pool: &SqlitePool, property_id: i64, unit_id: Option<i64>) -> Result<()> {
+    let vacant: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM units
+        WHERE property_id = ?1 AND (?2 IS NULL OR id = ?2) AND status = 'vacant'
+        "#,
+    )
+    .bind(property_id)
+    .bind(unit_id)
+    .fetch_one(pool)
+    .await?;
+
+    if vacant > 0 {
+        return Ok(());
+    }
+    Err(AppError::Conflict(match unit_id {
+        Some(unit_id) => format!("Unit {} is not vacant", unit_id),
+        None => format!("Property {} has no vacant units", property_id),
+    }))
+}
+
+pub async fn list_rental_applications(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<RentalApplicationQuery>,
+) -> Result<Json<Vec<RentalApplicationDetail>>> {
+    let applications = sqlx::query_as::<_, RentalApplication>(
+        r#"
+        SELECT * FROM rental_applications
+        WHERE (?1 IS NULL OR status = ?1)
+          AND (?2 IS NULL OR property_id = ?2)
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(&params.status)
+    .bind(params.property_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut details = Vec::with_capacity(applications.len());
+    for application in applications {
+        details.push(load_detail(&pool, application).await?);
+    }
+
+    Ok(Json(details))
+}
+
+pub async fn get_rental_application(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<RentalApplicationDetail>> {
+    let application = fetch_application(&pool, id).await?;
+
+    Ok(Json(load_detail(&pool, application).await?))
+}
+
+pub async fn create_rental_application(
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateRentalApplication>,
+) -> Result<(StatusCode, Json<RentalApplicationDetail>)> {
+    if payload.first_name.trim().is_empty() || payload.last_name.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "first_name and last_name are required".to_string(),
+        ));
+    }
+    if payload.email.is_none() && payload.phone.is_none() {
+        return Err(AppError::BadRequest(
+            "An email or phone number is required".to_string(),
+        ));
+    }
+    if payload.monthly_income < 0.0 {
+        return Err(AppError::BadRequest(
+            "monthly_income cannot be negative".to_string(),
+        ));
+    }
+    if payload.desired_rent.is_some_and(|rent| rent <= 0.0) {
+        return Err(AppError::BadRequest(
+            "desired_rent must be greater than zero".to_string(),
+        ));
+    }
+    let occupants = payload.occupants.unwrap_or(1);
+    if occupants < 1 {
+        return Err(AppError::BadRequest(
+            "occupants must be at least 1".to_string(),
+        ));
+    }
+    let references = payload.references.unwrap_or_default();
+    for reference in &references {
+        validate_relationship(&reference.relationship)?;
+    }
+
+    sqlx::query("SELECT id FROM properties WHERE id = ?")
+        .bind(payload.property_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| {
+            AppError::BadRequest(format!("Property {} does not exist", payload.property_id))
+        })?;
+    check_unit(&pool, payload.property_id, payload.unit_id).await?;
+    check_vacancy(&pool, payload.property_id, payload.unit_id).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO rental_applications (
+            property_id, unit_id, first_name, last_name, email, phone,
+            date_of_birth, current_address, monthly_income, employer, job_title,
+            employment_start, desired_move_in, desired_rent, occupants, pets, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(payload.property_id)
+    .bind(payload.unit_id)
+    .bind(&payload.first_name)
+    .bind(&payload.last_name)
+    .bind(&payload.email)
+    .bind(&payload.phone)
+    .bind(payload.date_of_birth)
+    .bind(&payload.current_address)
+    .bind(payload.monthly_income)
+    .bind(&payload.employer)
+    .bind(&payload.job_title)
+    .bind(payload.employment_start)
+    .bind(payload.desired_move_in)
+    .bind(payload.desired_rent)
+    .bind(occupants)
+    .bind(&payload.pets)
+    .bind(&payload.notes)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    for reference in &references {
+        sqlx::query(
+            r#"
+            INSERT INTO rental_application_references (
+                application_id, name, relationship, phone, email, notes
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id)
+        .bind(&reference.name)
+        .bind(&reference.relationship)
+        .bind(&reference.phone)
+        .bind(&reference.email)
+        .bind(&reference.notes)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let application = fetch_application(&pool, id).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(load_detail(&pool, application).await?),
+    ))
+}
+
+/// Record credit, background and income checks. Can be repeated until the
+/// application is decided; fields left out keep their previous values.
+pub async fn screen_rental_application(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<RecordScreening>,
+) -> Result<Json<RentalApplicationDetail>> {
+    let application = fetch_application(&pool, id).await?;
+    if !matches!(application.status.as_str(), "submitted" | "screened") {
+        return Err(AppError::Conflict(format!(
+            "Rental application {} has already been {}",
+            id, application.status
+        )));
+    }
+    if let Some(score) = payload.credit_score {
+        if !(300..=850).contains(&score) {
+            return Err(AppError::BadRequest(
+                "credit_score must be between 300 and 850".to_string(),
+            ));
+        }
+    }
+    if let Some(result) = &payload.background_check {
+        validate_background_check(result)?;
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let screened = sqlx::query(
+        r#"
+        UPDATE rental_applications
+        SET status = 'screened',
+            credit_score = COALESCE(?, credit_score),
+            background_check = COALESCE(?, background_check),
+            eviction_history = COALESCE(?, eviction_history),
+            income_verified = COALESCE(?, income_verified),
+            screened_by = COALESCE(?, screened_by),
+            screening_notes = COALESCE(?, screening_notes),
+            screened_at = ?,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = ? AND status IN ('submitted', 'screened')
+        "#,
+    )
+    .bind(payload.credit_score)
+    .bind(&payload.background_check)
+    .bind(payload.eviction_history)
+    .bind(payload.income_verified)
+    .bind(&payload.screened_by)
+    .bind(&payload.notes)
+    .bind(Utc::now())
+    .bind(id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    // Decided since it was loaded above
+    if screened == 0 {
+        return Err(AppError::Conflict(format!(
+            "Rental application {} has already been decided",
+            id
+        )));
+    }
+
+    for reference_id in payload.verified_reference_ids.unwrap_or_default() {
+        let updated = sqlx::query(
+            "UPDATE rental_application_references SET verified = 1 WHERE id = ? AND application_id = ?",
+        )
+        .bind(reference_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Err(AppError::BadRequest(format!(
+                "Reference {} is not part of rental application {}",
+                reference_id, id
+            )));
+        }
+    }
+
+    tx.commit().await?;
+
+    let application = fetch_application(&pool, id).await?;
+
+    Ok(Json(load_detail(&pool, application).await?))
+}
+
+/// Approve an application, creating the tenant and a pending lease, or reject it
+pub async fn decide_rental_application(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<DecideRentalApplication>,
+) -> Result<Json<RentalApplicationDetail>> {
+    let application = fetch_application(&pool, id).await?;
+    if !matches!(application.status.as_str(), "submitted" | "screened") {
+        return Err(AppError::Conflict(format!(
+            "Rental application {} has already been {}",
+            id, application.status
+        )));
+    }
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+
+    let decided = match payload.decision.as_str() {
+        "approve" => {
+            let unit_id = payload.unit_id.or(application.unit_id);
+            check_unit(&pool, application.property_id, unit_id).await?;
+            let monthly_rent = match payload.monthly_rent {
+                Some(rent) => Some(rent),
+                None => asking_rent(&pool, &application).await?,
+            }
+            .filter(|rent| *rent > 0.0)
+            .ok_or_else(|| {
+                AppError::BadRequest(
+                    "monthly_rent is required when the unit and property have no asking rent"
+                        .to_string(),
+                )
+            })?;
+            let start = payload.lease_start.unwrap_or(application.desired_move_in);
+            let end = match payload.lease_end {
+                Some(end) => end,
+                None => start
+                    .checked_add_months(Months::new(payload.term_months.unwrap_or(12)))
+                    .map(|d| d - Duration::days(1))
+                    .ok_or_else(|| {
+                        AppError::BadRequest("Lease term is out of range".to_string())
+                    })?,
+            };
+
+            let tenant_id = sqlx::query(
+                r#"
+                INSERT INTO tenants (
+                    property_id, unit_id, first_name, last_name, email, phone,
+                    lease_start, lease_end, monthly_rent, deposit_amount,
+                    status, notes, portal_token
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'pending', ?, lower(hex(randomblob(24))))
+                "#,
+            )
+            .bind(application.property_id)
+            .bind(unit_id)
+            .bind(&application.first_name)
+            .bind(&application.last_name)
+            .bind(&application.email)
+            .bind(&application.phone)
+            .bind(start)
+            .bind(end)
+            .bind(monthly_rent)
+            .bind(payload.deposit_amount)
+            .bind(format!("Rental application #{}", id))
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+
+            let lease_id = insert_lease(
+                &mut tx,
+                application.property_id,
+                unit_id,
+                "fixed",
+                start,
+                Some(end),
+                monthly_rent,
+                payload.deposit_amount,
+                1,
+                "pending",
+                None,
+                payload.notes.as_deref(),
+            )
+            .await?;
+            add_occupant(
+                &mut tx,
+                lease_id,
+                application.property_id,
+                tenant_id,
+                "primary",
+            )
+            .await?;
+            sync_lease_tenants(&mut *tx, lease_id).await?;
+            refresh_property(&mut tx, application.property_id, now).await?;
+
+            sqlx::query(
+                r#"
+                UPDATE rental_applications
+                SET status = 'approved', unit_id = ?, tenant_id = ?, lease_id = ?,
+                    decided_by = ?, decision_notes = ?, decided_at = ?,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = ? AND status IN ('submitted', 'screened')
+                "#,
+            )
+            .bind(unit_id)
+            .bind(tenant_id)
+            .bind(lease_id)
+            .bind(&payload.decided_by)
+            .bind(&payload.notes)
+            .bind(now)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+        }
+        "reject" => sqlx::query(
+            r#"
+            UPDATE rental_applications
+            SET status = 'rejected', decided_by = ?, decision_notes = ?,
+                decided_at = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ? AND status IN ('submitted', 'screened')
+            "#,
+        )
+        .bind(&payload.decided_by)
+        .bind(&payload.notes)
+        .bind(now)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected(),
+        other => {
+            return Err(AppError::BadRequest(format!(
+                "Unknown application decision '{}' (expected approve or reject)",
+                other
+            )))
+        }
+    };
+    // Someone else decided it first; dropping the transaction discards the
+    // tenant and lease created above
+    if decided == 0 {
+        return Err(AppError::Conflict(format!(
+            "Rental application {} has already been decided",
+            id
+        )));
+    }
+
+    tx.commit().await?;
+
+    let application = fetch_application(&pool, id).await?;
+
+    Ok(Json(load_detail(&pool, application).await?))
+}
+
+pub async fn delete_rental_application(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM rental_applications WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Rental application with id {} not found",
+            id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,483 @@
+use crate::error::{AppError, Result};
+use crate::models::{Attachment, AttachmentVariant, ATTACHMENT_CATEGORIES};
+use crate::storage::images::{self, RenderedVariant};
+use crate::storage::{content_key, SharedStore};
+use axum::{
+    extract::{multipart::MultipartError, DefaultBodyLimit, Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use sqlx::SqlitePool;
+
+/// A record type files can be attached to
+pub trait AttachmentOwner: Send + Sync + 'static {
+    const ENTITY_TYPE: &'static str;
+    const TABLE: &'static str;
+    const LABEL: &'static str;
+}
+
+pub struct PropertyFiles;
+pub struct TenantFiles;
+pub struct MaintenanceFiles;
+
+impl AttachmentOwner for PropertyFiles {
+    const ENTITY_TYPE: &'static str = "property";
+    const TABLE: &'static str = "properties";
+    const LABEL: &'static str = "Property";
+}
+
+impl AttachmentOwner for TenantFiles {
+    const ENTITY_TYPE: &'static str = "tenant";
+    const TABLE: &'static str = "tenants";
+    const LABEL: &'static str = "Tenant";
+}
+
+impl AttachmentOwner for MaintenanceFiles {
+    const ENTITY_TYPE: &'static str = "maintenance";
+    const TABLE: &'static str = "maintenance_records";
+    const LABEL: &'static str = "Maintenance record";
+}
+
+/// Upload size limit, shared with the body-limit layer on the upload routes
+#[derive(Debug, Clone, Copy)]
+pub struct UploadLimit(pub usize);
+
+impl UploadLimit {
+    /// Request body limit: the file plus room for multipart headers and form fields
+    pub fn body_limit(self) -> DefaultBodyLimit {
+        DefaultBodyLimit::max(self.0 + 64 * 1024)
+    }
+}
+
+const ALLOWED_TYPES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+    "application/msword",
+    "application/vnd.ms-excel",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    "text/plain",
+    "text/csv",
+];
+
+/// Determine the content type from the file's bytes rather than trusting the
+/// client. Text has no magic number, so fall back to the name for CSV.
+fn sniff_content_type(data: &[u8], filename: &str) -> Result<String> {
+    let content_type = match infer::get(data) {
+        Some(kind) => kind.mime_type().to_string(),
+        None if std::str::from_utf8(data).is_ok() => {
+            if filename.to_ascii_lowercase().ends_with(".csv") {
+                "text/csv".to_string()
+            } else {
+                "text/plain".to_string()
+            }
+        }
+        None => "application/octet-stream".to_string(),
+    };
+
+    if ALLOWED_TYPES.contains(&content_type.as_str()) {
+        Ok(content_type)
+    } else {
+        Err(AppError::UnsupportedMediaType(format!(
+            "Files of type {} are not accepted",
+            content_type
+        )))
+    }
+}
+
+/// Keep only the final path component and drop characters that would break
+/// the Content-Disposition header
+fn clean_filename(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .collect();
+    if cleaned.trim().is_empty() {
+        "upload".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Report a body over the size limit as 413 rather than a malformed upload
+fn multipart_error(err: MultipartError, max_bytes: usize) -> AppError {
+    if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        AppError::PayloadTooLarge(format!("Uploads are limited to {} bytes", max_bytes))
+    } else {
+        AppError::BadRequest(format!("Invalid upload: {}", err.body_text()))
+    }
+}
+
+async fn ensure_owner<O: AttachmentOwner>(pool: &SqlitePool, id: i64) -> Result<()> {
+    sqlx::query(&format!("SELECT id FROM {} WHERE id = ?", O::TABLE))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} with id {} not found", O::LABEL, id)))?;
+    Ok(())
+}
+
+async fn fetch_attachment<O: AttachmentOwner>(
+    pool: &SqlitePool,
+    id: i64,
+    attachment_id: i64,
+) -> Result<Attachment> {
+    sqlx::query_as::<_, Attachment>(
+        "SELECT * FROM attachments WHERE id = ? AND entity_type = ? AND entity_id = ?",
+    )
+    .bind(attachment_id)
+    .bind(O::ENTITY_TYPE)
+    .bind(id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Attachment with id {} not found", attachment_id)))
+}
+
+/// Resize off the async runtime; decoding a large photo takes a while
+async fn render_variants(data: Vec<u8>) -> Result<Vec<RenderedVariant>> {
+    tokio::task::spawn_blocking(move || images::render_variants(&data))
+        .await
+        .map_err(|e| AppError::Internal(format!("Image processing failed: {}", e)))?
+}
+
+async fn store_variants(
+    pool: &SqlitePool,
+    store: &SharedStore,
+    attachment_id: i64,
+    variants: Vec<RenderedVariant>,
+) -> Result<()> {
+    for variant in variants {
+        let key = content_key(&variant.data);
+
+        // Row first, so a concurrent release sees the blob is still wanted
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO attachment_variants (
+                attachment_id, variant, content_type, width, height, size_bytes, sha256
+            )
+            VALUES (?, ?, 'image/jpeg', ?, ?, ?, ?)
+            "#,
+        )
+        .bind(attachment_id)
+        .bind(variant.name)
+        .bind(variant.width)
+        .bind(variant.height)
+        .bind(variant.data.len() as i64)
+        .bind(&key)
+        .execute(pool)
+        .await?;
+
+        put_if_missing(store, &key, &variant.data).await?;
+    }
+
+    Ok(())
+}
+
+async fn put_if_missing(store: &SharedStore, key: &str, data: &[u8]) -> Result<()> {
+    if !store.exists(key).await? {
+        store.put(key, data).await?;
+    }
+    Ok(())
+}
+
+/// Drop stored blobs whose last attachment or variant row has gone, whether
+/// deleted directly or along with its property, tenant or work order. Keys
+/// stay queued until their blob is released, so a failed run is retried.
+pub async fn release_blobs(pool: &SqlitePool, store: &SharedStore) -> Result<()> {
+    let keys: Vec<String> = sqlx::query_scalar("SELECT sha256 FROM attachment_blob_releases")
+        .fetch_all(pool)
+        .await?;
+
+    for key in keys {
+        // Identical content may still be attached elsewhere
+        let remaining: i64 = sqlx::query_scalar(
+            r#"
+            SELECT (SELECT COUNT(*) FROM attachments WHERE sha256 = ?1)
+                 + (SELECT COUNT(*) FROM attachment_variants WHERE sha256 = ?1)
+            "#,
+        )
+        .bind(&key)
+        .fetch_one(pool)
+        .await?;
+
+        if remaining == 0 {
+            store.delete(&key).await?;
+        }
+
+        sqlx::query("DELETE FROM attachment_blob_releases WHERE sha256 = ?")
+            .bind(&key)
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Release blobs after deleting an attachment's owner. The owner is already
+/// gone, so a storage failure is logged and left queued for the next run.
+pub async fn release_owner_blobs(pool: &SqlitePool, store: &SharedStore) {
+    if let Err(e) = release_blobs(pool, store).await {
+        tracing::warn!("Failed to release attachment blobs: {:?}", e);
+    }
+}
+
+pub async fn list_attachments<O: AttachmentOwner>(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Attachment>>> {
+    ensure_owner::<O>(&pool, id).await?;
+
+    let attachments = sqlx::query_as::<_, Attachment>(
+        r#"
+        SELECT * FROM attachments
+        WHERE entity_type = ? AND entity_id = ?
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(O::ENTITY_TYPE)
+    .bind(id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(attachments))
+}
+
+/// Multipart upload: one or more `file` parts plus optional `category`,
+/// `description` and `uploaded_by` fields applied to every file. A file
+/// already attached to the same record is returned instead of duplicated.
+pub async fn upload_attachments<O: AttachmentOwner>(
+    State(pool): State<SqlitePool>,
+    Extension(store): Extension<SharedStore>,
+    Extension(UploadLimit(max_bytes)): Extension<UploadLimit>,
+    Path(id): Path<i64>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<Vec<Attachment>>)> {
+    ensure_owner::<O>(&pool, id).await?;
+
+    let mut files = Vec::new();
+    let mut category = "other".to_string();
+    let mut description = None;
+    let mut uploaded_by = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| multipart_error(e, max_bytes))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "file" => {
+                let filename = clean_filename(field.file_name().unwrap_or_default());
+                let data = field
+                    .bytes()
+                    .await
+                    .map_err(|e| multipart_error(e, max_bytes))?;
+                if data.len() > max_bytes {
+                    return Err(AppError::PayloadTooLarge(format!(
+                        "{} is {} bytes; the limit is {} bytes",
+                        filename,
+                        data.len(),
+                        max_bytes
+                    )));
+                }
+                if data.is_empty() {
+                    return Err(AppError::BadRequest(format!("{} is empty", filename)));
+                }
+                files.push((filename, data));
+            }
+            "category" | "description" | "uploaded_by" => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|e| multipart_error(e, max_bytes))?;
+                match name.as_str() {
+                    "category" => category = value,
+                    "description" => description = Some(value),
+                    _ => uploaded_by = Some(value),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if files.is_empty() {
+        return Err(AppError::BadRequest(
+            "No file part in the upload".to_string(),
+        ));
+    }
+    if !ATTACHMENT_CATEGORIES.contains(&category.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unknown attachment category '{}'",
+            category
+        )));
+    }
+
+    let mut attachments = Vec::with_capacity(files.len());
+    let mut created = false;
+    for (filename, data) in files {
+        let content_type = sniff_content_type(&data, &filename)?;
+        let mut data = data.to_vec();
+        images::strip_gps(&mut data, &content_type);
+        let key = content_key(&data);
+
+        if let Some(existing) = sqlx::query_as::<_, Attachment>(
+            "SELECT * FROM attachments WHERE entity_type = ? AND entity_id = ? AND sha256 = ?",
+        )
+        .bind(O::ENTITY_TYPE)
+        .bind(id)
+        .bind(&key)
+        .fetch_optional(&pool)
+        .await?
+        {
+            attachments.push(existing);
+            continue;
+        }
+
+        // Render first so an unreadable photo is rejected before anything is stored
+        let variants = if images::is_processable(&content_type) {
+            render_variants(data.clone()).await?
+        } else {
+            Vec::new()
+        };
+
+        // Row first, so a concurrent release sees the blob is still wanted
+        let result = sqlx::query(
+            r#"
+            INSERT INTO attachments (
+                entity_type, entity_id, filename, content_type, size_bytes,
+                sha256, category, description, uploaded_by
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(O::ENTITY_TYPE)
+        .bind(id)
+        .bind(&filename)
+        .bind(&content_type)
+        .bind(data.len() as i64)
+        .bind(&key)
+        .bind(&category)
+        .bind(&description)
+        .bind(&uploaded_by)
+        .execute(&pool)
+        .await?;
+
+        if let Err(e) = put_if_missing(&store, &key, &data).await {
+            sqlx::query("DELETE FROM attachments WHERE id = ?")
+                .bind(result.last_insert_rowid())
+                .execute(&pool)
+                .await?;
+            return Err(e);
+        }
+
+        let attachment = sqlx::query_as::<_, Attachment>("SELECT * FROM attachments WHERE id = ?")
+            .bind(result.last_insert_rowid())
+            .fetch_one(&pool)
+            .await?;
+        store_variants(&pool, &store, attachment.id, variants).await?;
+        attachments.push(attachment);
+        created = true;
+    }
+
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+
+    Ok((status, Json(attachments)))
+}
+
+pub async fn download_attachment<O: AttachmentOwner>(
+    State(pool): State<SqlitePool>,
+    Extension(store): Extension<SharedStore>,
+    Path((id, attachment_id)): Path<(i64, i64)>,
+) -> Result<Response> {
+    let attachment = fetch_attachment::<O>(&pool, id, attachment_id).await?;
+    let data = store.get(&attachment.sha256).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", attachment.filename),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+/// Serve a resized copy of a photo. Variants missing for photos uploaded
+/// before resizing existed are generated on first request.
+pub async fn download_variant<O: AttachmentOwner>(
+    State(pool): State<SqlitePool>,
+    Extension(store): Extension<SharedStore>,
+    Path((id, attachment_id, variant)): Path<(i64, i64, String)>,
+) -> Result<Response> {
+    let attachment = fetch_attachment::<O>(&pool, id, attachment_id).await?;
+    let not_found = || {
+        AppError::NotFound(format!(
+            "Attachment {} has no {} variant",
+            attachment_id, variant
+        ))
+    };
+    if !images::VARIANTS.iter().any(|(name, _)| *name == variant)
+        || !images::is_processable(&attachment.content_type)
+    {
+        return Err(not_found());
+    }
+
+    let query = "SELECT * FROM attachment_variants WHERE attachment_id = ? AND variant = ?";
+    let mut found = sqlx::query_as::<_, AttachmentVariant>(query)
+        .bind(attachment.id)
+        .bind(&variant)
+        .fetch_optional(&pool)
+        .await?;
+    if found.is_none() {
+        let original = store.get(&attachment.sha256).await?;
+        let rendered = render_variants(original).await?;
+        store_variants(&pool, &store, attachment.id, rendered).await?;
+        found = sqlx::query_as::<_, AttachmentVariant>(query)
+            .bind(attachment.id)
+            .bind(&variant)
+            .fetch_optional(&pool)
+            .await?;
+    }
+    let found = found.ok_or_else(not_found)?;
+    let data = store.get(&found.sha256).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, found.content_type),
+            // An attachment's contents never change, so variants can be cached
+            (header::CACHE_CONTROL, "private, max-age=604800".to_string()),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+pub async fn delete_attachment<O: AttachmentOwner>(
+    State(pool): State<SqlitePool>,
+    Extension(store): Extension<SharedStore>,
+    Path((id, attachment_id)): Path<(i64, i64)>,
+) -> Result<StatusCode> {
+    let attachment = fetch_attachment::<O>(&pool, id, attachment_id).await?;
+
+    // Variants go with it via ON DELETE CASCADE
+    sqlx::query("DELETE FROM attachments WHERE id = ?")
+        .bind(attachment.id)
+        .execute(&pool)
+        .await?;
+
+    release_blobs(&pool, &store).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,323 @@
+use crate::error::{AppError, Result};
+use crate::models::{Contractor, ContractorStats, CreateContractor, UpdateContractor};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+const ENTITY_TYPES: &[&str] = &["individual", "llc", "partnership", "corporation"];
+
+fn validate_entity_type(entity_type: &str) -> Result<()> {
+    if ENTITY_TYPES.contains(&entity_type) {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown entity type '{}'",
+            entity_type
+        )))
+    }
+}
+
+/// Job history rolled up from maintenance records linked to each contractor.
+/// Turnaround is measured from `scheduled_date` to `completed_date`.
+const STATS_QUERY: &str = r#"
+    SELECT
+        c.id AS contractor_id,
+        c.name,
+        c.trade,
+        c.hourly_rate,
+        COUNT(m.id) AS jobs_total,
+        COALESCE(SUM(CASE WHEN m.status = 'completed' THEN 1 ELSE 0 END), 0) AS jobs_completed,
+        COALESCE(SUM(CASE WHEN m.status = 'cancelled' THEN 1 ELSE 0 END), 0) AS jobs_cancelled,
+        COALESCE(SUM(CASE WHEN m.status = 'completed' THEN m.cost END), 0.0) AS total_cost,
+        ROUND(AVG(CASE WHEN m.status = 'completed' THEN m.cost END), 2) AS average_cost,
+        ROUND(AVG(
+            CASE WHEN m.status = 'completed'
+                  AND m.scheduled_date IS NOT NULL
+                  AND m.completed_date IS NOT NULL
+            THEN julianday(m.completed_date) - julianday(m.scheduled_date) END
+        ), 1) AS average_days_to_complete,
+        (c.license_expiry IS NOT NULL AND julianday(c.license_expiry) < julianday('now')) AS license_expired,
+        (c.insurance_expiry IS NOT NULL AND julianday(c.insurance_expiry) < julianday('now')) AS insurance_expired
+    FROM contractors c
+    LEFT JOIN maintenance_records m ON m.contractor_id = c.id
+    WHERE (?1 IS NULL OR c.id = ?1)
+      AND (?2 IS NULL OR c.trade = ?2)
+    GROUP BY c.id
+    ORDER BY c.name ASC
+"#;
+
+#[derive(Debug, Deserialize)]
+pub struct ContractorStatsQuery {
+    pub trade: Option<String>,
+}
+
+/// Look up a contractor's name when linking it to another record
+pub async fn contractor_name(pool: &SqlitePool, id: i64) -> Result<String> {
+    sqlx::query_scalar("SELECT name FROM contractors WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Contractor with id {} does not exist", id)))
+}
+
+pub async fn list_contractors(State(pool): State<SqlitePool>) -> Result<Json<Vec<Contractor>>> {
+    let contractors =
+        sqlx::query_as::<_, Contractor>("SELECT * FROM contractors ORDER BY name ASC")
+            .fetch_all(&pool)
+            .await?;
+
+    Ok(Json(contractors))
+}
+
+pub async fn get_contractor(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Contractor>> {
+    let contractor = sqlx::query_as::<_, Contractor>("SELECT * FROM contractors WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Contractor with id {} not found", id)))?;
+
+    Ok(Json(contractor))
+}
+
+pub async fn create_contractor(
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateContractor>,
+) -> Result<(StatusCode, Json<Contractor>)> {
+    let entity_type = payload
+        .entity_type
+        .clone()
+        .unwrap_or_else(|| "individual".to_string());
+    validate_entity_type(&entity_type)?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO contractors (
+            name, entity_type, trade, contact_name, tax_id, address,
+            city, state, zip_code, email, phone, license_number,
+            license_expiry, insurance_expiry, hourly_rate, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&payload.name)
+    .bind(&entity_type)
+    .bind(&payload.trade)
+    .bind(&payload.contact_name)
+    .bind(&payload.tax_id)
+    .bind(&payload.address)
+    .bind(&payload.city)
+    .bind(&payload.state)
+    .bind(&payload.zip_code)
+    .bind(&payload.email)
+    .bind(&payload.phone)
+    .bind(&payload.license_number)
+    .bind(payload.license_expiry)
+    .bind(payload.insurance_expiry)
+    .bind(payload.hourly_rate)
+    .bind(&payload.notes)
+    .execute(&pool)
+    .await?;
+
+    let contractor = sqlx::query_as::<_, Contractor>("SELECT * FROM contractors WHERE id = ?")
+        .bind(result.last_insert_rowid())
+        .fetch_one(&pool)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(contractor)))
+}
+
+pub async fn update_contractor(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateContractor>,
+) -> Result<Json<Contractor>> {
+    sqlx::query("SELECT id FROM contractors WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Contractor with id {} not found", id)))?;
+
+    if let Some(entity_type) = &payload.entity_type {
+        validate_entity_type(entity_type)?;
+    }
+
+    let mut query = String::from("UPDATE contractors SET ");
+    let mut updates = Vec::new();
+
+    if payload.name.is_some() {
+        updates.push("name = ?");
+    }
+    if payload.entity_type.is_some() {
+        updates.push("entity_type = ?");
+    }
+    if payload.trade.is_some() {
+        updates.push("trade = ?");
+    }
+    if payload.contact_name.is_some() {
+        updates.push("contact_name = ?");
+    }
+    if payload.tax_id.is_some() {
+        updates.push("tax_id = ?");
+    }
+    if payload.address.is_some() {
+        updates.push("address = ?");
+    }
+    if payload.city.is_some() {
+        updates.push("city = ?");
+    }
+    if payload.state.is_some() {
+        updates.push("state = ?");
+    }
+    if payload.zip_code.is_some() {
+        updates.push("zip_code = ?");
+    }
+    if payload.email.is_some() {
+        updates.push("email = ?");
+    }
+    if payload.phone.is_some() {
+        updates.push("phone = ?");
+    }
+    if payload.license_number.is_some() {
+        updates.push("license_number = ?");
+    }
+    if payload.license_expiry.is_some() {
+        updates.push("license_expiry = ?");
+    }
+    if payload.insurance_expiry.is_some() {
+        updates.push("insurance_expiry = ?");
+    }
+    if payload.hourly_rate.is_some() {
+        updates.push("hourly_rate = ?");
+    }
+    if payload.notes.is_some() {
+        updates.push("notes = ?");
+    }
+
+    updates.push("updated_at = CURRENT_TIMESTAMP");
+
+    query.push_str(&updates.join(", "));
+    query.push_str(" WHERE id = ?");
+
+    let mut q = sqlx::query(&query);
+
+    if let Some(v) = &payload.name {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.entity_type {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.trade {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.contact_name {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.tax_id {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.address {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.city {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.state {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.zip_code {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.email {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.phone {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.license_number {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.license_expiry {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.insurance_expiry {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.hourly_rate {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.notes {
+        q = q.bind(v);
+    }
+
+    q = q.bind(id);
+    q.execute(&pool).await?;
+
+    // Keep the denormalised name on maintenance records in step
+    if let Some(name) = &payload.name {
+        sqlx::query("UPDATE maintenance_records SET contractor = ? WHERE contractor_id = ?")
+            .bind(name)
+            .bind(id)
+            .execute(&pool)
+            .await?;
+    }
+
+    let contractor = sqlx::query_as::<_, Contractor>("SELECT * FROM contractors WHERE id = ?")
+        .bind(id)
+        .fetch_one(&pool)
+        .await?;
+
+    Ok(Json(contractor))
+}
+
+pub async fn delete_contractor(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM contractors WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Contractor with id {} not found",
+            id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_contractor_stats(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<ContractorStatsQuery>,
+) -> Result<Json<Vec<ContractorStats>>> {
+    let stats = sqlx::query_as::<_, ContractorStats>(STATS_QUERY)
+        .bind(None::<i64>)
+        .bind(&params.trade)
+        .fetch_all(&pool)
+        .await?;
+
+    Ok(Json(stats))
+}
+
+pub async fn get_contractor_stats(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<ContractorStats>> {
+    let stats = sqlx::query_as::<_, ContractorStats>(STATS_QUERY)
+        .bind(id)
+        .bind(None::<String>)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Contractor with id {} not found", id)))?;
+
+    Ok(Json(stats))
+}
@@ -0,0 +1,414 @@
+use crate::error::{AppError, Result};
+use crate::jobs::lease_renewals::expire_renewal;
+use crate::leasing::deposits::{
+    accrued_interest, days_until, deposit_ledger, disposition_statement, held_balance,
+    lease_tenant_names, reopen_if_owed, rule_for_property, settle_if_paid, DEPOSIT_ENTRY_TYPES,
+};
+use crate::models::{
+    AccrueDepositInterest, CreateDepositTransaction, DepositDisposition, DepositLedger,
+    DepositRule, DispositionDeadline, DispositionStatement, RecordMoveOut, UpsertDepositRule,
+};
+use crate::occupancy::refresh_property;
+use crate::routes::leases::{fetch_lease, sync_lease_tenants};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Deserialize)]
+pub struct DispositionQuery {
+    pub status: Option<String>,
+}
+
+pub async fn get_deposit_ledger(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+) -> Result<Json<DepositLedger>> {
+    Ok(Json(deposit_ledger(&pool, lease_id).await?))
+}
+
+pub async fn create_deposit_transaction(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+    Json(payload): Json<CreateDepositTransaction>,
+) -> Result<(StatusCode, Json<DepositLedger>)> {
+    let lease = fetch_lease(&pool, lease_id).await?;
+
+    if !DEPOSIT_ENTRY_TYPES.contains(&payload.entry_type.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unknown deposit entry type '{}'",
+            payload.entry_type
+        )));
+    }
+    if payload.amount <= 0.0 {
+        return Err(AppError::BadRequest(
+            "Amount must be greater than zero".to_string(),
+        ));
+    }
+    if payload.entry_type == "deduction"
+        && payload
+            .description
+            .as_deref()
+            .is_none_or(|d| d.trim().is_empty())
+    {
+        return Err(AppError::BadRequest(
+            "A deduction needs a reason in description".to_string(),
+        ));
+    }
+    if let Some(maintenance_id) = payload.maintenance_id {
+        let property_id: i64 =
+            sqlx::query_scalar("SELECT property_id FROM maintenance_records WHERE id = ?")
+                .bind(maintenance_id)
+                .fetch_optional(&pool)
+                .await?
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!(
+                        "Maintenance record {} does not exist",
+                        maintenance_id
+                    ))
+                })?;
+        if property_id != lease.property_id {
+            return Err(AppError::BadRequest(format!(
+                "Maintenance record {} is not for property {}",
+                maintenance_id, lease.property_id
+            )));
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+
+    if matches!(payload.entry_type.as_str(), "deduction" | "refund") {
+        let held = held_balance(&mut *tx, lease_id).await?;
+        if payload.amount > held {
+            return Err(AppError::Conflict(format!(
+                "Only ${:.2} of the deposit is held on lease {}",
+                held, lease_id
+            )));
+        }
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO deposit_transactions (
+            lease_id, entry_type, amount, description, maintenance_id, transaction_date
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(lease_id)
+    .bind(&payload.entry_type)
+    .bind(payload.amount)
+    .bind(&payload.description)
+    .bind(payload.maintenance_id)
+    .bind(payload.transaction_date.unwrap_or_else(Utc::now))
+    .execute(&mut *tx)
+    .await?;
+
+    if matches!(payload.entry_type.as_str(), "deduction" | "refund") {
+        settle_if_paid(&mut tx, lease_id).await?;
+    } else {
+        reopen_if_owed(&mut tx, lease_id).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(deposit_ledger(&pool, lease_id).await?),
+    ))
+}
+
+pub async fn delete_deposit_transaction(
+    State(pool): State<SqlitePool>,
+    Path((lease_id, transaction_id)): Path<(i64, i64)>,
+) -> Result<StatusCode> {
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query(
+        r#"
+        DELETE FROM deposit_transactions
+        WHERE id = ? AND lease_id = ? AND entry_type NOT IN ('transfer_in', 'transfer_out')
+        "#,
+    )
+    .bind(transaction_id)
+    .bind(lease_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Deposit transaction {} not found on lease {}",
+            transaction_id, lease_id
+        )));
+    }
+
+    // Removing a refund or deduction can leave money owed again, and removing
+    // a receipt or interest can leave nothing to return
+    reopen_if_owed(&mut tx, lease_id).await?;
+    settle_if_paid(&mut tx, lease_id).await?;
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Credit interest owed under the property's deposit rule
+pub async fn accrue_deposit_interest(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+    Json(payload): Json<AccrueDepositInterest>,
+) -> Result<Json<DepositLedger>> {
+    let lease = fetch_lease(&pool, lease_id).await?;
+    let rule = rule_for_property(&pool, lease.property_id).await?;
+    let Some(rate) = rule.interest_rate.filter(|r| *r > 0.0) else {
+        return Err(AppError::BadRequest(format!(
+            "No deposit interest is required in {}",
+            rule.state
+        )));
+    };
+
+    let through = payload.through.unwrap_or_else(Utc::now);
+    let interest = accrued_interest(&pool, lease_id, rate, through).await?;
+
+    if interest > 0.0 {
+        sqlx::query(
+            r#"
+            INSERT INTO deposit_transactions (lease_id, entry_type, amount, description, transaction_date)
+            VALUES (?, 'interest', ?, ?, ?)
+            "#,
+        )
+        .bind(lease_id)
+        .bind(interest)
+        .bind(format!("Interest at {}% per year", rate))
+        .bind(through)
+        .execute(&pool)
+        .await?;
+    }
+
+    Ok(Json(deposit_ledger(&pool, lease_id).await?))
+}
+
+/// Record a move-out: the lease ends on that date, and the deposit must be
+/// returned within the statutory period, tracked by a `deposit_return` event
+pub async fn record_move_out(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+    Json(payload): Json<RecordMoveOut>,
+) -> Result<(StatusCode, Json<DispositionStatement>)> {
+    let lease = fetch_lease(&pool, lease_id).await?;
+    if payload.move_out_date < lease.start_date {
+        return Err(AppError::BadRequest(
+            "move_out_date is before the lease started".to_string(),
+        ));
+    }
+
+    let rule = rule_for_property(&pool, lease.property_id).await?;
+    let deadline = payload.move_out_date + Duration::days(rule.return_days.into());
+    let names = lease_tenant_names(&pool, lease_id).await?;
+    let now = Utc::now();
+
+    let mut tx = pool.begin().await?;
+
+    let event_id = sqlx::query(
+        r#"
+        INSERT INTO calendar_events (
+            title, description, event_type, property_id, unit_id,
+            start_time, reminder_minutes
+        )
+        VALUES (?, ?, 'deposit_return', ?, ?, ?, ?)
+        "#,
+    )
+    .bind(format!("Deposit return due: {}", names.join(", ")))
+    .bind(format!(
+        "Return or account for the deposit on lease #{} within {} days of move-out ({})",
+        lease_id, rule.return_days, rule.state
+    ))
+    .bind(lease.property_id)
+    .bind(lease.unit_id)
+    .bind(deadline)
+    .bind(3 * 24 * 60)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    sqlx::query(
+        r#"
+        INSERT INTO deposit_dispositions (
+            lease_id, move_out_date, return_deadline, forwarding_address, event_id, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(lease_id)
+    .bind(payload.move_out_date)
+    .bind(deadline)
+    .bind(&payload.forwarding_address)
+    .bind(event_id)
+    .bind(&payload.notes)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Conflict(format!(
+            "A move-out is already recorded for lease {}",
+            lease_id
+        )),
+        _ => e.into(),
+    })?;
+
+    // Moving out ends the lease, early if need be
+    if lease.end_date.is_none_or(|end| payload.move_out_date < end) {
+        sqlx::query("UPDATE leases SET end_date = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+            .bind(payload.move_out_date)
+            .bind(lease_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    if payload.move_out_date <= now {
+        sqlx::query(
+            "UPDATE leases SET status = 'ended', updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(lease_id)
+        .execute(&mut *tx)
+        .await?;
+        expire_renewal(&mut tx, lease_id).await?;
+    }
+    // Nothing held means nothing to return
+    settle_if_paid(&mut tx, lease_id).await?;
+    sync_lease_tenants(&mut *tx, lease_id).await?;
+    refresh_property(&mut tx, lease.property_id, now).await?;
+
+    tx.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(disposition_statement(&pool, lease_id, now).await?),
+    ))
+}
+
+pub async fn get_disposition(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+) -> Result<Json<DispositionStatement>> {
+    Ok(Json(
+        disposition_statement(&pool, lease_id, Utc::now()).await?,
+    ))
+}
+
+/// Statement for the tenant's most recent move-out
+pub async fn get_tenant_disposition(
+    State(pool): State<SqlitePool>,
+    Path(tenant_id): Path<i64>,
+) -> Result<Json<DispositionStatement>> {
+    let lease_id: i64 = sqlx::query_scalar(
+        r#"
+        SELECT d.lease_id FROM deposit_dispositions d
+        JOIN lease_occupants o ON o.lease_id = d.lease_id
+        WHERE o.tenant_id = ?
+        ORDER BY d.move_out_date DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!(
+            "No move-out has been recorded for tenant {}",
+            tenant_id
+        ))
+    })?;
+
+    Ok(Json(
+        disposition_statement(&pool, lease_id, Utc::now()).await?,
+    ))
+}
+
+/// Deadline tracker: dispositions by how soon the deposit is due back
+pub async fn list_dispositions(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<DispositionQuery>,
+) -> Result<Json<Vec<DispositionDeadline>>> {
+    let dispositions = sqlx::query_as::<_, DepositDisposition>(
+        r#"
+        SELECT * FROM deposit_dispositions
+        WHERE (?1 IS NULL OR status = ?1)
+        ORDER BY return_deadline
+        "#,
+    )
+    .bind(&params.status)
+    .fetch_all(&pool)
+    .await?;
+
+    let now = Utc::now();
+    let mut deadlines = Vec::with_capacity(dispositions.len());
+
+    for disposition in dispositions {
+        let property_id: i64 = sqlx::query_scalar("SELECT property_id FROM leases WHERE id = ?")
+            .bind(disposition.lease_id)
+            .fetch_one(&pool)
+            .await?;
+        let outstanding = held_balance(&pool, disposition.lease_id).await?.max(0.0);
+        let overdue =
+            disposition.status == "open" && outstanding > 0.0 && now > disposition.return_deadline;
+
+        deadlines.push(DispositionDeadline {
+            property_id,
+            tenants: lease_tenant_names(&pool, disposition.lease_id).await?,
+            outstanding,
+            days_until_deadline: days_until(disposition.return_deadline, now),
+            overdue,
+            disposition,
+        });
+    }
+
+    Ok(Json(deadlines))
+}
+
+pub async fn list_deposit_rules(State(pool): State<SqlitePool>) -> Result<Json<Vec<DepositRule>>> {
+    let rules = sqlx::query_as::<_, DepositRule>("SELECT * FROM deposit_rules ORDER BY state")
+        .fetch_all(&pool)
+        .await?;
+
+    Ok(Json(rules))
+}
+
+pub async fn upsert_deposit_rule(
+    State(pool): State<SqlitePool>,
+    Path(state): Path<String>,
+    Json(payload): Json<UpsertDepositRule>,
+) -> Result<Json<DepositRule>> {
+    if payload.return_days <= 0 {
+        return Err(AppError::BadRequest(
+            "return_days must be greater than zero".to_string(),
+        ));
+    }
+    if payload.interest_rate.is_some_and(|r| r < 0.0) {
+        return Err(AppError::BadRequest(
+            "interest_rate cannot be negative".to_string(),
+        ));
+    }
+
+    let rule = sqlx::query_as::<_, DepositRule>(
+        r#"
+        INSERT INTO deposit_rules (state, return_days, interest_rate, notes)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT (state) DO UPDATE SET
+            return_days = excluded.return_days,
+            interest_rate = excluded.interest_rate,
+            notes = excluded.notes,
+            updated_at = CURRENT_TIMESTAMP
+        RETURNING *
+        "#,
+    )
+    .bind(state.to_uppercase())
+    .bind(payload.return_days)
+    .bind(payload.interest_rate)
+    .bind(&payload.notes)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(Json(rule))
+}
@@ -0,0 +1,206 @@
+use crate::error::{AppError, Result};
+use crate::leasing::escalation::{parse_month_key, rent_schedule, ESCALATION_TYPES};
+use crate::models::{CpiIndex, CreateRentEscalation, RentSchedule, UpsertCpiIndex};
+use crate::routes::leases::fetch_lease;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Deserialize)]
+pub struct RentScheduleQuery {
+    pub through: Option<DateTime<Utc>>,
+}
+
+pub async fn get_rent_schedule(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+    Query(params): Query<RentScheduleQuery>,
+) -> Result<Json<RentSchedule>> {
+    let lease = fetch_lease(&pool, lease_id).await?;
+
+    Ok(Json(
+        rent_schedule(&pool, &lease, params.through, Utc::now()).await?,
+    ))
+}
+
+pub async fn create_rent_escalation(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+    Json(payload): Json<CreateRentEscalation>,
+) -> Result<(StatusCode, Json<RentSchedule>)> {
+    let lease = fetch_lease(&pool, lease_id).await?;
+
+    if !ESCALATION_TYPES.contains(&payload.escalation_type.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unknown escalation type '{}'",
+            payload.escalation_type
+        )));
+    }
+    if payload.effective_date <= lease.start_date
+        || lease
+            .end_date
+            .is_some_and(|end| payload.effective_date > end)
+    {
+        return Err(AppError::BadRequest(
+            "effective_date must fall after the lease starts and before it ends".to_string(),
+        ));
+    }
+    if payload.interval_months.is_some_and(|m| m <= 0) {
+        return Err(AppError::BadRequest(
+            "interval_months must be greater than zero".to_string(),
+        ));
+    }
+    match payload.escalation_type.as_str() {
+        "fixed" if payload.amount.is_none_or(|a| a <= 0.0) => {
+            return Err(AppError::BadRequest(
+                "A fixed step needs the new monthly rent in amount".to_string(),
+            ));
+        }
+        "percent" if payload.percent.is_none() => {
+            return Err(AppError::BadRequest(
+                "A percent escalation needs percent".to_string(),
+            ));
+        }
+        // A cut of 100% or more would take the rent to zero or below
+        "percent" if payload.percent.is_some_and(|p| p <= -100.0) => {
+            return Err(AppError::BadRequest(
+                "percent must be greater than -100".to_string(),
+            ));
+        }
+        "cpi" => {
+            if payload
+                .cpi_floor
+                .into_iter()
+                .chain(payload.cpi_cap)
+                .any(|p| p <= -100.0)
+            {
+                return Err(AppError::BadRequest(
+                    "cpi_floor and cpi_cap must be greater than -100".to_string(),
+                ));
+            }
+            if let (Some(floor), Some(cap)) = (payload.cpi_floor, payload.cpi_cap) {
+                if floor > cap {
+                    return Err(AppError::BadRequest(
+                        "cpi_floor cannot exceed cpi_cap".to_string(),
+                    ));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO rent_escalations (
+            lease_id, escalation_type, effective_date, amount, percent,
+            interval_months, cpi_floor, cpi_cap, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(lease_id)
+    .bind(&payload.escalation_type)
+    .bind(payload.effective_date)
+    .bind(payload.amount)
+    .bind(payload.percent)
+    .bind(payload.interval_months)
+    .bind(payload.cpi_floor)
+    .bind(payload.cpi_cap)
+    .bind(&payload.notes)
+    .execute(&pool)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(rent_schedule(&pool, &lease, None, Utc::now()).await?),
+    ))
+}
+
+pub async fn delete_rent_escalation(
+    State(pool): State<SqlitePool>,
+    Path((lease_id, escalation_id)): Path<(i64, i64)>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM rent_escalations WHERE id = ? AND lease_id = ?")
+        .bind(escalation_id)
+        .bind(lease_id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Rent escalation {} not found on lease {}",
+            escalation_id, lease_id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_cpi_index(State(pool): State<SqlitePool>) -> Result<Json<Vec<CpiIndex>>> {
+    let index = sqlx::query_as::<_, CpiIndex>("SELECT * FROM cpi_index ORDER BY period")
+        .fetch_all(&pool)
+        .await?;
+
+    Ok(Json(index))
+}
+
+pub async fn upsert_cpi_index(
+    State(pool): State<SqlitePool>,
+    Path(period): Path<String>,
+    Json(payload): Json<UpsertCpiIndex>,
+) -> Result<Json<CpiIndex>> {
+    if parse_month_key(&period).is_none() {
+        return Err(AppError::BadRequest(format!(
+            "Period '{}' is not a month in YYYY-MM form",
+            period
+        )));
+    }
+    if payload.index_value <= 0.0 {
+        return Err(AppError::BadRequest(
+            "index_value must be greater than zero".to_string(),
+        ));
+    }
+
+    let index = sqlx::query_as::<_, CpiIndex>(
+        r#"
+        INSERT INTO cpi_index (period, index_value, notes)
+        VALUES (?, ?, ?)
+        ON CONFLICT (period) DO UPDATE SET
+            index_value = excluded.index_value,
+            notes = excluded.notes,
+            updated_at = CURRENT_TIMESTAMP
+        RETURNING *
+        "#,
+    )
+    .bind(&period)
+    .bind(payload.index_value)
+    .bind(&payload.notes)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(Json(index))
+}
+
+pub async fn delete_cpi_index(
+    State(pool): State<SqlitePool>,
+    Path(period): Path<String>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM cpi_index WHERE period = ?")
+        .bind(&period)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "No CPI index entered for {}",
+            period
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
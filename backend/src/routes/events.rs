@@ -1,5 +1,6 @@
 use crate::error::{AppError, Result};
 use crate::models::{CalendarEvent, CreateEvent, UpdateEvent};
+use crate::routes::units::{check_unit, unit_property};
 use axum::{
     extract::{Path, State},
     http::StatusCode,
@@ -33,19 +34,30 @@ pub async fn create_event(
     State(pool): State<SqlitePool>,
     Json(payload): Json<CreateEvent>,
 ) -> Result<(StatusCode, Json<CalendarEvent>)> {
+    // A unit implies its property
+    let property_id = match (payload.property_id, payload.unit_id) {
+        (None, Some(unit_id)) => Some(unit_property(&pool, unit_id).await?),
+        (Some(property_id), unit_id) => {
+            check_unit(&pool, property_id, unit_id).await?;
+            Some(property_id)
+        }
+        (None, None) => None,
+    };
+
     let result = sqlx::query(
         r#"
         INSERT INTO calendar_events (
-            title, description, event_type, property_id,
+            title, description, event_type, property_id, unit_id,
             start_time, end_time, reminder_minutes
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&payload.title)
     .bind(&payload.description)
     .bind(&payload.event_type)
-    .bind(payload.property_id)
+    .bind(property_id)
+    .bind(payload.unit_id)
     .bind(payload.start_time)
     .bind(payload.end_time)
     .bind(payload.reminder_minutes)
@@ -65,11 +77,24 @@ pub async fn update_event(
     Path(id): Path<i64>,
     Json(payload): Json<UpdateEvent>,
 ) -> Result<Json<CalendarEvent>> {
-    sqlx::query("SELECT id FROM calendar_events WHERE id = ?")
-        .bind(id)
-        .fetch_optional(&pool)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Event with id {} not found", id)))?;
+    let (current_property_id,): (Option<i64>,) =
+        sqlx::query_as("SELECT property_id FROM calendar_events WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Event with id {} not found", id)))?;
+
+    let property_id = payload.property_id.or(current_property_id);
+    if let Some(unit_id) = payload.unit_id {
+        match property_id {
+            Some(property_id) => check_unit(&pool, property_id, Some(unit_id)).await?,
+            None => {
+                return Err(AppError::BadRequest(
+                    "An event needs a property before it can reference a unit".to_string(),
+                ))
+            }
+        }
+    }
 
     let mut query = String::from("UPDATE calendar_events SET ");
     let mut updates = Vec::new();
@@ -86,6 +111,11 @@ pub async fn update_event(
     if payload.property_id.is_some() {
         updates.push("property_id = ?");
     }
+    if payload.unit_id.is_some() {
+        updates.push("unit_id = ?");
+    } else if property_id != current_property_id {
+        updates.push("unit_id = NULL");
+    }
     if payload.start_time.is_some() {
         updates.push("start_time = ?");
     }
@@ -118,6 +148,9 @@ pub async fn update_event(
     if let Some(v) = payload.property_id {
         q = q.bind(v);
     }
+    if let Some(v) = payload.unit_id {
+        q = q.bind(v);
+    }
     if let Some(v) = payload.start_time {
         q = q.bind(v);
     }
@@ -0,0 +1,188 @@
+use crate::error::{AppError, Result};
+use crate::models::{CreateExpense, Expense, UpdateExpense, EXPENSE_CATEGORIES};
+use crate::routes::contractors::contractor_name;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::SqlitePool;
+
+fn validate_category(category: &str) -> Result<()> {
+    if EXPENSE_CATEGORIES.contains(&category) {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown expense category '{}'",
+            category
+        )))
+    }
+}
+
+fn validate_amount(amount: f64) -> Result<()> {
+    if amount > 0.0 {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(
+            "Expense amount must be positive".to_string(),
+        ))
+    }
+}
+
+pub async fn list_expenses(State(pool): State<SqlitePool>) -> Result<Json<Vec<Expense>>> {
+    let expenses =
+        sqlx::query_as::<_, Expense>("SELECT * FROM expenses ORDER BY expense_date DESC")
+            .fetch_all(&pool)
+            .await?;
+
+    Ok(Json(expenses))
+}
+
+pub async fn get_expense(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Expense>> {
+    let expense = sqlx::query_as::<_, Expense>("SELECT * FROM expenses WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Expense with id {} not found", id)))?;
+
+    Ok(Json(expense))
+}
+
+pub async fn create_expense(
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateExpense>,
+) -> Result<(StatusCode, Json<Expense>)> {
+    validate_category(&payload.category)?;
+    validate_amount(payload.amount)?;
+    if let Some(contractor_id) = payload.contractor_id {
+        contractor_name(&pool, contractor_id).await?;
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO expenses (
+            property_id, category, amount, expense_date, payee,
+            contractor_id, description
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(payload.property_id)
+    .bind(&payload.category)
+    .bind(payload.amount)
+    .bind(payload.expense_date)
+    .bind(&payload.payee)
+    .bind(payload.contractor_id)
+    .bind(&payload.description)
+    .execute(&pool)
+    .await?;
+
+    let expense = sqlx::query_as::<_, Expense>("SELECT * FROM expenses WHERE id = ?")
+        .bind(result.last_insert_rowid())
+        .fetch_one(&pool)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(expense)))
+}
+
+pub async fn update_expense(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateExpense>,
+) -> Result<Json<Expense>> {
+    sqlx::query("SELECT id FROM expenses WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Expense with id {} not found", id)))?;
+
+    if let Some(category) = &payload.category {
+        validate_category(category)?;
+    }
+    if let Some(amount) = payload.amount {
+        validate_amount(amount)?;
+    }
+    if let Some(contractor_id) = payload.contractor_id {
+        contractor_name(&pool, contractor_id).await?;
+    }
+
+    let mut query = String::from("UPDATE expenses SET ");
+    let mut updates = Vec::new();
+
+    if payload.category.is_some() {
+        updates.push("category = ?");
+    }
+    if payload.amount.is_some() {
+        updates.push("amount = ?");
+    }
+    if payload.expense_date.is_some() {
+        updates.push("expense_date = ?");
+    }
+    if payload.payee.is_some() {
+        updates.push("payee = ?");
+    }
+    if payload.contractor_id.is_some() {
+        updates.push("contractor_id = ?");
+    }
+    if payload.description.is_some() {
+        updates.push("description = ?");
+    }
+
+    updates.push("updated_at = CURRENT_TIMESTAMP");
+
+    query.push_str(&updates.join(", "));
+    query.push_str(" WHERE id = ?");
+
+    let mut q = sqlx::query(&query);
+
+    if let Some(v) = &payload.category {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.amount {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.expense_date {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.payee {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.contractor_id {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.description {
+        q = q.bind(v);
+    }
+
+    q = q.bind(id);
+    q.execute(&pool).await?;
+
+    let expense = sqlx::query_as::<_, Expense>("SELECT * FROM expenses WHERE id = ?")
+        .bind(id)
+        .fetch_one(&pool)
+        .await?;
+
+    Ok(Json(expense))
+}
+
+pub async fn delete_expense(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM expenses WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Expense with id {} not found",
+            id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
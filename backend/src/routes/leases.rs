@@ -0,0 +1,739 @@
+use crate::error::{AppError, Result};
+use crate::jobs::lease_renewals::expire_renewal;
+use crate::models::{
+    AddLeaseOccupant, CreateLease, Lease, LeaseConflict, LeaseDetail, LeaseOccupant, UpdateLease,
+};
+use crate::occupancy::refresh_property;
+use crate::routes::units::check_unit;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{Sqlite, SqliteExecutor, SqlitePool, Transaction};
+
+#[derive(Debug, Deserialize)]
+pub struct LeaseQuery {
+    pub status: Option<String>,
+    pub property_id: Option<i64>,
+    pub unit_id: Option<i64>,
+}
+
+// A tenant's current lease is their active one, else pending, else the most
+// recent; the tenant row mirrors its terms for older clients. Open-ended
+// month-to-month leases report the end of their first month.
+const SYNC_TENANT_TERMS: &str = r#"
+    WITH ranked AS (
+        SELECT o.tenant_id, l.*,
+               ROW_NUMBER() OVER (
+                   PARTITION BY o.tenant_id
+                   ORDER BY CASE l.status WHEN 'active' THEN 0 WHEN 'pending' THEN 1 ELSE 2 END,
+                            l.start_date DESC
+               ) AS rank
+        FROM leases l
+        JOIN lease_occupants o ON o.lease_id = l.id
+        WHERE o.tenant_id IN (SELECT tenant_id FROM lease_occupants WHERE lease_id = ?1)
+           OR o.tenant_id = ?2
+    )
+    UPDATE tenants SET
+        property_id = cur.property_id,
+        unit_id = cur.unit_id,
+        lease_start = cur.start_date,
+        lease_end = COALESCE(cur.end_date, datetime(cur.start_date, '+1 month')),
+        monthly_rent = cur.monthly_rent,
+        deposit_amount = cur.deposit_amount,
+        status = CASE cur.status WHEN 'ended' THEN 'past' ELSE cur.status END,
+        updated_at = CURRENT_TIMESTAMP
+    FROM ranked cur
+    WHERE cur.tenant_id = tenants.id AND cur.rank = 1
+"#;
+
+/// Refresh the mirrored lease terms of everyone on a lease
+pub async fn sync_lease_tenants<'e>(
+    executor: impl SqliteExecutor<'e>,
+    lease_id: i64,
+) -> Result<()> {
+    sqlx::query(SYNC_TENANT_TERMS)
+        .bind(lease_id)
+        .bind(None::<i64>)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Refresh one tenant's mirrored lease terms
+pub async fn sync_tenant<'e>(executor: impl SqliteExecutor<'e>, tenant_id: i64) -> Result<()> {
+    sqlx::query(SYNC_TENANT_TERMS)
+        .bind(None::<i64>)
+        .bind(tenant_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// The lease a tenant's mirrored terms come from
+pub async fn current_lease_id<'e>(
+    executor: impl SqliteExecutor<'e>,
+    tenant_id: i64,
+) -> Result<Option<i64>> {
+    Ok(sqlx::query_scalar(
+        r#"
+        SELECT l.id FROM leases l
+        JOIN lease_occupants o ON o.lease_id = l.id
+        WHERE o.tenant_id = ?
+        ORDER BY CASE l.status WHEN 'active' THEN 0 WHEN 'pending' THEN 1 ELSE 2 END,
+                 l.start_date DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_optional(executor)
+    .await?)
+}
+
+fn validate_lease_type(lease_type: &str) -> Result<()> {
+    if matches!(lease_type, "fixed" | "month_to_month") {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown lease type '{}'",
+            lease_type
+        )))
+    }
+}
+
+fn validate_status(status: &str) -> Result<()> {
+    if matches!(status, "pending" | "active" | "ended") {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown lease status '{}'",
+            status
+        )))
+    }
+}
+
+/// Due days stop at the 28th so every month has one
+fn validate_rent_due_day(day: i32) -> Result<()> {
+    if (1..=28).contains(&day) {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(
+            "rent_due_day must be between 1 and 28".to_string(),
+        ))
+    }
+}
+
+fn validate_role(role: &str) -> Result<()> {
+    if matches!(role, "primary" | "co_tenant" | "occupant") {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown occupant role '{}'",
+            role
+        )))
+    }
+}
+
+/// Reject leases that end before (or the moment) they start
+pub fn check_lease_dates(start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<()> {
+    match end {
+        Some(end) if end <= start => Err(AppError::BadRequest(format!(
+            "Lease end {} must be after its start {}",
+            end.format("%Y-%m-%d"),
+            start.format("%Y-%m-%d")
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Reject a pending or active lease whose dates overlap another pending or
+/// active lease on the same unit. Leases without a unit cover the whole
+/// property, so they collide with every lease there. Open-ended leases run
+/// forever.
+pub async fn check_overlap(
+    tx: &mut Transaction<'_, Sqlite>,
+    property_id: i64,
+    unit_id: Option<i64>,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    exclude_lease_id: Option<i64>,
+) -> Result<()> {
+    let candidates = sqlx::query_as::<_, Lease>(
+        r#"
+        SELECT * FROM leases
+        WHERE property_id = ?1
+          AND status IN ('pending', 'active')
+          AND (?2 IS NULL OR unit_id IS NULL OR unit_id = ?2)
+          AND (?3 IS NULL OR id != ?3)
+        ORDER BY start_date
+        "#,
+    )
+    .bind(property_id)
+    .bind(unit_id)
+    .bind(exclude_lease_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let overlaps = |other: &Lease| {
+        let starts_before_other_ends = other.end_date.is_none_or(|e| start <= e);
+        let other_starts_before_end = end.is_none_or(|e| other.start_date <= e);
+        starts_before_other_ends && other_starts_before_end
+    };
+
+    let Some(other) = candidates.into_iter().find(overlaps) else {
+        return Ok(());
+    };
+
+    let tenant: Option<(i64, String)> = sqlx::query_as(
+        r#"
+        SELECT t.id, t.first_name || ' ' || t.last_name
+        FROM lease_occupants o
+        JOIN tenants t ON t.id = o.tenant_id
+        WHERE o.lease_id = ?
+        ORDER BY CASE o.role WHEN 'primary' THEN 0 WHEN 'co_tenant' THEN 1 ELSE 2 END
+        LIMIT 1
+        "#,
+    )
+    .bind(other.id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let place = match other.unit_id {
+        Some(unit_id) => format!("unit {}", unit_id),
+        None => format!("property {}", other.property_id),
+    };
+    let message = match &tenant {
+        Some((_, name)) => format!(
+            "Overlaps {}'s {} lease #{} on {}",
+            name, other.status, other.id, place
+        ),
+        None => format!("Overlaps {} lease #{} on {}", other.status, other.id, place),
+    };
+
+    let conflict = LeaseConflict {
+        lease_id: other.id,
+        unit_id: other.unit_id,
+        status: other.status,
+        start_date: other.start_date,
+        end_date: other.end_date,
+        tenant_id: tenant.as_ref().map(|(id, _)| *id),
+        tenant_name: tenant.map(|(_, name)| name),
+    };
+
+    Err(AppError::ConflictWith {
+        message,
+        conflict: serde_json::to_value(conflict).map_err(|e| AppError::Internal(e.to_string()))?,
+    })
+}
+
+/// Lease status matching a tenant status
+pub fn lease_status_for(tenant_status: &str) -> &'static str {
+    match tenant_status {
+        "active" => "active",
+        "pending" => "pending",
+        _ => "ended",
+    }
+}
+
+pub async fn fetch_lease(pool: &SqlitePool, id: i64) -> Result<Lease> {
+    sqlx::query_as::<_, Lease>("SELECT * FROM leases WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Lease with id {} not found", id)))
+}
+
+pub async fn load_detail(pool: &SqlitePool, lease: Lease) -> Result<LeaseDetail> {
+    let occupants = sqlx::query_as::<_, LeaseOccupant>(
+        r#"
+        SELECT o.tenant_id, o.role, t.first_name, t.last_name, t.email
+        FROM lease_occupants o
+        JOIN tenants t ON t.id = o.tenant_id
+        WHERE o.lease_id = ?
+        ORDER BY CASE o.role WHEN 'primary' THEN 0 WHEN 'co_tenant' THEN 1 ELSE 2 END, t.last_name
+        "#,
+    )
+    .bind(lease.id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(LeaseDetail { lease, occupants })
+}
+
+async fn load_details(pool: &SqlitePool, leases: Vec<Lease>) -> Result<Vec<LeaseDetail>> {
+    let mut details = Vec::with_capacity(leases.len());
+    for lease in leases {
+        details.push(load_detail(pool, lease).await?);
+    }
+    Ok(details)
+}
+
+/// Put a tenant on a lease; they must rent at the lease's property
+pub async fn add_occupant(
+    tx: &mut Transaction<'_, Sqlite>,
+    lease_id: i64,
+    property_id: i64,
+    tenant_id: i64,
+    role: &str,
+) -> Result<()> {
+    validate_role(role)?;
+
+    let tenant_property: i64 = sqlx::query_scalar("SELECT property_id FROM tenants WHERE id = ?")
+        .bind(tenant_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Tenant {} does not exist", tenant_id)))?;
+    if tenant_property != property_id {
+        return Err(AppError::BadRequest(format!(
+            "Tenant {} is not a tenant of property {}",
+            tenant_id, property_id
+        )));
+    }
+
+    sqlx::query("INSERT INTO lease_occupants (lease_id, tenant_id, role) VALUES (?, ?, ?)")
+        .bind(lease_id)
+        .bind(tenant_id)
+        .bind(role)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Conflict(format!(
+                "Tenant {} is already on lease {}",
+                tenant_id, lease_id
+            )),
+            _ => e.into(),
+        })?;
+
+    Ok(())
+}
+
+/// Insert a lease row; occupants are added separately
+#[allow(clippy::too_many_arguments)]
+pub async fn insert_lease(
+    tx: &mut Transaction<'_, Sqlite>,
+    property_id: i64,
+    unit_id: Option<i64>,
+    lease_type: &str,
+    start_date: DateTime<Utc>,
+    end_date: Option<DateTime<Utc>>,
+    monthly_rent: f64,
+    deposit_amount: Option<f64>,
+    rent_due_day: i32,
+    status: &str,
+    previous_lease_id: Option<i64>,
+    notes: Option<&str>,
+) -> Result<i64> {
+    validate_lease_type(lease_type)?;
+    validate_status(status)?;
+    validate_rent_due_day(rent_due_day)?;
+    if lease_type == "fixed" && end_date.is_none() {
+        return Err(AppError::BadRequest(
+            "A fixed-term lease needs an end_date".to_string(),
+        ));
+    }
+    check_lease_dates(start_date, end_date)?;
+    if status != "ended" {
+        check_overlap(tx, property_id, unit_id, start_date, end_date, None).await?;
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO leases (
+            property_id, unit_id, lease_type, start_date, end_date,
+            monthly_rent, deposit_amount, rent_due_day, status, previous_lease_id, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(property_id)
+    .bind(unit_id)
+    .bind(lease_type)
+    .bind(start_date)
+    .bind(end_date)
+    .bind(monthly_rent)
+    .bind(deposit_amount)
+    .bind(rent_due_day)
+    .bind(status)
+    .bind(previous_lease_id)
+    .bind(notes)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn list_leases(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<LeaseQuery>,
+) -> Result<Json<Vec<LeaseDetail>>> {
+    let leases = sqlx::query_as::<_, Lease>(
+        r#"
+        SELECT * FROM leases
+        WHERE (?1 IS NULL OR status = ?1)
+          AND (?2 IS NULL OR property_id = ?2)
+          AND (?3 IS NULL OR unit_id = ?3)
+        ORDER BY start_date DESC
+        "#,
+    )
+    .bind(&params.status)
+    .bind(params.property_id)
+    .bind(params.unit_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(load_details(&pool, leases).await?))
+}
+
+/// Every lease a unit has had, newest first
+pub async fn list_unit_leases(
+    State(pool): State<SqlitePool>,
+    Path(unit_id): Path<i64>,
+) -> Result<Json<Vec<LeaseDetail>>> {
+    sqlx::query("SELECT id FROM units WHERE id = ?")
+        .bind(unit_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Unit with id {} not found", unit_id)))?;
+
+    let leases = sqlx::query_as::<_, Lease>(
+        "SELECT * FROM leases WHERE unit_id = ? ORDER BY start_date DESC",
+    )
+    .bind(unit_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(load_details(&pool, leases).await?))
+}
+
+pub async fn list_tenant_leases(
+    State(pool): State<SqlitePool>,
+    Path(tenant_id): Path<i64>,
+) -> Result<Json<Vec<LeaseDetail>>> {
+    sqlx::query("SELECT id FROM tenants WHERE id = ?")
+        .bind(tenant_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Tenant with id {} not found", tenant_id)))?;
+
+    let leases = sqlx::query_as::<_, Lease>(
+        r#"
+        SELECT l.* FROM leases l
+        JOIN lease_occupants o ON o.lease_id = l.id
+        WHERE o.tenant_id = ?
+        ORDER BY l.start_date DESC
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(load_details(&pool, leases).await?))
+}
+
+pub async fn get_lease(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<LeaseDetail>> {
+    let lease = fetch_lease(&pool, id).await?;
+
+    Ok(Json(load_detail(&pool, lease).await?))
+}
+
+pub async fn create_lease(
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateLease>,
+) -> Result<(StatusCode, Json<LeaseDetail>)> {
+    check_unit(&pool, payload.property_id, payload.unit_id).await?;
+    if payload.occupants.is_empty() {
+        return Err(AppError::BadRequest(
+            "A lease needs at least one occupant".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let id = insert_lease(
+        &mut tx,
+        payload.property_id,
+        payload.unit_id,
+        payload.lease_type.as_deref().unwrap_or("fixed"),
+        payload.start_date,
+        payload.end_date,
+        payload.monthly_rent,
+        payload.deposit_amount,
+        payload.rent_due_day.unwrap_or(1),
+        payload.status.as_deref().unwrap_or("active"),
+        None,
+        payload.notes.as_deref(),
+    )
+    .await?;
+
+    for (i, occupant) in payload.occupants.iter().enumerate() {
+        // The first occupant is the primary leaseholder unless told otherwise
+        let default_role = if i == 0 { "primary" } else { "co_tenant" };
+        let role = occupant.role.as_deref().unwrap_or(default_role);
+        add_occupant(&mut tx, id, payload.property_id, occupant.tenant_id, role).await?;
+    }
+
+    sync_lease_tenants(&mut *tx, id).await?;
+    refresh_property(&mut tx, payload.property_id, Utc::now()).await?;
+    tx.commit().await?;
+
+    let lease = fetch_lease(&pool, id).await?;
+
+    Ok((StatusCode::CREATED, Json(load_detail(&pool, lease).await?)))
+}
+
+pub async fn update_lease(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateLease>,
+) -> Result<Json<LeaseDetail>> {
+    let current = fetch_lease(&pool, id).await?;
+
+    check_unit(&pool, current.property_id, payload.unit_id).await?;
+    if let Some(lease_type) = &payload.lease_type {
+        validate_lease_type(lease_type)?;
+    }
+    if let Some(status) = &payload.status {
+        validate_status(status)?;
+    }
+    if let Some(day) = payload.rent_due_day {
+        validate_rent_due_day(day)?;
+    }
+    let lease_type = payload.lease_type.as_deref().unwrap_or(&current.lease_type);
+    let start_date = payload.start_date.unwrap_or(current.start_date);
+    // Going month-to-month drops the old fixed end unless a new end (notice
+    // to vacate) is given with it
+    let clear_end = payload.lease_type.as_deref() == Some("month_to_month")
+        && current.lease_type != "month_to_month"
+        && payload.end_date.is_none();
+    let end_date = if clear_end {
+        None
+    } else {
+        payload.end_date.or(current.end_date)
+    };
+    if lease_type == "fixed" && end_date.is_none() {
+        return Err(AppError::BadRequest(
+            "A fixed-term lease needs an end_date".to_string(),
+        ));
+    }
+    check_lease_dates(start_date, end_date)?;
+
+    let mut query = String::from("UPDATE leases SET ");
+    let mut updates = Vec::new();
+
+    if payload.unit_id.is_some() {
+        updates.push("unit_id = ?");
+    }
+    if payload.lease_type.is_some() {
+        updates.push("lease_type = ?");
+    }
+    if payload.start_date.is_some() {
+        updates.push("start_date = ?");
+    }
+    if payload.end_date.is_some() || clear_end {
+        updates.push("end_date = ?");
+    }
+    if payload.monthly_rent.is_some() {
+        updates.push("monthly_rent = ?");
+    }
+    if payload.deposit_amount.is_some() {
+        updates.push("deposit_amount = ?");
+    }
+    if payload.rent_due_day.is_some() {
+        updates.push("rent_due_day = ?");
+    }
+    if payload.status.is_some() {
+        updates.push("status = ?");
+    }
+    if payload.notes.is_some() {
+        updates.push("notes = ?");
+    }
+
+    updates.push("updated_at = CURRENT_TIMESTAMP");
+
+    query.push_str(&updates.join(", "));
+    query.push_str(" WHERE id = ?");
+
+    let mut q = sqlx::query(&query);
+
+    if let Some(v) = payload.unit_id {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.lease_type {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.start_date {
+        q = q.bind(v);
+    }
+    if payload.end_date.is_some() || clear_end {
+        q = q.bind(end_date);
+    }
+    if let Some(v) = payload.monthly_rent {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.deposit_amount {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.rent_due_day {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.status {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.notes {
+        q = q.bind(v);
+    }
+
+    q = q.bind(id);
+
+    let mut tx = pool.begin().await?;
+    if payload.status.as_deref().unwrap_or(&current.status) != "ended" {
+        check_overlap(
+            &mut tx,
+            current.property_id,
+            payload.unit_id.or(current.unit_id),
+            start_date,
+            end_date,
+            Some(id),
+        )
+        .await?;
+    }
+    q.execute(&mut *tx).await?;
+    if payload.status.as_deref() == Some("ended") {
+        expire_renewal(&mut tx, id).await?;
+    }
+    sync_lease_tenants(&mut *tx, id).await?;
+    refresh_property(&mut tx, current.property_id, Utc::now()).await?;
+    tx.commit().await?;
+
+    let lease = fetch_lease(&pool, id).await?;
+
+    Ok(Json(load_detail(&pool, lease).await?))
+}
+
+/// End a pending or active lease nobody is left on, so it stops holding the
+/// unit. An active lease ends now if it would have run longer. Returns whether
+/// the lease was ended.
+pub async fn end_if_unoccupied(
+    tx: &mut Transaction<'_, Sqlite>,
+    lease_id: i64,
+    now: DateTime<Utc>,
+) -> Result<bool> {
+    let ended = sqlx::query(
+        r#"
+        UPDATE leases SET
+            status = 'ended',
+            end_date = CASE
+                WHEN status = 'active' AND (end_date IS NULL OR end_date > ?2) THEN ?2
+                ELSE end_date
+            END,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = ?1
+          AND status IN ('pending', 'active')
+          AND NOT EXISTS (SELECT 1 FROM lease_occupants WHERE lease_id = ?1)
+        "#,
+    )
+    .bind(lease_id)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if ended {
+        expire_renewal(tx, lease_id).await?;
+    }
+
+    Ok(ended)
+}
+
+pub async fn delete_lease(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let mut tx = pool.begin().await?;
+
+    let tenant_ids: Vec<i64> =
+        sqlx::query_scalar("SELECT tenant_id FROM lease_occupants WHERE lease_id = ?")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+    let property_id: i64 =
+        sqlx::query_scalar("DELETE FROM leases WHERE id = ? RETURNING property_id")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Lease with id {} not found", id)))?;
+
+    // Former occupants fall back to their next most relevant lease
+    for tenant_id in tenant_ids {
+        sync_tenant(&mut *tx, tenant_id).await?;
+    }
+    refresh_property(&mut tx, property_id, Utc::now()).await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn add_lease_occupant(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<AddLeaseOccupant>,
+) -> Result<(StatusCode, Json<LeaseDetail>)> {
+    let lease = fetch_lease(&pool, id).await?;
+
+    let mut tx = pool.begin().await?;
+    add_occupant(
+        &mut tx,
+        id,
+        lease.property_id,
+        payload.tenant_id,
+        payload.role.as_deref().unwrap_or("co_tenant"),
+    )
+    .await?;
+    sync_tenant(&mut *tx, payload.tenant_id).await?;
+    tx.commit().await?;
+
+    let lease = fetch_lease(&pool, id).await?;
+
+    Ok((StatusCode::CREATED, Json(load_detail(&pool, lease).await?)))
+}
+
+pub async fn remove_lease_occupant(
+    State(pool): State<SqlitePool>,
+    Path((id, tenant_id)): Path<(i64, i64)>,
+) -> Result<StatusCode> {
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query("DELETE FROM lease_occupants WHERE lease_id = ? AND tenant_id = ?")
+        .bind(id)
+        .bind(tenant_id)
+        .execute(&mut *tx)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Tenant {} is not on lease {}",
+            tenant_id, id
+        )));
+    }
+
+    sync_tenant(&mut *tx, tenant_id).await?;
+    if end_if_unoccupied(&mut tx, id, Utc::now()).await? {
+        let property_id: i64 = sqlx::query_scalar("SELECT property_id FROM leases WHERE id = ?")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        refresh_property(&mut tx, property_id, Utc::now()).await?;
+    }
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
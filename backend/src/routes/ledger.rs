@@ -0,0 +1,295 @@
+use crate::error::{AppError, Result};
+use crate::jobs::rent_charges;
+use crate::leasing::escalation::{self, parse_month_key};
+use crate::leasing::proration::ProrationMethod;
+use crate::leasing::rent_charges::{post_rent_charge, rent_for_month};
+use crate::ledger::statement::{render_statement, Letterhead, StatementParty};
+use crate::ledger::{self, CHARGE_TYPES};
+use crate::models::{
+    CreateTenantCharge, GenerateRentCharge, RentChargeRunSummary, TenantCharge, TenantLedger,
+};
+use crate::routes::leases::fetch_lease;
+use axum::{
+    extract::{Path, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Deserialize)]
+pub struct LedgerQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl LedgerQuery {
+    /// The period as instants: from the start of `from` through the end of `to`
+    fn range(&self) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        let start = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc();
+        (
+            self.from.map(start),
+            self.to
+                .map(|to| start(to + Duration::days(1)) - Duration::nanoseconds(1)),
+        )
+    }
+}
+
+/// Property the tenant rents, or NotFound when the tenant doesn't exist
+pub async fn tenant_property_id(pool: &SqlitePool, tenant_id: i64) -> Result<i64> {
+    sqlx::query_scalar("SELECT property_id FROM tenants WHERE id = ?")
+        .bind(tenant_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Tenant with id {} not found", tenant_id)))
+}
+
+pub async fn get_tenant_ledger(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Query(params): Query<LedgerQuery>,
+) -> Result<Json<TenantLedger>> {
+    tenant_property_id(&pool, id).await?;
+
+    let (from, to) = params.range();
+    let ledger = ledger::tenant_ledger(&pool, id, from, to).await?;
+
+    Ok(Json(ledger))
+}
+
+/// The tenant's ledger for the period as a PDF statement on the letterhead
+pub async fn get_tenant_statement(
+    State(pool): State<SqlitePool>,
+    Extension(letterhead): Extension<Letterhead>,
+    Path(id): Path<i64>,
+    Query(params): Query<LedgerQuery>,
+) -> Result<Response> {
+    let (from, to) = params.range();
+    if let (Some(from), Some(to)) = (from, to) {
+        if to < from {
+            return Err(AppError::BadRequest("to is before from".to_string()));
+        }
+    }
+
+    let tenant = sqlx::query_as::<_, (String, String, Option<String>, i64, Option<i64>)>(
+        "SELECT first_name, last_name, email, property_id, unit_id FROM tenants WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Tenant with id {} not found", id)))?;
+    let (first_name, last_name, email, property_id, unit_id) = tenant;
+
+    let (title, address, city, state, zip_code) =
+        sqlx::query_as::<_, (String, String, String, String, String)>(
+            "SELECT title, address, city, state, zip_code FROM properties WHERE id = ?",
+        )
+        .bind(property_id)
+        .fetch_one(&pool)
+        .await?;
+
+    let unit_number: Option<String> =
+        sqlx::query_scalar("SELECT unit_number FROM units WHERE id = ?")
+            .bind(unit_id)
+            .fetch_optional(&pool)
+            .await?;
+
+    let party = StatementParty {
+        tenant_name: format!("{} {}", first_name, last_name),
+        tenant_email: email,
+        property_title: title,
+        property_address: vec![address, format!("{}, {} {}", city, state, zip_code)],
+        unit_number,
+    };
+
+    let ledger = ledger::tenant_ledger(&pool, id, from, to).await?;
+    let now = Utc::now();
+    let pdf = render_statement(&letterhead, &party, &ledger, from, to, now);
+
+    Ok((
+        [
+            (CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!(
+                    "inline; filename=\"statement-{}-{}.pdf\"",
+                    id,
+                    to.unwrap_or(now).format("%Y-%m-%d")
+                ),
+            ),
+        ],
+        pdf,
+    )
+        .into_response())
+}
+
+pub async fn list_tenant_charges(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<TenantCharge>>> {
+    tenant_property_id(&pool, id).await?;
+
+    let charges = sqlx::query_as::<_, TenantCharge>(
+        "SELECT * FROM tenant_charges WHERE tenant_id = ? ORDER BY charge_date DESC",
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(charges))
+}
+
+pub async fn create_tenant_charge(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<CreateTenantCharge>,
+) -> Result<(StatusCode, Json<TenantCharge>)> {
+    let property_id = tenant_property_id(&pool, id).await?;
+
+    if !CHARGE_TYPES.contains(&payload.charge_type.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unknown charge type '{}'",
+            payload.charge_type
+        )));
+    }
+
+    let amount = match payload.amount {
+        Some(amount) => amount,
+        None if payload.charge_type == "rent" => {
+            let lease = escalation::tenant_lease_on(&pool, id, payload.charge_date)
+                .await?
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!(
+                        "Tenant {} has no lease in force on {}; give the rent amount",
+                        id,
+                        payload.charge_date.format("%Y-%m-%d")
+                    ))
+                })?;
+            escalation::rent_on(&pool, &lease, payload.charge_date).await?
+        }
+        None => {
+            return Err(AppError::BadRequest(
+                "amount is required for this charge type".to_string(),
+            ))
+        }
+    };
+    if amount <= 0.0 {
+        return Err(AppError::BadRequest(
+            "Charge amount must be positive".to_string(),
+        ));
+    }
+
+    // Credits reduce what the tenant owes
+    let amount = if payload.charge_type == "credit" {
+        -amount
+    } else {
+        amount
+    };
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO tenant_charges (
+            tenant_id, property_id, charge_type, amount, charge_date, description
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(id)
+    .bind(property_id)
+    .bind(&payload.charge_type)
+    .bind(amount)
+    .bind(payload.charge_date)
+    .bind(&payload.description)
+    .execute(&pool)
+    .await?;
+
+    let charge = sqlx::query_as::<_, TenantCharge>("SELECT * FROM tenant_charges WHERE id = ?")
+        .bind(result.last_insert_rowid())
+        .fetch_one(&pool)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(charge)))
+}
+
+pub async fn list_lease_rent_charges(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+) -> Result<Json<Vec<TenantCharge>>> {
+    fetch_lease(&pool, lease_id).await?;
+
+    let charges = sqlx::query_as::<_, TenantCharge>(
+        r#"
+        SELECT * FROM tenant_charges
+        WHERE lease_id = ? AND charge_type = 'rent'
+        ORDER BY billing_period DESC
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(charges))
+}
+
+/// Bill one month's rent on a lease, prorating a partial first or last
+/// month with the configured method unless the request names another
+pub async fn generate_rent_charge(
+    State(pool): State<SqlitePool>,
+    Extension(default_method): Extension<ProrationMethod>,
+    Path(lease_id): Path<i64>,
+    Json(payload): Json<GenerateRentCharge>,
+) -> Result<(StatusCode, Json<TenantCharge>)> {
+    let lease = fetch_lease(&pool, lease_id).await?;
+    let month_start = parse_month_key(&payload.period).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Period '{}' is not a month in YYYY-MM form",
+            payload.period
+        ))
+    })?;
+    let method = match payload.method.as_deref() {
+        None => default_method,
+        Some(method) => ProrationMethod::parse(method).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Unknown proration method '{}' (actual_days, thirty_day or 365_day)",
+                method
+            ))
+        })?,
+    };
+
+    let calculation = rent_for_month(&pool, &lease, month_start, method)
+        .await?
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Lease {} does not cover {}",
+                lease_id, payload.period
+            ))
+        })?;
+
+    let mut tx = pool.begin().await?;
+    let charge = post_rent_charge(&mut tx, &lease, month_start, &calculation)
+        .await?
+        .ok_or_else(|| {
+            AppError::Conflict(format!(
+                "Rent for {} has already been charged on lease {}",
+                payload.period, lease_id
+            ))
+        })?;
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(charge)))
+}
+
+/// Post any rent charges that are due and report how many were created
+pub async fn run_rent_charges(
+    State(pool): State<SqlitePool>,
+    Extension(method): Extension<ProrationMethod>,
+) -> Result<Json<RentChargeRunSummary>> {
+    let summary = rent_charges::run(&pool, Utc::now(), method).await?;
+
+    Ok(Json(summary))
+}
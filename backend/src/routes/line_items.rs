@@ -0,0 +1,346 @@
+use crate::error::{AppError, Result};
+use crate::models::{
+    ChargeBackRequest, CreateLineItem, MaintenanceCostSummary, MaintenanceLineItem,
+    MaintenanceRecord, TenantCharge,
+};
+use crate::tax::round_cents;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+async fn fetch_record(pool: &SqlitePool, id: i64) -> Result<MaintenanceRecord> {
+    sqlx::query_as::<_, MaintenanceRecord>("SELECT * FROM maintenance_records WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Maintenance record with id {} not found", id)))
+}
+
+/// Keep the record's estimated and actual totals in step with its line items.
+/// A manually entered total stands until the first line item of its kind;
+/// after that the total is the sum of the items, or empty once none are left.
+async fn refresh_totals(tx: &mut Transaction<'_, Sqlite>, maintenance_id: i64) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE maintenance_records SET
+            cost = CASE
+                WHEN EXISTS (
+                    SELECT 1 FROM maintenance_line_items
+                    WHERE maintenance_id = ?1 AND kind = 'actual'
+                ) THEN (
+                    SELECT SUM(amount) FROM maintenance_line_items
+                    WHERE maintenance_id = ?1 AND kind = 'actual'
+                )
+                WHEN cost_itemized THEN NULL
+                ELSE cost
+            END,
+            cost_itemized = cost_itemized OR EXISTS (
+                SELECT 1 FROM maintenance_line_items
+                WHERE maintenance_id = ?1 AND kind = 'actual'
+            ),
+            estimated_cost = CASE
+                WHEN EXISTS (
+                    SELECT 1 FROM maintenance_line_items
+                    WHERE maintenance_id = ?1 AND kind = 'estimate'
+                ) THEN (
+                    SELECT SUM(amount) FROM maintenance_line_items
+                    WHERE maintenance_id = ?1 AND kind = 'estimate'
+                )
+                WHEN estimate_itemized THEN NULL
+                ELSE estimated_cost
+            END,
+            estimate_itemized = estimate_itemized OR EXISTS (
+                SELECT 1 FROM maintenance_line_items
+                WHERE maintenance_id = ?1 AND kind = 'estimate'
+            ),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = ?1
+        "#,
+    )
+    .bind(maintenance_id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn list_line_items(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<MaintenanceLineItem>>> {
+    fetch_record(&pool, id).await?;
+
+    let items = sqlx::query_as::<_, MaintenanceLineItem>(
+        "SELECT * FROM maintenance_line_items WHERE maintenance_id = ? ORDER BY kind, id",
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(items))
+}
+
+pub async fn create_line_item(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<CreateLineItem>,
+) -> Result<(StatusCode, Json<MaintenanceLineItem>)> {
+    fetch_record(&pool, id).await?;
+
+    let kind = payload.kind.clone().unwrap_or_else(|| "actual".to_string());
+    if !matches!(kind.as_str(), "estimate" | "actual") {
+        return Err(AppError::BadRequest(format!(
+            "Unknown line item kind '{}'",
+            kind
+        )));
+    }
+    if !matches!(payload.item_type.as_str(), "labor" | "materials" | "fee") {
+        return Err(AppError::BadRequest(format!(
+            "Unknown line item type '{}'",
+            payload.item_type
+        )));
+    }
+    let quantity = payload.quantity.unwrap_or(1.0);
+    if quantity <= 0.0 || payload.unit_cost < 0.0 {
+        return Err(AppError::BadRequest(
+            "Quantity must be positive and unit cost cannot be negative".to_string(),
+        ));
+    }
+    let tenant_chargeable = payload.tenant_chargeable.unwrap_or(false);
+    if tenant_chargeable && kind == "estimate" {
+        return Err(AppError::BadRequest(
+            "Only actual costs can be charged to the tenant".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO maintenance_line_items (
+            maintenance_id, kind, item_type, description, quantity,
+            unit_cost, amount, invoice_number, tenant_chargeable
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(id)
+    .bind(&kind)
+    .bind(&payload.item_type)
+    .bind(&payload.description)
+    .bind(quantity)
+    .bind(payload.unit_cost)
+    .bind(round_cents(quantity * payload.unit_cost))
+    .bind(&payload.invoice_number)
+    .bind(tenant_chargeable)
+    .execute(&mut *tx)
+    .await?;
+
+    refresh_totals(&mut tx, id).await?;
+    tx.commit().await?;
+
+    let item = sqlx::query_as::<_, MaintenanceLineItem>(
+        "SELECT * FROM maintenance_line_items WHERE id = ?",
+    )
+    .bind(result.last_insert_rowid())
+    .fetch_one(&pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(item)))
+}
+
+pub async fn delete_line_item(
+    State(pool): State<SqlitePool>,
+    Path((id, item_id)): Path<(i64, i64)>,
+) -> Result<StatusCode> {
+    let charge_id: Option<Option<i64>> = sqlx::query_scalar(
+        "SELECT tenant_charge_id FROM maintenance_line_items WHERE id = ? AND maintenance_id = ?",
+    )
+    .bind(item_id)
+    .bind(id)
+    .fetch_optional(&pool)
+    .await?;
+
+    match charge_id {
+        None => {
+            return Err(AppError::NotFound(format!(
+                "Line item with id {} not found",
+                item_id
+            )))
+        }
+        Some(Some(_)) => {
+            return Err(AppError::Conflict(format!(
+                "Line item {} has already been charged to the tenant",
+                item_id
+            )))
+        }
+        Some(None) => {}
+    }
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM maintenance_line_items WHERE id = ?")
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await?;
+
+    refresh_totals(&mut tx, id).await?;
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_cost_summary(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<MaintenanceCostSummary>> {
+    let record = fetch_record(&pool, id).await?;
+
+    let line_items = sqlx::query_as::<_, MaintenanceLineItem>(
+        "SELECT * FROM maintenance_line_items WHERE maintenance_id = ? ORDER BY kind, id",
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await?;
+
+    let actual: Vec<&MaintenanceLineItem> =
+        line_items.iter().filter(|i| i.kind == "actual").collect();
+    let sum = |pred: &dyn Fn(&MaintenanceLineItem) -> bool| {
+        round_cents(actual.iter().filter(|i| pred(i)).map(|i| i.amount).sum())
+    };
+
+    let estimate_total = record.estimated_cost.unwrap_or(0.0);
+    let actual_total = record.cost.unwrap_or(0.0);
+    let tenant_chargeable = sum(&|i| i.tenant_chargeable);
+
+    let mut invoice_numbers: Vec<String> = line_items
+        .iter()
+        .filter_map(|i| i.invoice_number.clone())
+        .collect();
+    invoice_numbers.sort();
+    invoice_numbers.dedup();
+
+    Ok(Json(MaintenanceCostSummary {
+        maintenance_id: id,
+        estimate_total: round_cents(estimate_total),
+        actual_total: round_cents(actual_total),
+        variance: round_cents(actual_total - estimate_total),
+        labor: sum(&|i| i.item_type == "labor"),
+        materials: sum(&|i| i.item_type == "materials"),
+        fees: sum(&|i| i.item_type == "fee"),
+        tenant_chargeable,
+        owner_paid: round_cents(actual_total - tenant_chargeable),
+        charged_back: sum(&|i| i.tenant_charge_id.is_some()),
+        invoice_numbers,
+        line_items,
+    }))
+}
+
+/// Post the tenant-chargeable actual costs that haven't been billed yet to the
+/// tenant's ledger as a single maintenance charge.
+pub async fn charge_back(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<ChargeBackRequest>,
+) -> Result<(StatusCode, Json<TenantCharge>)> {
+    let record = fetch_record(&pool, id).await?;
+
+    // Occupants of the active lease on the work order's unit, primary first
+    let occupants = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT o.tenant_id
+        FROM leases l
+        JOIN lease_occupants o ON o.lease_id = l.id
+        WHERE l.status = 'active' AND l.property_id = ?1
+          AND (?2 IS NULL OR l.unit_id IS NULL OR l.unit_id = ?2)
+        ORDER BY l.start_date DESC,
+                 CASE o.role WHEN 'primary' THEN 0 WHEN 'co_tenant' THEN 1 ELSE 2 END,
+                 o.created_at, o.tenant_id
+        "#,
+    )
+    .bind(record.property_id)
+    .bind(record.unit_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let tenant_id = match payload.tenant_id {
+        Some(tenant_id) if occupants.contains(&tenant_id) => tenant_id,
+        Some(tenant_id) => {
+            return Err(AppError::BadRequest(format!(
+                "Tenant {} is not on the active lease for maintenance record {}",
+                tenant_id, id
+            )))
+        }
+        None => *occupants.first().ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Maintenance record {} has no active lease to charge",
+                id
+            ))
+        })?,
+    };
+
+    let mut tx = pool.begin().await?;
+
+    let amount: Option<f64> = sqlx::query_scalar(
+        r#"
+        SELECT SUM(amount) FROM maintenance_line_items
+        WHERE maintenance_id = ? AND kind = 'actual'
+          AND tenant_chargeable = 1 AND tenant_charge_id IS NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let amount = round_cents(amount.unwrap_or(0.0));
+    if amount <= 0.0 {
+        return Err(AppError::Conflict(format!(
+            "Maintenance record {} has no unbilled tenant-chargeable costs",
+            id
+        )));
+    }
+
+    let charge_id = sqlx::query(
+        r#"
+        INSERT INTO tenant_charges (
+            tenant_id, property_id, charge_type, amount, charge_date,
+            description, maintenance_id
+        )
+        VALUES (?, ?, 'maintenance', ?, ?, ?, ?)
+        "#,
+    )
+    .bind(tenant_id)
+    .bind(record.property_id)
+    .bind(amount)
+    .bind(payload.charge_date.unwrap_or_else(Utc::now))
+    .bind(format!("Charge-back: {}", record.title))
+    .bind(id)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    sqlx::query(
+        r#"
+        UPDATE maintenance_line_items SET tenant_charge_id = ?
+        WHERE maintenance_id = ? AND kind = 'actual'
+          AND tenant_chargeable = 1 AND tenant_charge_id IS NULL
+        "#,
+    )
+    .bind(charge_id)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let charge = sqlx::query_as::<_, TenantCharge>("SELECT * FROM tenant_charges WHERE id = ?")
+        .bind(charge_id)
+        .fetch_one(&pool)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(charge)))
+}
@@ -0,0 +1,84 @@
+use crate::error::{AppError, Result};
+use crate::models::{CreateLoan, Loan};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::SqlitePool;
+
+pub async fn list_loans(State(pool): State<SqlitePool>) -> Result<Json<Vec<Loan>>> {
+    let loans = sqlx::query_as::<_, Loan>("SELECT * FROM loans ORDER BY created_at DESC")
+        .fetch_all(&pool)
+        .await?;
+
+    Ok(Json(loans))
+}
+
+pub async fn get_loan(State(pool): State<SqlitePool>, Path(id): Path<i64>) -> Result<Json<Loan>> {
+    let loan = sqlx::query_as::<_, Loan>("SELECT * FROM loans WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Loan with id {} not found", id)))?;
+
+    Ok(Json(loan))
+}
+
+pub async fn create_loan(
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateLoan>,
+) -> Result<(StatusCode, Json<Loan>)> {
+    if payload.original_principal <= 0.0 || payload.term_months <= 0 {
+        return Err(AppError::BadRequest(
+            "Loan principal and term must be positive".to_string(),
+        ));
+    }
+    if payload.interest_rate < 0.0 {
+        return Err(AppError::BadRequest(
+            "Interest rate cannot be negative".to_string(),
+        ));
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO loans (
+            property_id, lender, original_principal, interest_rate,
+            term_months, first_payment_date, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(payload.property_id)
+    .bind(&payload.lender)
+    .bind(payload.original_principal)
+    .bind(payload.interest_rate)
+    .bind(payload.term_months)
+    .bind(payload.first_payment_date)
+    .bind(&payload.notes)
+    .execute(&pool)
+    .await?;
+
+    let loan = sqlx::query_as::<_, Loan>("SELECT * FROM loans WHERE id = ?")
+        .bind(result.last_insert_rowid())
+        .fetch_one(&pool)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(loan)))
+}
+
+pub async fn delete_loan(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM loans WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Loan with id {} not found", id)));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
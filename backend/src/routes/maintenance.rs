@@ -1,52 +1,246 @@
 use crate::error::{AppError, Result};
-use crate::models::{CreateMaintenance, MaintenanceRecord, UpdateMaintenance};
+use crate::models::{
+    CreateMaintenance, MaintenanceRecord, MaintenanceStatusChange, MaintenanceTransition,
+    UpdateMaintenance,
+};
+use crate::routes::attachments::release_owner_blobs;
+use crate::routes::contractors::contractor_name;
+use crate::routes::units::check_unit;
+use crate::storage::SharedStore;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
-use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{Sqlite, SqlitePool, Transaction};
 
-pub async fn list_maintenance(
-    State(pool): State<SqlitePool>,
-) -> Result<Json<Vec<MaintenanceRecord>>> {
+/// Work-order lifecycle: pending -> scheduled -> in_progress -> completed,
+/// with cancellation allowed from any open state.
+fn allowed_transitions(status: &str) -> &'static [&'static str] {
+    match status {
+        "pending" => &["scheduled", "cancelled"],
+        "scheduled" => &["in_progress", "cancelled"],
+        "in_progress" => &["completed", "cancelled"],
+        _ => &[],
+    }
+}
+
+fn validate_status(status: &str) -> Result<()> {
+    match status {
+        "pending" | "scheduled" | "in_progress" | "completed" | "cancelled" => Ok(()),
+        _ => Err(AppError::BadRequest(format!(
+            "Unknown maintenance status '{}'",
+            status
+        ))),
+    }
+}
+
+fn check_transition(id: i64, from: &str, to: &str) -> Result<()> {
+    validate_status(to)?;
+
+    if from != to && !allowed_transitions(from).contains(&to) {
+        return Err(AppError::Conflict(format!(
+            "Maintenance record {} cannot move from '{}' to '{}'",
+            id, from, to
+        )));
+    }
+
+    Ok(())
+}
+
+/// The record's status changed after it was loaded, so the transition that
+/// was checked no longer applies
+fn status_moved(id: i64) -> AppError {
+    AppError::Conflict(format!(
+        "Maintenance record {} was changed by someone else; reload and try again",
+        id
+    ))
+}
+
+/// Completion date to store once `status` applies: stamped automatically on
+/// entering `completed`, and rejected for any other status.
+fn resolve_completed_date(
+    id: i64,
+    from: Option<&str>,
+    status: &str,
+    requested: Option<DateTime<Utc>>,
+) -> Result<Option<DateTime<Utc>>> {
+    if status != "completed" {
+        if requested.is_some() {
+            return Err(AppError::Conflict(format!(
+                "Maintenance record {} cannot have a completed_date while '{}'",
+                id, status
+            )));
+        }
+        return Ok(None);
+    }
+
+    if from == Some("completed") {
+        return Ok(requested);
+    }
+
+    Ok(Some(requested.unwrap_or_else(Utc::now)))
+}
+
+pub(crate) async fn record_status_change(
+    tx: &mut Transaction<'_, Sqlite>,
+    maintenance_id: i64,
+    from_status: Option<&str>,
+    to_status: &str,
+    changed_by: Option<&str>,
+    reason: Option<&str>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO maintenance_status_history (
+            maintenance_id, from_status, to_status, changed_by, reason
+        )
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(maintenance_id)
+    .bind(from_status)
+    .bind(to_status)
+    .bind(changed_by)
+    .bind(reason)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceQuery {
+    pub status: Option<String>,
+    pub priority: Option<String>,
+}
+
+async fn fetch_maintenance(
+    pool: &SqlitePool,
+    property_id: Option<i64>,
+    params: &MaintenanceQuery,
+) -> Result<Vec<MaintenanceRecord>> {
     let records = sqlx::query_as::<_, MaintenanceRecord>(
-        "SELECT * FROM maintenance_records ORDER BY created_at DESC",
+        r#"
+        SELECT * FROM maintenance_records
+        WHERE (?1 IS NULL OR property_id = ?1)
+          AND (?2 IS NULL OR status = ?2)
+          AND (?3 IS NULL OR priority = ?3)
+        ORDER BY created_at DESC
+        "#,
     )
-    .fetch_all(&pool)
+    .bind(property_id)
+    .bind(&params.status)
+    .bind(&params.priority)
+    .fetch_all(pool)
     .await?;
 
+    Ok(records)
+}
+
+pub async fn list_maintenance(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<MaintenanceQuery>,
+) -> Result<Json<Vec<MaintenanceRecord>>> {
+    let records = fetch_maintenance(&pool, None, &params).await?;
+
     Ok(Json(records))
 }
 
+pub async fn list_property_maintenance(
+    State(pool): State<SqlitePool>,
+    Path(property_id): Path<i64>,
+    Query(params): Query<MaintenanceQuery>,
+) -> Result<Json<Vec<MaintenanceRecord>>> {
+    sqlx::query("SELECT id FROM properties WHERE id = ?")
+        .bind(property_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", property_id)))?;
+
+    let records = fetch_maintenance(&pool, Some(property_id), &params).await?;
+
+    Ok(Json(records))
+}
+
+pub async fn get_maintenance(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<MaintenanceRecord>> {
+    let record =
+        sqlx::query_as::<_, MaintenanceRecord>("SELECT * FROM maintenance_records WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Maintenance record with id {} not found", id))
+            })?;
+
+    Ok(Json(record))
+}
+
 pub async fn create_maintenance(
     State(pool): State<SqlitePool>,
     Json(payload): Json<CreateMaintenance>,
 ) -> Result<(StatusCode, Json<MaintenanceRecord>)> {
+    validate_status(&payload.status)?;
+    check_unit(&pool, payload.property_id, payload.unit_id).await?;
+    // Records entered as already completed are stamped now
+    let completed_date = (payload.status == "completed").then(Utc::now);
+
+    // Default the display name from the linked contractor
+    let contractor = match (payload.contractor_id, &payload.contractor) {
+        (Some(contractor_id), None) => Some(contractor_name(&pool, contractor_id).await?),
+        (_, name) => name.clone(),
+    };
+
+    let mut tx = pool.begin().await?;
+
     let result = sqlx::query(
         r#"
         INSERT INTO maintenance_records (
-            property_id, title, description, priority, status,
-            cost, scheduled_date, contractor, notes
+            property_id, unit_id, title, description, priority, status,
+            cost, estimated_cost, scheduled_date, completed_date,
+            contractor, contractor_id, notes, capital_improvement
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(payload.property_id)
+    .bind(payload.unit_id)
     .bind(&payload.title)
     .bind(&payload.description)
     .bind(&payload.priority)
     .bind(&payload.status)
     .bind(payload.cost)
+    .bind(payload.estimated_cost)
     .bind(payload.scheduled_date)
-    .bind(&payload.contractor)
+    .bind(completed_date)
+    .bind(&contractor)
+    .bind(payload.contractor_id)
     .bind(&payload.notes)
-    .execute(&pool)
+    .bind(payload.capital_improvement.unwrap_or(false))
+    .execute(&mut *tx)
     .await?;
 
+    let id = result.last_insert_rowid();
+    record_status_change(
+        &mut tx,
+        id,
+        None,
+        &payload.status,
+        payload.changed_by.as_deref(),
+        None,
+    )
+    .await?;
+
+    tx.commit().await?;
+
     let record =
         sqlx::query_as::<_, MaintenanceRecord>("SELECT * FROM maintenance_records WHERE id = ?")
-            .bind(result.last_insert_rowid())
+            .bind(id)
             .fetch_one(&pool)
             .await?;
 
@@ -58,17 +252,33 @@ pub async fn update_maintenance(
     Path(id): Path<i64>,
     Json(payload): Json<UpdateMaintenance>,
 ) -> Result<Json<MaintenanceRecord>> {
-    sqlx::query("SELECT id FROM maintenance_records WHERE id = ?")
-        .bind(id)
-        .fetch_optional(&pool)
-        .await?
-        .ok_or_else(|| {
-            AppError::NotFound(format!("Maintenance record with id {} not found", id))
-        })?;
+    let current =
+        sqlx::query_as::<_, MaintenanceRecord>("SELECT * FROM maintenance_records WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Maintenance record with id {} not found", id))
+            })?;
+
+    check_unit(&pool, current.property_id, payload.unit_id).await?;
+    let status = payload.status.as_deref().unwrap_or(&current.status);
+    check_transition(id, &current.status, status)?;
+    let status_changed = status != current.status;
+    let completed_date =
+        resolve_completed_date(id, Some(&current.status), status, payload.completed_date)?;
+
+    let contractor = match (payload.contractor_id, &payload.contractor) {
+        (Some(contractor_id), None) => Some(contractor_name(&pool, contractor_id).await?),
+        (_, name) => name.clone(),
+    };
 
     let mut query = String::from("UPDATE maintenance_records SET ");
     let mut updates = Vec::new();
 
+    if payload.unit_id.is_some() {
+        updates.push("unit_id = ?");
+    }
     if payload.title.is_some() {
         updates.push("title = ?");
     }
@@ -84,26 +294,38 @@ pub async fn update_maintenance(
     if payload.cost.is_some() {
         updates.push("cost = ?");
     }
+    if payload.estimated_cost.is_some() {
+        updates.push("estimated_cost = ?");
+    }
     if payload.scheduled_date.is_some() {
         updates.push("scheduled_date = ?");
     }
-    if payload.completed_date.is_some() {
+    if completed_date.is_some() {
         updates.push("completed_date = ?");
     }
-    if payload.contractor.is_some() {
+    if contractor.is_some() {
         updates.push("contractor = ?");
     }
+    if payload.contractor_id.is_some() {
+        updates.push("contractor_id = ?");
+    }
     if payload.notes.is_some() {
         updates.push("notes = ?");
     }
+    if payload.capital_improvement.is_some() {
+        updates.push("capital_improvement = ?");
+    }
 
     updates.push("updated_at = CURRENT_TIMESTAMP");
 
     query.push_str(&updates.join(", "));
-    query.push_str(" WHERE id = ?");
+    query.push_str(" WHERE id = ? AND status = ?");
 
     let mut q = sqlx::query(&query);
 
+    if let Some(v) = payload.unit_id {
+        q = q.bind(v);
+    }
     if let Some(v) = &payload.title {
         q = q.bind(v);
     }
@@ -119,21 +341,117 @@ pub async fn update_maintenance(
     if let Some(v) = payload.cost {
         q = q.bind(v);
     }
+    if let Some(v) = payload.estimated_cost {
+        q = q.bind(v);
+    }
     if let Some(v) = payload.scheduled_date {
         q = q.bind(v);
     }
-    if let Some(v) = payload.completed_date {
+    if let Some(v) = completed_date {
+        q = q.bind(v);
+    }
+    if let Some(v) = &contractor {
         q = q.bind(v);
     }
-    if let Some(v) = &payload.contractor {
+    if let Some(v) = payload.contractor_id {
         q = q.bind(v);
     }
     if let Some(v) = &payload.notes {
         q = q.bind(v);
     }
+    if let Some(v) = payload.capital_improvement {
+        q = q.bind(v);
+    }
+
+    q = q.bind(id).bind(&current.status);
+
+    let mut tx = pool.begin().await?;
+    if q.execute(&mut *tx).await?.rows_affected() == 0 {
+        return Err(status_moved(id));
+    }
+
+    if status_changed {
+        record_status_change(
+            &mut tx,
+            id,
+            Some(&current.status),
+            status,
+            payload.changed_by.as_deref(),
+            payload.reason.as_deref(),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let record =
+        sqlx::query_as::<_, MaintenanceRecord>("SELECT * FROM maintenance_records WHERE id = ?")
+            .bind(id)
+            .fetch_one(&pool)
+            .await?;
+
+    Ok(Json(record))
+}
+
+pub async fn transition_maintenance(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<MaintenanceTransition>,
+) -> Result<Json<MaintenanceRecord>> {
+    let current =
+        sqlx::query_as::<_, MaintenanceRecord>("SELECT * FROM maintenance_records WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Maintenance record with id {} not found", id))
+            })?;
+
+    if payload.status == current.status {
+        return Err(AppError::Conflict(format!(
+            "Maintenance record {} is already '{}'",
+            id, current.status
+        )));
+    }
+    check_transition(id, &current.status, &payload.status)?;
+    let completed_date = resolve_completed_date(
+        id,
+        Some(&current.status),
+        &payload.status,
+        payload.completed_date,
+    )?;
+
+    let mut tx = pool.begin().await?;
 
-    q = q.bind(id);
-    q.execute(&pool).await?;
+    let result = sqlx::query(
+        r#"
+        UPDATE maintenance_records
+        SET status = ?, completed_date = COALESCE(?, completed_date),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = ? AND status = ?
+        "#,
+    )
+    .bind(&payload.status)
+    .bind(completed_date)
+    .bind(id)
+    .bind(&current.status)
+    .execute(&mut *tx)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(status_moved(id));
+    }
+
+    record_status_change(
+        &mut tx,
+        id,
+        Some(&current.status),
+        &payload.status,
+        payload.changed_by.as_deref(),
+        payload.reason.as_deref(),
+    )
+    .await?;
+
+    tx.commit().await?;
 
     let record =
         sqlx::query_as::<_, MaintenanceRecord>("SELECT * FROM maintenance_records WHERE id = ?")
@@ -143,3 +461,47 @@ pub async fn update_maintenance(
 
     Ok(Json(record))
 }
+
+pub async fn get_maintenance_history(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<MaintenanceStatusChange>>> {
+    sqlx::query("SELECT id FROM maintenance_records WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Maintenance record with id {} not found", id))
+        })?;
+
+    let history = sqlx::query_as::<_, MaintenanceStatusChange>(
+        "SELECT * FROM maintenance_status_history WHERE maintenance_id = ? ORDER BY changed_at ASC, id ASC",
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(history))
+}
+
+pub async fn delete_maintenance(
+    State(pool): State<SqlitePool>,
+    Extension(store): Extension<SharedStore>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM maintenance_records WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Maintenance record with id {} not found",
+            id
+        )));
+    }
+
+    release_owner_blobs(&pool, &store).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -65,14 +65,25 @@ pub async fn get_analytics(State(pool): State<SqlitePool>) -> Result<Json<Market
     .fetch_one(&pool)
     .await?;
 
-    // Get occupancy rate
-    let occupied: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM properties WHERE status = 'occupied'")
-            .fetch_one(&pool)
-            .await?;
-
-    let occupancy_rate = if total_properties > 0 {
-        (occupied as f32 / total_properties as f32) * 100.0
+    // Get occupancy rate: units covered by an active lease (a lease without
+    // a unit covers the whole property)
+    let (total_units, occupied): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*),
+               COALESCE(SUM(EXISTS (
+                   SELECT 1 FROM leases l
+                   WHERE l.property_id = u.property_id
+                     AND l.status = 'active'
+                     AND (l.unit_id = u.id OR l.unit_id IS NULL)
+               )), 0)
+        FROM units u
+        "#,
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    let occupancy_rate = if total_units > 0 {
+        (occupied as f32 / total_units as f32) * 100.0
     } else {
         0.0
     };
@@ -1,5 +1,24 @@
+pub mod applications;
+pub mod attachments;
+pub mod contractors;
+pub mod deposits;
+pub mod escalations;
 pub mod events;
+pub mod expenses;
+pub mod leases;
+pub mod ledger;
+pub mod line_items;
+pub mod loans;
 pub mod maintenance;
 pub mod market;
+pub mod occupancy;
+pub mod payments;
+pub mod plans;
 pub mod properties;
+pub mod renewals;
+pub mod reports;
+pub mod requests;
+pub mod sla;
+pub mod tax;
 pub mod tenants;
+pub mod units;
@@ -0,0 +1,125 @@
+use crate::error::{AppError, Result};
+use crate::jobs::occupancy;
+use crate::models::{
+    OccupancyReport, OccupancyRunSummary, OccupancySpan, VacancyPeriod, VacancyReport,
+};
+use crate::occupancy::{occupancy_report, vacancy_periods, vacancy_report};
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Deserialize)]
+pub struct OccupancyQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub property_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VacancyQuery {
+    pub property_id: Option<i64>,
+    pub unit_id: Option<i64>,
+    pub current: Option<bool>,
+}
+
+/// Reporting period, the last 12 months by default
+fn period(
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let to = to.unwrap_or_else(Utc::now);
+    let from = from.unwrap_or(to - Duration::days(365));
+    if from >= to {
+        return Err(AppError::BadRequest("from must be before to".to_string()));
+    }
+    Ok((from, to))
+}
+
+/// Physical and economic occupancy over a period
+pub async fn get_occupancy_report(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<OccupancyQuery>,
+) -> Result<Json<OccupancyReport>> {
+    let (from, to) = period(params.from, params.to)?;
+
+    let report = occupancy_report(&pool, from, to, params.property_id, Utc::now()).await?;
+
+    Ok(Json(report))
+}
+
+pub async fn list_unit_occupancy(
+    State(pool): State<SqlitePool>,
+    Path(unit_id): Path<i64>,
+) -> Result<Json<Vec<OccupancySpan>>> {
+    sqlx::query("SELECT id FROM units WHERE id = ?")
+        .bind(unit_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Unit with id {} not found", unit_id)))?;
+
+    let spans = sqlx::query_as::<_, OccupancySpan>(
+        "SELECT * FROM unit_occupancy_history WHERE unit_id = ? ORDER BY started_at DESC",
+    )
+    .bind(unit_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(spans))
+}
+
+pub async fn list_property_occupancy(
+    State(pool): State<SqlitePool>,
+    Path(property_id): Path<i64>,
+) -> Result<Json<Vec<OccupancySpan>>> {
+    sqlx::query("SELECT id FROM properties WHERE id = ?")
+        .bind(property_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", property_id)))?;
+
+    let spans = sqlx::query_as::<_, OccupancySpan>(
+        "SELECT * FROM unit_occupancy_history WHERE property_id = ? ORDER BY started_at DESC, unit_id",
+    )
+    .bind(property_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(spans))
+}
+
+pub async fn list_vacancies(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<VacancyQuery>,
+) -> Result<Json<Vec<VacancyPeriod>>> {
+    let mut periods =
+        vacancy_periods(&pool, params.property_id, params.unit_id, Utc::now()).await?;
+
+    if params.current.unwrap_or(false) {
+        periods.retain(|p| p.ended_at.is_none());
+    }
+    periods.sort_by_key(|p| std::cmp::Reverse(p.started_at));
+
+    Ok(Json(periods))
+}
+
+pub async fn get_vacancy_report(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<OccupancyQuery>,
+) -> Result<Json<VacancyReport>> {
+    let (from, to) = period(params.from, params.to)?;
+
+    let report = vacancy_report(&pool, from, to, params.property_id, Utc::now()).await?;
+
+    Ok(Json(report))
+}
+
+/// Start and end leases that have reached their dates and refresh unit occupancy
+pub async fn run_occupancy(State(pool): State<SqlitePool>) -> Result<Json<OccupancyRunSummary>> {
+    let summary = occupancy::run(&pool, Utc::now()).await?;
+
+    Ok(Json(summary))
+}
@@ -0,0 +1,86 @@
+use crate::error::{AppError, Result};
+use crate::models::{CreateRentPayment, RentPayment};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::SqlitePool;
+
+pub async fn list_payments(State(pool): State<SqlitePool>) -> Result<Json<Vec<RentPayment>>> {
+    let payments =
+        sqlx::query_as::<_, RentPayment>("SELECT * FROM rent_payments ORDER BY payment_date DESC")
+            .fetch_all(&pool)
+            .await?;
+
+    Ok(Json(payments))
+}
+
+pub async fn get_payment(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<RentPayment>> {
+    let payment = sqlx::query_as::<_, RentPayment>("SELECT * FROM rent_payments WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Payment with id {} not found", id)))?;
+
+    Ok(Json(payment))
+}
+
+pub async fn create_payment(
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateRentPayment>,
+) -> Result<(StatusCode, Json<RentPayment>)> {
+    if payload.amount <= 0.0 {
+        return Err(AppError::BadRequest(
+            "Payment amount must be positive".to_string(),
+        ));
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO rent_payments (
+            tenant_id, property_id, amount, payment_date,
+            payment_method, reference, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(payload.tenant_id)
+    .bind(payload.property_id)
+    .bind(payload.amount)
+    .bind(payload.payment_date)
+    .bind(&payload.payment_method)
+    .bind(&payload.reference)
+    .bind(&payload.notes)
+    .execute(&pool)
+    .await?;
+
+    let payment = sqlx::query_as::<_, RentPayment>("SELECT * FROM rent_payments WHERE id = ?")
+        .bind(result.last_insert_rowid())
+        .fetch_one(&pool)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(payment)))
+}
+
+pub async fn delete_payment(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM rent_payments WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Payment with id {} not found",
+            id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
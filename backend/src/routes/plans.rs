@@ -0,0 +1,255 @@
+use crate::error::{AppError, Result};
+use crate::jobs::maintenance_plans;
+use crate::models::{
+    CreateMaintenancePlan, MaintenancePlan, PlanRunSummary, UpdateMaintenancePlan,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+fn validate_recurrence(value: i32, unit: &str) -> Result<()> {
+    if value < 1 {
+        return Err(AppError::BadRequest(
+            "Recurrence interval must be at least 1".to_string(),
+        ));
+    }
+    if !matches!(unit, "days" | "weeks" | "months" | "years") {
+        return Err(AppError::BadRequest(format!(
+            "Unknown recurrence unit '{}'",
+            unit
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_priority(priority: &str) -> Result<()> {
+    if matches!(priority, "low" | "medium" | "high" | "urgent") {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown priority '{}'",
+            priority
+        )))
+    }
+}
+
+pub async fn list_plans(State(pool): State<SqlitePool>) -> Result<Json<Vec<MaintenancePlan>>> {
+    let plans = sqlx::query_as::<_, MaintenancePlan>(
+        "SELECT * FROM maintenance_plans ORDER BY next_due_date ASC",
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(plans))
+}
+
+pub async fn get_plan(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<MaintenancePlan>> {
+    let plan = sqlx::query_as::<_, MaintenancePlan>("SELECT * FROM maintenance_plans WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Maintenance plan with id {} not found", id)))?;
+
+    Ok(Json(plan))
+}
+
+pub async fn create_plan(
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateMaintenancePlan>,
+) -> Result<(StatusCode, Json<MaintenancePlan>)> {
+    let priority = payload
+        .priority
+        .clone()
+        .unwrap_or_else(|| "low".to_string());
+    validate_priority(&priority)?;
+    validate_recurrence(payload.interval_value, &payload.interval_unit)?;
+    let lead_days = payload.lead_days.unwrap_or(14);
+    if lead_days < 0 {
+        return Err(AppError::BadRequest(
+            "Lead days cannot be negative".to_string(),
+        ));
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO maintenance_plans (
+            property_id, title, description, priority, contractor_id,
+            estimated_cost, interval_value, interval_unit, lead_days,
+            next_due_date, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(payload.property_id)
+    .bind(&payload.title)
+    .bind(&payload.description)
+    .bind(&priority)
+    .bind(payload.contractor_id)
+    .bind(payload.estimated_cost)
+    .bind(payload.interval_value)
+    .bind(&payload.interval_unit)
+    .bind(lead_days)
+    .bind(payload.next_due_date)
+    .bind(&payload.notes)
+    .execute(&pool)
+    .await?;
+
+    let plan = sqlx::query_as::<_, MaintenancePlan>("SELECT * FROM maintenance_plans WHERE id = ?")
+        .bind(result.last_insert_rowid())
+        .fetch_one(&pool)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(plan)))
+}
+
+pub async fn update_plan(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateMaintenancePlan>,
+) -> Result<Json<MaintenancePlan>> {
+    let current =
+        sqlx::query_as::<_, MaintenancePlan>("SELECT * FROM maintenance_plans WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Maintenance plan with id {} not found", id))
+            })?;
+
+    if let Some(priority) = &payload.priority {
+        validate_priority(priority)?;
+    }
+    validate_recurrence(
+        payload.interval_value.unwrap_or(current.interval_value),
+        payload
+            .interval_unit
+            .as_deref()
+            .unwrap_or(&current.interval_unit),
+    )?;
+    if payload.lead_days.is_some_and(|d| d < 0) {
+        return Err(AppError::BadRequest(
+            "Lead days cannot be negative".to_string(),
+        ));
+    }
+
+    let mut query = String::from("UPDATE maintenance_plans SET ");
+    let mut updates = Vec::new();
+
+    if payload.title.is_some() {
+        updates.push("title = ?");
+    }
+    if payload.description.is_some() {
+        updates.push("description = ?");
+    }
+    if payload.priority.is_some() {
+        updates.push("priority = ?");
+    }
+    if payload.contractor_id.is_some() {
+        updates.push("contractor_id = ?");
+    }
+    if payload.estimated_cost.is_some() {
+        updates.push("estimated_cost = ?");
+    }
+    if payload.interval_value.is_some() {
+        updates.push("interval_value = ?");
+    }
+    if payload.interval_unit.is_some() {
+        updates.push("interval_unit = ?");
+    }
+    if payload.lead_days.is_some() {
+        updates.push("lead_days = ?");
+    }
+    if payload.next_due_date.is_some() {
+        updates.push("next_due_date = ?");
+    }
+    if payload.active.is_some() {
+        updates.push("active = ?");
+    }
+    if payload.notes.is_some() {
+        updates.push("notes = ?");
+    }
+
+    updates.push("updated_at = CURRENT_TIMESTAMP");
+
+    query.push_str(&updates.join(", "));
+    query.push_str(" WHERE id = ?");
+
+    let mut q = sqlx::query(&query);
+
+    if let Some(v) = &payload.title {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.description {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.priority {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.contractor_id {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.estimated_cost {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.interval_value {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.interval_unit {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.lead_days {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.next_due_date {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.active {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.notes {
+        q = q.bind(v);
+    }
+
+    q = q.bind(id);
+    q.execute(&pool).await?;
+
+    let plan = sqlx::query_as::<_, MaintenancePlan>("SELECT * FROM maintenance_plans WHERE id = ?")
+        .bind(id)
+        .fetch_one(&pool)
+        .await?;
+
+    Ok(Json(plan))
+}
+
+pub async fn delete_plan(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let result = sqlx::query("DELETE FROM maintenance_plans WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Maintenance plan with id {} not found",
+            id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create the work orders for plans that have come due
+pub async fn run_plans(State(pool): State<SqlitePool>) -> Result<Json<PlanRunSummary>> {
+    let summary = maintenance_plans::generate_due_work_orders(&pool, Utc::now()).await?;
+
+    Ok(Json(summary))
+}
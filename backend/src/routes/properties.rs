@@ -1,19 +1,41 @@
 use crate::error::{AppError, Result};
-use crate::models::{CreateProperty, Property, UpdateProperty};
+use crate::models::{CreateProperty, CreateUnit, Property, SetCoverPhoto, UpdateProperty};
+use crate::occupancy::refresh_property;
+use crate::routes::attachments::release_owner_blobs;
+use crate::routes::units::insert_unit;
+use crate::storage::{images, SharedStore};
+use crate::tax::depreciation;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
+use chrono::Utc;
 use sqlx::SqlitePool;
 
+fn validate_property_type(property_type: &str) -> Result<()> {
+    if depreciation::recovery_period(property_type).is_some() {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown property type '{}'",
+            property_type
+        )))
+    }
+}
+
 pub async fn list_properties(State(pool): State<SqlitePool>) -> Result<Json<Vec<Property>>> {
     let properties =
         sqlx::query_as::<_, Property>("SELECT * FROM properties ORDER BY created_at DESC")
             .fetch_all(&pool)
             .await?;
 
-    Ok(Json(properties))
+    Ok(Json(
+        properties
+            .into_iter()
+            .map(Property::with_cover_url)
+            .collect(),
+    ))
 }
 
 pub async fn get_property(
@@ -26,21 +48,26 @@ pub async fn get_property(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", id)))?;
 
-    Ok(Json(property))
+    Ok(Json(property.with_cover_url()))
 }
 
 pub async fn create_property(
     State(pool): State<SqlitePool>,
     Json(payload): Json<CreateProperty>,
 ) -> Result<(StatusCode, Json<Property>)> {
+    validate_property_type(&payload.property_type)?;
+
+    let mut tx = pool.begin().await?;
+
     let result = sqlx::query(
         r#"
         INSERT INTO properties (
             title, address, city, state, zip_code, property_type,
             bedrooms, bathrooms, square_feet, purchase_price,
-            current_value, monthly_rent, status, notes
+            current_value, monthly_rent, status, notes,
+            land_value, placed_in_service_date
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&payload.title)
@@ -57,11 +84,37 @@ pub async fn create_property(
     .bind(payload.monthly_rent)
     .bind(&payload.status)
     .bind(&payload.notes)
-    .execute(&pool)
+    .bind(payload.land_value)
+    .bind(payload.placed_in_service_date)
+    .execute(&mut *tx)
     .await?;
+    let id = result.last_insert_rowid();
+
+    match &payload.units {
+        Some(units) if !units.is_empty() => {
+            for unit in units {
+                insert_unit(&mut tx, id, unit).await?;
+            }
+        }
+        _ => {
+            let unit = CreateUnit {
+                unit_number: "1".to_string(),
+                bedrooms: payload.bedrooms,
+                bathrooms: payload.bathrooms,
+                square_feet: payload.square_feet,
+                market_rent: payload.monthly_rent,
+                status: Some(payload.status.clone()),
+                notes: None,
+            };
+            insert_unit(&mut tx, id, &unit).await?;
+        }
+    }
+
+    refresh_property(&mut tx, id, Utc::now()).await?;
+    tx.commit().await?;
 
     let property = sqlx::query_as::<_, Property>("SELECT * FROM properties WHERE id = ?")
-        .bind(result.last_insert_rowid())
+        .bind(id)
         .fetch_one(&pool)
         .await?;
 
@@ -79,6 +132,9 @@ pub async fn update_property(
         .fetch_optional(&pool)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", id)))?;
+    if let Some(property_type) = &payload.property_type {
+        validate_property_type(property_type)?;
+    }
 
     // Build dynamic update query
     let mut query = String::from("UPDATE properties SET ");
@@ -126,6 +182,12 @@ pub async fn update_property(
     if payload.notes.is_some() {
         updates.push("notes = ?");
     }
+    if payload.land_value.is_some() {
+        updates.push("land_value = ?");
+    }
+    if payload.placed_in_service_date.is_some() {
+        updates.push("placed_in_service_date = ?");
+    }
 
     updates.push("updated_at = CURRENT_TIMESTAMP");
 
@@ -176,20 +238,100 @@ pub async fn update_property(
     if let Some(v) = &payload.notes {
         q = q.bind(v);
     }
+    if let Some(v) = payload.land_value {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.placed_in_service_date {
+        q = q.bind(v);
+    }
 
     q = q.bind(id);
-    q.execute(&pool).await?;
+    // Status follows the units' leases, so a manual change is re-derived
+    let mut tx = pool.begin().await?;
+    q.execute(&mut *tx).await?;
+    refresh_property(&mut tx, id, Utc::now()).await?;
+    tx.commit().await?;
+
+    let property = sqlx::query_as::<_, Property>("SELECT * FROM properties WHERE id = ?")
+        .bind(id)
+        .fetch_one(&pool)
+        .await?;
+
+    Ok(Json(property.with_cover_url()))
+}
+
+/// Use one of the property's photo attachments as its cover photo
+pub async fn set_cover_photo(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<SetCoverPhoto>,
+) -> Result<Json<Property>> {
+    sqlx::query("SELECT id FROM properties WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", id)))?;
+
+    let content_type: String = sqlx::query_scalar(
+        "SELECT content_type FROM attachments WHERE id = ? AND entity_type = 'property' AND entity_id = ?",
+    )
+    .bind(payload.attachment_id)
+    .bind(id)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Attachment {} is not attached to property {}",
+            payload.attachment_id, id
+        ))
+    })?;
+    if !images::is_processable(&content_type) {
+        return Err(AppError::BadRequest(format!(
+            "Attachment {} is not a photo ({})",
+            payload.attachment_id, content_type
+        )));
+    }
+
+    sqlx::query(
+        "UPDATE properties SET cover_attachment_id = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(payload.attachment_id)
+    .bind(id)
+    .execute(&pool)
+    .await?;
 
     let property = sqlx::query_as::<_, Property>("SELECT * FROM properties WHERE id = ?")
         .bind(id)
         .fetch_one(&pool)
         .await?;
 
-    Ok(Json(property))
+    Ok(Json(property.with_cover_url()))
+}
+
+pub async fn clear_cover_photo(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let result = sqlx::query(
+        "UPDATE properties SET cover_attachment_id = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(id)
+    .execute(&pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Property with id {} not found",
+            id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn delete_property(
     State(pool): State<SqlitePool>,
+    Extension(store): Extension<SharedStore>,
     Path(id): Path<i64>,
 ) -> Result<StatusCode> {
     let result = sqlx::query("DELETE FROM properties WHERE id = ?")
@@ -204,5 +346,7 @@ pub async fn delete_property(
         )));
     }
 
+    release_owner_blobs(&pool, &store).await;
+
     Ok(StatusCode::NO_CONTENT)
 }
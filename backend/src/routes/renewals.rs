@@ -0,0 +1,335 @@
+use crate::error::{AppError, Result};
+use crate::jobs::lease_renewals::{self, open_renewal, start_renewed_lease, RenewalWindow};
+use crate::leasing::deposits::transfer_to_renewal;
+use crate::models::{
+    LeaseRenewal, LeaseRenewalDetail, OfferRenewal, RenewalResponse, RenewalRunSummary,
+};
+use crate::routes::leases::{fetch_lease, insert_lease, load_detail, sync_lease_tenants};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use chrono::{Duration, Months, Utc};
+use serde::Deserialize;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+#[derive(Debug, Deserialize)]
+pub struct RenewalQuery {
+    pub status: Option<String>,
+}
+
+async fn fetch_renewal(pool: &SqlitePool, id: i64) -> Result<LeaseRenewal> {
+    sqlx::query_as::<_, LeaseRenewal>("SELECT * FROM lease_renewals WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Lease renewal with id {} not found", id)))
+}
+
+async fn load_renewal(pool: &SqlitePool, renewal: LeaseRenewal) -> Result<LeaseRenewalDetail> {
+    let lease = fetch_lease(pool, renewal.lease_id).await?;
+    let lease = load_detail(pool, lease).await?;
+
+    Ok(LeaseRenewalDetail { renewal, lease })
+}
+
+fn require_status(renewal: &LeaseRenewal, allowed: &[&str], action: &str) -> Result<()> {
+    if allowed.contains(&renewal.status.as_str()) {
+        Ok(())
+    } else {
+        Err(AppError::Conflict(format!(
+            "Cannot {} a renewal that is {}",
+            action, renewal.status
+        )))
+    }
+}
+
+async fn close_event(tx: &mut Transaction<'_, Sqlite>, renewal: &LeaseRenewal) -> Result<()> {
+    sqlx::query(
+        "UPDATE calendar_events SET completed = 1, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(renewal.event_id)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn list_renewals(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<RenewalQuery>,
+) -> Result<Json<Vec<LeaseRenewalDetail>>> {
+    let renewals = sqlx::query_as::<_, LeaseRenewal>(
+        r#"
+        SELECT r.* FROM lease_renewals r
+        JOIN leases l ON l.id = r.lease_id
+        WHERE (?1 IS NULL OR r.status = ?1)
+        ORDER BY l.end_date
+        "#,
+    )
+    .bind(&params.status)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut details = Vec::with_capacity(renewals.len());
+    for renewal in renewals {
+        details.push(load_renewal(&pool, renewal).await?);
+    }
+
+    Ok(Json(details))
+}
+
+pub async fn get_renewal(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<LeaseRenewalDetail>> {
+    let renewal = fetch_renewal(&pool, id).await?;
+
+    Ok(Json(load_renewal(&pool, renewal).await?))
+}
+
+/// Open renewals for leases nearing their end and start signed renewals
+pub async fn run_renewals(
+    State(pool): State<SqlitePool>,
+    Extension(window): Extension<RenewalWindow>,
+) -> Result<Json<RenewalRunSummary>> {
+    let summary = lease_renewals::run(&pool, Utc::now(), window).await?;
+
+    Ok(Json(summary))
+}
+
+/// Offer renewal terms, opening the renewal if the job hasn't yet. A declined
+/// offer can be replaced with a new one.
+pub async fn offer_renewal(
+    State(pool): State<SqlitePool>,
+    Path(lease_id): Path<i64>,
+    Json(payload): Json<OfferRenewal>,
+) -> Result<Json<LeaseRenewalDetail>> {
+    let lease = fetch_lease(&pool, lease_id).await?;
+    if lease.status != "active" {
+        return Err(AppError::Conflict(format!(
+            "Lease {} is {}; only active leases can be renewed",
+            lease_id, lease.status
+        )));
+    }
+    let Some(current_end) = lease.end_date else {
+        return Err(AppError::BadRequest(
+            "Set an end_date on a month-to-month lease before offering a renewal".to_string(),
+        ));
+    };
+    if payload.proposed_rent <= 0.0 {
+        return Err(AppError::BadRequest(
+            "proposed_rent must be greater than zero".to_string(),
+        ));
+    }
+
+    let lease_type = payload.lease_type.as_deref().unwrap_or("fixed");
+    let start = current_end + Duration::days(1);
+    let end = match (lease_type, payload.term_months) {
+        ("fixed", Some(months)) if months > 0 => start
+            .checked_add_months(Months::new(months as u32))
+            .map(|d| d - Duration::days(1)),
+        ("fixed", _) => {
+            return Err(AppError::BadRequest(
+                "A fixed-term offer needs a positive term_months".to_string(),
+            ))
+        }
+        ("month_to_month", _) => None,
+        (other, _) => {
+            return Err(AppError::BadRequest(format!(
+                "Unknown lease type '{}'",
+                other
+            )))
+        }
+    };
+
+    let mut tx = pool.begin().await?;
+    open_renewal(&mut tx, &lease).await?;
+
+    let renewal =
+        sqlx::query_as::<_, LeaseRenewal>("SELECT * FROM lease_renewals WHERE lease_id = ?")
+            .bind(lease_id)
+            .fetch_one(&mut *tx)
+            .await?;
+    require_status(&renewal, &["pending", "offered", "declined"], "offer")?;
+
+    sqlx::query(
+        r#"
+        UPDATE lease_renewals SET
+            status = 'offered',
+            proposed_rent = ?,
+            proposed_lease_type = ?,
+            proposed_term_months = ?,
+            proposed_start_date = ?,
+            proposed_end_date = ?,
+            offered_at = CURRENT_TIMESTAMP,
+            responded_at = NULL,
+            notes = COALESCE(?, notes),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = ?
+        "#,
+    )
+    .bind(payload.proposed_rent)
+    .bind(lease_type)
+    .bind(payload.term_months.filter(|_| lease_type == "fixed"))
+    .bind(start)
+    .bind(end)
+    .bind(&payload.notes)
+    .bind(renewal.id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let renewal = fetch_renewal(&pool, renewal.id).await?;
+
+    Ok(Json(load_renewal(&pool, renewal).await?))
+}
+
+/// The tenant accepts: the next lease period is created as a pending lease
+/// with the same occupants, leaving the current lease untouched
+pub async fn accept_renewal(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<RenewalResponse>,
+) -> Result<Json<LeaseRenewalDetail>> {
+    let renewal = fetch_renewal(&pool, id).await?;
+    require_status(&renewal, &["offered"], "accept")?;
+
+    let lease = fetch_lease(&pool, renewal.lease_id).await?;
+    let (Some(rent), Some(start)) = (renewal.proposed_rent, renewal.proposed_start_date) else {
+        return Err(AppError::Conflict(
+            "The renewal has no offered terms".to_string(),
+        ));
+    };
+
+    let mut tx = pool.begin().await?;
+
+    let new_lease_id = insert_lease(
+        &mut tx,
+        lease.property_id,
+        lease.unit_id,
+        renewal.proposed_lease_type.as_deref().unwrap_or("fixed"),
+        start,
+        renewal.proposed_end_date,
+        rent,
+        lease.deposit_amount,
+        lease.rent_due_day,
+        "pending",
+        Some(lease.id),
+        Some(&format!("Renewal of lease #{}", lease.id)),
+    )
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO lease_occupants (lease_id, tenant_id, role)
+        SELECT ?, tenant_id, role FROM lease_occupants WHERE lease_id = ?
+        "#,
+    )
+    .bind(new_lease_id)
+    .bind(lease.id)
+    .execute(&mut *tx)
+    .await?;
+
+    transfer_to_renewal(&mut tx, lease.id, new_lease_id, start).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE lease_renewals SET
+            status = 'accepted',
+            new_lease_id = ?,
+            responded_at = CURRENT_TIMESTAMP,
+            notes = COALESCE(?, notes),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = ?
+        "#,
+    )
+    .bind(new_lease_id)
+    .bind(&payload.notes)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    sync_lease_tenants(&mut *tx, new_lease_id).await?;
+    tx.commit().await?;
+
+    let renewal = fetch_renewal(&pool, id).await?;
+
+    Ok(Json(load_renewal(&pool, renewal).await?))
+}
+
+pub async fn decline_renewal(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<RenewalResponse>,
+) -> Result<Json<LeaseRenewalDetail>> {
+    let renewal = fetch_renewal(&pool, id).await?;
+    require_status(&renewal, &["offered"], "decline")?;
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        UPDATE lease_renewals SET
+            status = 'declined',
+            responded_at = CURRENT_TIMESTAMP,
+            notes = COALESCE(?, notes),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = ?
+        "#,
+    )
+    .bind(&payload.notes)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    close_event(&mut tx, &renewal).await?;
+    tx.commit().await?;
+
+    let renewal = fetch_renewal(&pool, id).await?;
+
+    Ok(Json(load_renewal(&pool, renewal).await?))
+}
+
+/// Signing finalises an accepted renewal; the new lease becomes active on
+/// its start date (immediately if that has already passed)
+pub async fn sign_renewal(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<RenewalResponse>,
+) -> Result<Json<LeaseRenewalDetail>> {
+    let renewal = fetch_renewal(&pool, id).await?;
+    require_status(&renewal, &["accepted"], "sign")?;
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        UPDATE lease_renewals SET
+            status = 'signed',
+            signed_at = CURRENT_TIMESTAMP,
+            notes = COALESCE(?, notes),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = ?
+        "#,
+    )
+    .bind(&payload.notes)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    close_event(&mut tx, &renewal).await?;
+
+    if renewal
+        .proposed_start_date
+        .is_some_and(|start| start <= Utc::now())
+    {
+        start_renewed_lease(&mut tx, &renewal).await?;
+    }
+
+    tx.commit().await?;
+
+    let renewal = fetch_renewal(&pool, id).await?;
+
+    Ok(Json(load_renewal(&pool, renewal).await?))
+}
@@ -0,0 +1,313 @@
+use crate::error::{AppError, Result};
+use crate::leasing::{escalation, rent_roll};
+use crate::models::CashFlowProjection;
+use crate::tax::{form_1099, schedule_e};
+use axum::{
+    extract::{Query, State},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Datelike, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Deserialize)]
+pub struct TaxYearQuery {
+    pub year: Option<i32>,
+    pub format: Option<String>, // json (default) or csv
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RentRollQuery {
+    pub as_of: Option<DateTime<Utc>>,
+    pub property_id: Option<i64>,
+    pub format: Option<String>, // json (default) or csv
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CashFlowQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub months: Option<u32>,
+    pub property_id: Option<i64>,
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_response(filename: &str, rows: Vec<Vec<String>>) -> Response {
+    let body = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|v| csv_field(v))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n");
+
+    (
+        [
+            (CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Default to the most recently completed tax year
+fn last_tax_year() -> i32 {
+    Utc::now().year() - 1
+}
+
+fn wants_csv(format: Option<&str>) -> Result<bool> {
+    match format {
+        None | Some("json") => Ok(false),
+        Some("csv") => Ok(true),
+        Some(other) => Err(AppError::BadRequest(format!(
+            "Unsupported report format '{}'",
+            other
+        ))),
+    }
+}
+
+pub async fn get_schedule_e(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<TaxYearQuery>,
+) -> Result<Response> {
+    let year = params.year.unwrap_or_else(last_tax_year);
+    let csv = wants_csv(params.format.as_deref())?;
+
+    let report = schedule_e::build_report(&pool, year).await?;
+
+    if !csv {
+        return Ok(Json(report).into_response());
+    }
+
+    let mut header = vec![
+        "tax_year".to_string(),
+        "property_id".to_string(),
+        "property_title".to_string(),
+        "address".to_string(),
+        "property_type".to_string(),
+    ];
+    header.extend(
+        report
+            .totals
+            .columns()
+            .into_iter()
+            .map(|(name, _)| name.to_string()),
+    );
+
+    let mut rows = vec![header];
+    for property in &report.properties {
+        let mut row = vec![
+            year.to_string(),
+            property.property_id.to_string(),
+            property.property_title.clone(),
+            property.address.clone(),
+            property.property_type.clone(),
+        ];
+        row.extend(
+            property
+                .lines
+                .columns()
+                .into_iter()
+                .map(|(_, amount)| format!("{:.2}", amount)),
+        );
+        rows.push(row);
+    }
+
+    let mut totals = vec![
+        year.to_string(),
+        String::new(),
+        "TOTAL".to_string(),
+        String::new(),
+        String::new(),
+    ];
+    totals.extend(
+        report
+            .totals
+            .columns()
+            .into_iter()
+            .map(|(_, amount)| format!("{:.2}", amount)),
+    );
+    rows.push(totals);
+
+    Ok(csv_response(&format!("schedule-e-{}.csv", year), rows))
+}
+
+pub async fn get_1099_nec(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<TaxYearQuery>,
+) -> Result<Response> {
+    let year = params.year.unwrap_or_else(last_tax_year);
+    let csv = wants_csv(params.format.as_deref())?;
+
+    let report = form_1099::build_report(&pool, year).await?;
+
+    if !csv {
+        return Ok(Json(report).into_response());
+    }
+
+    let mut rows = vec![[
+        "tax_year",
+        "contractor_id",
+        "name",
+        "entity_type",
+        "tax_id",
+        "address",
+        "city",
+        "state",
+        "zip_code",
+        "maintenance_total",
+        "expense_total",
+        "total_paid",
+        "missing_tax_id",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect::<Vec<_>>()];
+
+    for vendor in &report.vendors {
+        rows.push(vec![
+            year.to_string(),
+            vendor.contractor_id.to_string(),
+            vendor.name.clone(),
+            vendor.entity_type.clone(),
+            vendor.tax_id.clone().unwrap_or_default(),
+            vendor.address.clone().unwrap_or_default(),
+            vendor.city.clone().unwrap_or_default(),
+            vendor.state.clone().unwrap_or_default(),
+            vendor.zip_code.clone().unwrap_or_default(),
+            format!("{:.2}", vendor.maintenance_total),
+            format!("{:.2}", vendor.expense_total),
+            format!("{:.2}", vendor.total_paid),
+            vendor.missing_tax_id.to_string(),
+        ]);
+    }
+
+    Ok(csv_response(&format!("1099-nec-{}.csv", year), rows))
+}
+
+fn money(amount: Option<f64>) -> String {
+    amount.map(|a| format!("{:.2}", a)).unwrap_or_default()
+}
+
+fn date(date: Option<DateTime<Utc>>) -> String {
+    date.map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+pub async fn get_rent_roll(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<RentRollQuery>,
+) -> Result<Response> {
+    let as_of = params.as_of.unwrap_or_else(Utc::now);
+    let csv = wants_csv(params.format.as_deref())?;
+
+    let report = rent_roll::build_rent_roll(&pool, as_of, params.property_id).await?;
+
+    if !csv {
+        return Ok(Json(report).into_response());
+    }
+
+    let mut rows = vec![[
+        "property_id",
+        "property_title",
+        "address",
+        "unit_id",
+        "unit_number",
+        "bedrooms",
+        "bathrooms",
+        "square_feet",
+        "status",
+        "lease_id",
+        "lease_type",
+        "tenants",
+        "lease_start",
+        "lease_end",
+        "market_rent",
+        "monthly_rent",
+        "deposit_held",
+        "balance_due",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect::<Vec<_>>()];
+
+    let optional = |v: Option<String>| v.unwrap_or_default();
+
+    for row in &report.rows {
+        rows.push(vec![
+            row.property_id.to_string(),
+            row.property_title.clone(),
+            row.address.clone(),
+            row.unit_id.to_string(),
+            row.unit_number.clone(),
+            optional(row.bedrooms.map(|v| v.to_string())),
+            optional(row.bathrooms.map(|v| v.to_string())),
+            optional(row.square_feet.map(|v| v.to_string())),
+            row.status.clone(),
+            optional(row.lease_id.map(|v| v.to_string())),
+            optional(row.lease_type.clone()),
+            row.tenants.join("; "),
+            date(row.lease_start),
+            date(row.lease_end),
+            money(row.market_rent),
+            money(row.monthly_rent),
+            money(row.deposit_held),
+            format!("{:.2}", row.balance_due),
+        ]);
+    }
+
+    let totals = &report.totals;
+    let mut total_row = vec![String::new(); 18];
+    total_row[1] = "TOTAL".to_string();
+    total_row[4] = format!("{} units", totals.units);
+    total_row[8] = format!("{} occupied", totals.occupied_units);
+    total_row[14] = format!("{:.2}", totals.market_rent);
+    total_row[15] = format!("{:.2}", totals.monthly_rent);
+    total_row[16] = format!("{:.2}", totals.deposits_held);
+    total_row[17] = format!("{:.2}", totals.balance_due);
+    rows.push(total_row);
+
+    Ok(csv_response(
+        &format!("rent-roll-{}.csv", as_of.format("%Y-%m-%d")),
+        rows,
+    ))
+}
+
+/// Month-by-month scheduled rent (with escalations) less debt service
+pub async fn get_cash_flow_projection(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<CashFlowQuery>,
+) -> Result<Json<CashFlowProjection>> {
+    let months = params.months.unwrap_or(12);
+    if !(1..=120).contains(&months) {
+        return Err(AppError::BadRequest(
+            "months must be between 1 and 120".to_string(),
+        ));
+    }
+
+    let projection = escalation::cash_flow_projection(
+        &pool,
+        params.from.unwrap_or_else(Utc::now),
+        months,
+        params.property_id,
+    )
+    .await?;
+
+    Ok(Json(projection))
+}
@@ -0,0 +1,416 @@
+use crate::error::{AppError, Result};
+use crate::models::{
+    AccessWindow, CreateMaintenanceRequest, MaintenanceRequest, MaintenanceRequestDetail,
+    PortalLink, TriageMaintenanceRequest,
+};
+use crate::routes::contractors::contractor_name;
+use crate::routes::maintenance::record_status_change;
+use crate::routes::plans::validate_priority;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceRequestQuery {
+    pub status: Option<String>,
+}
+
+/// Resolve a portal link token to its tenant, property and unit. The link
+/// only works while the tenant is on an active or upcoming lease.
+async fn portal_tenant(pool: &SqlitePool, token: &str) -> Result<(i64, i64, Option<i64>)> {
+    sqlx::query_as(
+        r#"
+        SELECT t.id, t.property_id, t.unit_id FROM tenants t
+        WHERE t.portal_token = ?
+          AND EXISTS (
+              SELECT 1 FROM lease_occupants o
+              JOIN leases l ON l.id = o.lease_id
+              WHERE o.tenant_id = t.id AND l.status IN ('active', 'pending')
+          )
+        "#,
+    )
+    .bind(token)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Unknown portal link".to_string()))
+}
+
+async fn load_detail(
+    pool: &SqlitePool,
+    request: MaintenanceRequest,
+) -> Result<MaintenanceRequestDetail> {
+    let access_windows = sqlx::query_as::<_, AccessWindow>(
+        r#"
+        SELECT starts_at, ends_at FROM maintenance_request_access_windows
+        WHERE request_id = ?
+        ORDER BY starts_at
+        "#,
+    )
+    .bind(request.id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(MaintenanceRequestDetail {
+        request,
+        access_windows,
+    })
+}
+
+async fn fetch_request(pool: &SqlitePool, id: i64) -> Result<MaintenanceRequest> {
+    sqlx::query_as::<_, MaintenanceRequest>("SELECT * FROM maintenance_requests WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Maintenance request with id {} not found", id)))
+}
+
+/// Human-readable summary of the tenant's access preferences for the work order
+fn access_notes(request: &MaintenanceRequest, windows: &[AccessWindow]) -> String {
+    let mut lines = vec![if request.permission_to_enter {
+        "Tenant gives permission to enter.".to_string()
+    } else {
+        "Tenant must be present; do not enter without them.".to_string()
+    }];
+    for window in windows {
+        lines.push(format!(
+            "Available {} to {}",
+            window.starts_at.format("%Y-%m-%d %H:%M"),
+            window.ends_at.format("%Y-%m-%d %H:%M")
+        ));
+    }
+    if let Some(notes) = &request.entry_notes {
+        lines.push(format!("Entry notes: {}", notes));
+    }
+    lines.join("\n")
+}
+
+// Tenant portal
+
+pub async fn list_portal_requests(
+    State(pool): State<SqlitePool>,
+    Path(token): Path<String>,
+) -> Result<Json<Vec<MaintenanceRequestDetail>>> {
+    let (tenant_id, _, _) = portal_tenant(&pool, &token).await?;
+
+    let requests = sqlx::query_as::<_, MaintenanceRequest>(
+        "SELECT * FROM maintenance_requests WHERE tenant_id = ? ORDER BY created_at DESC",
+    )
+    .bind(tenant_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut details = Vec::with_capacity(requests.len());
+    for request in requests {
+        details.push(load_detail(&pool, request).await?);
+    }
+
+    Ok(Json(details))
+}
+
+pub async fn get_portal_request(
+    State(pool): State<SqlitePool>,
+    Path((token, id)): Path<(String, i64)>,
+) -> Result<Json<MaintenanceRequestDetail>> {
+    let (tenant_id, _, _) = portal_tenant(&pool, &token).await?;
+
+    let request = fetch_request(&pool, id).await?;
+    // Don't reveal other tenants' requests
+    if request.tenant_id != tenant_id {
+        return Err(AppError::NotFound(format!(
+            "Maintenance request with id {} not found",
+            id
+        )));
+    }
+
+    Ok(Json(load_detail(&pool, request).await?))
+}
+
+pub async fn submit_portal_request(
+    State(pool): State<SqlitePool>,
+    Path(token): Path<String>,
+    Json(payload): Json<CreateMaintenanceRequest>,
+) -> Result<(StatusCode, Json<MaintenanceRequestDetail>)> {
+    let (tenant_id, property_id, unit_id) = portal_tenant(&pool, &token).await?;
+
+    if payload.title.trim().is_empty() || payload.description.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "Title and description are required".to_string(),
+        ));
+    }
+    let urgency = payload
+        .urgency
+        .clone()
+        .unwrap_or_else(|| "medium".to_string());
+    validate_priority(&urgency)?;
+    let windows = payload.access_windows.unwrap_or_default();
+    if let Some(window) = windows.iter().find(|w| w.ends_at <= w.starts_at) {
+        return Err(AppError::BadRequest(format!(
+            "Access window starting {} must end after it starts",
+            window.starts_at
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let id = sqlx::query(
+        r#"
+        INSERT INTO maintenance_requests (
+            tenant_id, property_id, unit_id, title, description, urgency,
+            permission_to_enter, entry_notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(tenant_id)
+    .bind(property_id)
+    .bind(unit_id)
+    .bind(&payload.title)
+    .bind(&payload.description)
+    .bind(&urgency)
+    .bind(payload.permission_to_enter.unwrap_or(false))
+    .bind(&payload.entry_notes)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    for window in &windows {
+        sqlx::query(
+            r#"
+            INSERT INTO maintenance_request_access_windows (request_id, starts_at, ends_at)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(id)
+        .bind(window.starts_at)
+        .bind(window.ends_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let request = fetch_request(&pool, id).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(load_detail(&pool, request).await?),
+    ))
+}
+
+// Staff
+
+pub async fn list_maintenance_requests(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<MaintenanceRequestQuery>,
+) -> Result<Json<Vec<MaintenanceRequestDetail>>> {
+    let requests = sqlx::query_as::<_, MaintenanceRequest>(
+        r#"
+        SELECT * FROM maintenance_requests
+        WHERE (?1 IS NULL OR status = ?1)
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(&params.status)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut details = Vec::with_capacity(requests.len());
+    for request in requests {
+        details.push(load_detail(&pool, request).await?);
+    }
+
+    Ok(Json(details))
+}
+
+pub async fn get_maintenance_request(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<MaintenanceRequestDetail>> {
+    let request = fetch_request(&pool, id).await?;
+
+    Ok(Json(load_detail(&pool, request).await?))
+}
+
+/// Convert a submitted request into a pending work order, or decline it
+pub async fn triage_maintenance_request(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<TriageMaintenanceRequest>,
+) -> Result<Json<MaintenanceRequestDetail>> {
+    let request = fetch_request(&pool, id).await?;
+    if request.status != "submitted" {
+        return Err(AppError::Conflict(format!(
+            "Maintenance request {} has already been {}",
+            id, request.status
+        )));
+    }
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+
+    let triaged = match payload.decision.as_str() {
+        "convert" => {
+            let priority = payload
+                .priority
+                .clone()
+                .unwrap_or_else(|| request.urgency.clone());
+            validate_priority(&priority)?;
+            let status = if payload.scheduled_date.is_some() {
+                "scheduled"
+            } else {
+                "pending"
+            };
+            let contractor = match payload.contractor_id {
+                Some(contractor_id) => Some(contractor_name(&pool, contractor_id).await?),
+                None => None,
+            };
+            let windows = load_detail(&pool, request.clone()).await?.access_windows;
+
+            let maintenance_id = sqlx::query(
+                r#"
+                INSERT INTO maintenance_records (
+                    property_id, unit_id, title, description, priority, status,
+                    estimated_cost, scheduled_date, contractor, contractor_id, notes
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(request.property_id)
+            .bind(request.unit_id)
+            .bind(payload.title.as_ref().unwrap_or(&request.title))
+            .bind(&request.description)
+            .bind(&priority)
+            .bind(status)
+            .bind(payload.estimated_cost)
+            .bind(payload.scheduled_date)
+            .bind(&contractor)
+            .bind(payload.contractor_id)
+            .bind(access_notes(&request, &windows))
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+
+            record_status_change(
+                &mut tx,
+                maintenance_id,
+                None,
+                status,
+                payload.triaged_by.as_deref(),
+                Some(&format!("Tenant request #{}", id)),
+            )
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE maintenance_requests
+                SET status = 'converted', maintenance_id = ?, triaged_by = ?,
+                    triage_notes = ?, triaged_at = ?, updated_at = CURRENT_TIMESTAMP
+                WHERE id = ? AND status = 'submitted'
+                "#,
+            )
+            .bind(maintenance_id)
+            .bind(&payload.triaged_by)
+            .bind(&payload.notes)
+            .bind(now)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+        }
+        "decline" => sqlx::query(
+            r#"
+            UPDATE maintenance_requests
+            SET status = 'declined', triaged_by = ?, triage_notes = ?,
+                triaged_at = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ? AND status = 'submitted'
+            "#,
+        )
+        .bind(&payload.triaged_by)
+        .bind(&payload.notes)
+        .bind(now)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected(),
+        other => {
+            return Err(AppError::BadRequest(format!(
+                "Unknown triage decision '{}' (expected convert or decline)",
+                other
+            )))
+        }
+    };
+    // Someone else triaged it first; dropping the transaction discards the
+    // work order created above
+    if triaged == 0 {
+        return Err(AppError::Conflict(format!(
+            "Maintenance request {} has already been triaged",
+            id
+        )));
+    }
+
+    tx.commit().await?;
+
+    let request = fetch_request(&pool, id).await?;
+
+    Ok(Json(load_detail(&pool, request).await?))
+}
+
+pub async fn get_portal_link(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<PortalLink>> {
+    let token: Option<String> = sqlx::query_scalar("SELECT portal_token FROM tenants WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Tenant with id {} not found", id)))?;
+
+    let token = match token {
+        Some(token) => token,
+        None => rotate_token(&pool, id).await?,
+    };
+
+    Ok(Json(PortalLink {
+        tenant_id: id,
+        token,
+    }))
+}
+
+/// Issue a new link token, invalidating the old one
+pub async fn rotate_portal_link(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<PortalLink>> {
+    sqlx::query("SELECT id FROM tenants WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Tenant with id {} not found", id)))?;
+
+    let token = rotate_token(&pool, id).await?;
+
+    Ok(Json(PortalLink {
+        tenant_id: id,
+        token,
+    }))
+}
+
+async fn rotate_token(pool: &SqlitePool, tenant_id: i64) -> Result<String> {
+    let token = sqlx::query_scalar(
+        r#"
+        UPDATE tenants SET portal_token = lower(hex(randomblob(24)))
+        WHERE id = ?
+        RETURNING portal_token
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(token)
+}
@@ -0,0 +1,185 @@
+use crate::error::{AppError, Result};
+use crate::models::{
+    MaintenanceRecord, MaintenanceSla, SlaComplianceReport, SlaTarget, UpdateSlaTarget,
+};
+use crate::sla;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+pub struct ComplianceQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+pub async fn list_sla_targets(State(pool): State<SqlitePool>) -> Result<Json<Vec<SlaTarget>>> {
+    let targets = sqlx::query_as::<_, SlaTarget>(
+        "SELECT * FROM maintenance_sla_targets ORDER BY resolution_hours ASC",
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(targets))
+}
+
+pub async fn update_sla_target(
+    State(pool): State<SqlitePool>,
+    Path(priority): Path<String>,
+    Json(payload): Json<UpdateSlaTarget>,
+) -> Result<Json<SlaTarget>> {
+    let current =
+        sqlx::query_as::<_, SlaTarget>("SELECT * FROM maintenance_sla_targets WHERE priority = ?")
+            .bind(&priority)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("No SLA target for priority '{}'", priority))
+            })?;
+
+    let response_hours = payload.response_hours.unwrap_or(current.response_hours);
+    let resolution_hours = payload.resolution_hours.unwrap_or(current.resolution_hours);
+
+    if response_hours <= 0.0 || resolution_hours <= 0.0 {
+        return Err(AppError::BadRequest(
+            "SLA targets must be positive".to_string(),
+        ));
+    }
+    if response_hours > resolution_hours {
+        return Err(AppError::BadRequest(
+            "Response target cannot be longer than the resolution target".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE maintenance_sla_targets
+        SET response_hours = ?, resolution_hours = ?, updated_at = CURRENT_TIMESTAMP
+        WHERE priority = ?
+        "#,
+    )
+    .bind(response_hours)
+    .bind(resolution_hours)
+    .bind(&priority)
+    .execute(&pool)
+    .await?;
+
+    let target =
+        sqlx::query_as::<_, SlaTarget>("SELECT * FROM maintenance_sla_targets WHERE priority = ?")
+            .bind(&priority)
+            .fetch_one(&pool)
+            .await?;
+
+    Ok(Json(target))
+}
+
+pub async fn get_maintenance_sla(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<MaintenanceSla>> {
+    let record =
+        sqlx::query_as::<_, MaintenanceRecord>("SELECT * FROM maintenance_records WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Maintenance record with id {} not found", id))
+            })?;
+
+    let mut evaluations = sla::evaluate(&pool, &[record], Utc::now()).await?;
+
+    Ok(Json(evaluations.remove(0)))
+}
+
+/// Open work orders that have already missed a target, most overdue first
+pub async fn list_sla_breaches(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<MaintenanceSla>>> {
+    let records = sqlx::query_as::<_, MaintenanceRecord>(
+        "SELECT * FROM maintenance_records WHERE status NOT IN ('completed', 'cancelled')",
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let mut breaches: Vec<MaintenanceSla> = sla::evaluate(&pool, &records, Utc::now())
+        .await?
+        .into_iter()
+        .filter(|s| s.sla_status == "breached")
+        .collect();
+
+    breaches.sort_by_key(|s| {
+        if s.response_status == "breached" {
+            s.response_due
+        } else {
+            s.resolution_due
+        }
+    });
+
+    Ok(Json(breaches))
+}
+
+/// Compliance for work orders opened in the period, defaulting to the last 90 days
+pub async fn get_sla_compliance(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<ComplianceQuery>,
+) -> Result<Json<SlaComplianceReport>> {
+    let to = params.to.unwrap_or_else(Utc::now);
+    let from = params.from.unwrap_or(to - Duration::days(90));
+
+    if from >= to {
+        return Err(AppError::BadRequest(
+            "'from' must be before 'to'".to_string(),
+        ));
+    }
+
+    let records = sqlx::query_as::<_, MaintenanceRecord>(
+        r#"
+        SELECT * FROM maintenance_records
+        WHERE status != 'cancelled'
+          AND julianday(created_at) >= julianday(?)
+          AND julianday(created_at) < julianday(?)
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(&pool)
+    .await?;
+
+    let evaluations = sla::evaluate(&pool, &records, Utc::now()).await?;
+
+    let property_names: HashMap<i64, String> =
+        sqlx::query_as::<_, (i64, String)>("SELECT id, title FROM properties")
+            .fetch_all(&pool)
+            .await?
+            .into_iter()
+            .collect();
+    let contractor_names: HashMap<i64, String> =
+        sqlx::query_as::<_, (i64, String)>("SELECT id, name FROM contractors")
+            .fetch_all(&pool)
+            .await?
+            .into_iter()
+            .collect();
+
+    Ok(Json(SlaComplianceReport {
+        from,
+        to,
+        overall: sla::compliance(None, "All work orders".to_string(), evaluations.iter()),
+        by_property: sla::compliance_by(
+            &evaluations,
+            |s| Some(s.property_id),
+            &property_names,
+            "Unknown property",
+        ),
+        by_contractor: sla::compliance_by(
+            &evaluations,
+            |s| s.contractor_id,
+            &contractor_names,
+            "Unassigned",
+        ),
+    }))
+}
@@ -0,0 +1,55 @@
+use crate::error::{AppError, Result};
+use crate::models::{DepreciationSchedule, PortfolioDepreciation, Property, SkippedProperty};
+use crate::tax::depreciation;
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use sqlx::SqlitePool;
+
+pub async fn get_property_depreciation(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<Json<DepreciationSchedule>> {
+    let property = sqlx::query_as::<_, Property>("SELECT * FROM properties WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", id)))?;
+
+    let schedule = depreciation::property_schedule(&pool, &property).await?;
+
+    Ok(Json(schedule))
+}
+
+pub async fn get_portfolio_depreciation(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PortfolioDepreciation>> {
+    let properties = sqlx::query_as::<_, Property>("SELECT * FROM properties ORDER BY id ASC")
+        .fetch_all(&pool)
+        .await?;
+
+    let mut schedules = Vec::new();
+    let mut skipped = Vec::new();
+
+    // Properties missing tax inputs are reported rather than failing the whole portfolio
+    for property in &properties {
+        match depreciation::property_schedule(&pool, property).await {
+            Ok(schedule) => schedules.push(schedule),
+            Err(AppError::BadRequest(reason)) => skipped.push(SkippedProperty {
+                property_id: property.id,
+                property_title: property.title.clone(),
+                reason,
+            }),
+            Err(e) => return Err(e),
+        }
+    }
+
+    let years = depreciation::combine_years(&schedules);
+
+    Ok(Json(PortfolioDepreciation {
+        properties: schedules,
+        skipped,
+        years,
+    }))
+}
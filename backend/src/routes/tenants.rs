@@ -1,10 +1,19 @@
 use crate::error::{AppError, Result};
-use crate::models::{CreateTenant, Tenant, UpdateTenant};
+use crate::models::{CreateTenant, Lease, Tenant, UpdateTenant};
+use crate::occupancy::refresh_property;
+use crate::routes::attachments::release_owner_blobs;
+use crate::routes::leases::{
+    add_occupant, check_lease_dates, check_overlap, current_lease_id, end_if_unoccupied,
+    insert_lease, lease_status_for, sync_lease_tenants,
+};
+use crate::routes::units::check_unit;
+use crate::storage::SharedStore;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
+use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 
 pub async fn list_tenants(State(pool): State<SqlitePool>) -> Result<Json<Vec<Tenant>>> {
@@ -32,32 +41,111 @@ pub async fn create_tenant(
     State(pool): State<SqlitePool>,
     Json(payload): Json<CreateTenant>,
 ) -> Result<(StatusCode, Json<Tenant>)> {
+    check_unit(&pool, payload.property_id, payload.unit_id).await?;
+
+    // Joining an existing lease copies its terms; otherwise the payload
+    // describes a new fixed-term lease
+    let joining = match payload.lease_id {
+        Some(lease_id) => {
+            let lease = sqlx::query_as::<_, Lease>("SELECT * FROM leases WHERE id = ?")
+                .bind(lease_id)
+                .fetch_optional(&pool)
+                .await?
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!("Lease {} does not exist", lease_id))
+                })?;
+            if lease.property_id != payload.property_id {
+                return Err(AppError::BadRequest(format!(
+                    "Lease {} is not for property {}",
+                    lease_id, payload.property_id
+                )));
+            }
+            Some(lease)
+        }
+        None => None,
+    };
+
+    let (lease_start, lease_end, monthly_rent, deposit_amount, unit_id) = match &joining {
+        Some(lease) => (
+            lease.start_date,
+            lease.end_date.unwrap_or(lease.start_date),
+            lease.monthly_rent,
+            lease.deposit_amount,
+            lease.unit_id,
+        ),
+        None => match (payload.lease_start, payload.lease_end, payload.monthly_rent) {
+            (Some(start), Some(end), Some(rent)) => {
+                (start, end, rent, payload.deposit_amount, payload.unit_id)
+            }
+            _ => {
+                return Err(AppError::BadRequest(
+                    "lease_start, lease_end and monthly_rent are required unless joining a lease via lease_id"
+                        .to_string(),
+                ))
+            }
+        },
+    };
+
+    let mut tx = pool.begin().await?;
+
     let result = sqlx::query(
         r#"
         INSERT INTO tenants (
-            property_id, first_name, last_name, email, phone,
+            property_id, unit_id, first_name, last_name, email, phone,
             lease_start, lease_end, monthly_rent, deposit_amount,
-            status, notes
+            status, notes, portal_token
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, lower(hex(randomblob(24))))
         "#,
     )
     .bind(payload.property_id)
+    .bind(unit_id)
     .bind(&payload.first_name)
     .bind(&payload.last_name)
     .bind(&payload.email)
     .bind(&payload.phone)
-    .bind(payload.lease_start)
-    .bind(payload.lease_end)
-    .bind(payload.monthly_rent)
-    .bind(payload.deposit_amount)
+    .bind(lease_start)
+    .bind(lease_end)
+    .bind(monthly_rent)
+    .bind(deposit_amount)
     .bind(&payload.status)
     .bind(&payload.notes)
-    .execute(&pool)
+    .execute(&mut *tx)
     .await?;
+    let id = result.last_insert_rowid();
+
+    let lease_id = match &joining {
+        Some(lease) => {
+            add_occupant(&mut tx, lease.id, lease.property_id, id, "co_tenant").await?;
+            lease.id
+        }
+        None => {
+            let lease_id = insert_lease(
+                &mut tx,
+                payload.property_id,
+                unit_id,
+                "fixed",
+                lease_start,
+                Some(lease_end),
+                monthly_rent,
+                deposit_amount,
+                1,
+                lease_status_for(&payload.status),
+                None,
+                None,
+            )
+            .await?;
+            add_occupant(&mut tx, lease_id, payload.property_id, id, "primary").await?;
+            lease_id
+        }
+    };
+
+    sync_lease_tenants(&mut *tx, lease_id).await?;
+    refresh_property(&mut tx, payload.property_id, Utc::now()).await?;
+    tx.commit().await?;
 
     let tenant = sqlx::query_as::<_, Tenant>("SELECT * FROM tenants WHERE id = ?")
-        .bind(result.last_insert_rowid())
+        .bind(id)
         .fetch_one(&pool)
         .await?;
 
@@ -69,11 +157,32 @@ pub async fn update_tenant(
     Path(id): Path<i64>,
     Json(payload): Json<UpdateTenant>,
 ) -> Result<Json<Tenant>> {
-    sqlx::query("SELECT id FROM tenants WHERE id = ?")
+    let current = sqlx::query_as::<_, Tenant>("SELECT * FROM tenants WHERE id = ?")
         .bind(id)
         .fetch_optional(&pool)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Tenant with id {} not found", id)))?;
+    let current_property_id = current.property_id;
+
+    // Where a tenant lives and their status come from their lease; the tenant
+    // row only mirrors them
+    let moves = payload
+        .property_id
+        .is_some_and(|v| v != current.property_id)
+        || payload.unit_id.is_some_and(|v| Some(v) != current.unit_id)
+        || payload
+            .status
+            .as_deref()
+            .is_some_and(|v| v != current.status);
+    if moves && current_lease_id(&pool, id).await?.is_some() {
+        return Err(AppError::BadRequest(
+            "property_id, unit_id and status follow the tenant's lease; change them through /api/leases"
+                .to_string(),
+        ));
+    }
+
+    let property_id = payload.property_id.unwrap_or(current_property_id);
+    check_unit(&pool, property_id, payload.unit_id).await?;
 
     let mut query = String::from("UPDATE tenants SET ");
     let mut updates = Vec::new();
@@ -81,6 +190,12 @@ pub async fn update_tenant(
     if payload.property_id.is_some() {
         updates.push("property_id = ?");
     }
+    if payload.unit_id.is_some() {
+        updates.push("unit_id = ?");
+    } else if property_id != current_property_id {
+        // The old unit belongs to the old property
+        updates.push("unit_id = NULL");
+    }
     if payload.first_name.is_some() {
         updates.push("first_name = ?");
     }
@@ -122,6 +237,9 @@ pub async fn update_tenant(
     if let Some(v) = payload.property_id {
         q = q.bind(v);
     }
+    if let Some(v) = payload.unit_id {
+        q = q.bind(v);
+    }
     if let Some(v) = &payload.first_name {
         q = q.bind(v);
     }
@@ -154,7 +272,66 @@ pub async fn update_tenant(
     }
 
     q = q.bind(id);
-    q.execute(&pool).await?;
+
+    let mut tx = pool.begin().await?;
+    q.execute(&mut *tx).await?;
+
+    // Lease terms edited on the tenant belong to their current lease
+    let edits_terms = payload.lease_start.is_some()
+        || payload.lease_end.is_some()
+        || payload.monthly_rent.is_some()
+        || payload.deposit_amount.is_some();
+    if edits_terms {
+        if let Some(lease_id) = current_lease_id(&mut *tx, id).await? {
+            let lease = sqlx::query_as::<_, Lease>("SELECT * FROM leases WHERE id = ?")
+                .bind(lease_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            let start = payload.lease_start.unwrap_or(lease.start_date);
+            let end = payload.lease_end.or(lease.end_date);
+            check_lease_dates(start, end)?;
+            if lease.status != "ended" {
+                check_overlap(
+                    &mut tx,
+                    lease.property_id,
+                    lease.unit_id,
+                    start,
+                    end,
+                    Some(lease_id),
+                )
+                .await?;
+            }
+
+            sqlx::query(
+                r#"
+                UPDATE leases SET
+                    start_date = COALESCE(?, start_date),
+                    end_date = COALESCE(?, end_date),
+                    monthly_rent = COALESCE(?, monthly_rent),
+                    deposit_amount = COALESCE(?, deposit_amount),
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = ?
+                "#,
+            )
+            .bind(payload.lease_start)
+            .bind(payload.lease_end)
+            .bind(payload.monthly_rent)
+            .bind(payload.deposit_amount)
+            .bind(lease_id)
+            .execute(&mut *tx)
+            .await?;
+            sync_lease_tenants(&mut *tx, lease_id).await?;
+        } else {
+            let (start, end): (DateTime<Utc>, DateTime<Utc>) =
+                sqlx::query_as("SELECT lease_start, lease_end FROM tenants WHERE id = ?")
+                    .bind(id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+            check_lease_dates(start, Some(end))?;
+        }
+    }
+
+    tx.commit().await?;
 
     let tenant = sqlx::query_as::<_, Tenant>("SELECT * FROM tenants WHERE id = ?")
         .bind(id)
@@ -166,11 +343,20 @@ pub async fn update_tenant(
 
 pub async fn delete_tenant(
     State(pool): State<SqlitePool>,
+    Extension(store): Extension<SharedStore>,
     Path(id): Path<i64>,
 ) -> Result<StatusCode> {
+    let mut tx = pool.begin().await?;
+
+    let lease_ids: Vec<i64> =
+        sqlx::query_scalar("SELECT lease_id FROM lease_occupants WHERE tenant_id = ?")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
     let result = sqlx::query("DELETE FROM tenants WHERE id = ?")
         .bind(id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await?;
 
     if result.rows_affected() == 0 {
@@ -180,5 +366,22 @@ pub async fn delete_tenant(
         )));
     }
 
+    // A lease left with nobody on it no longer holds the unit
+    let now = Utc::now();
+    for lease_id in lease_ids {
+        if end_if_unoccupied(&mut tx, lease_id, now).await? {
+            let property_id: i64 =
+                sqlx::query_scalar("SELECT property_id FROM leases WHERE id = ?")
+                    .bind(lease_id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+            refresh_property(&mut tx, property_id, now).await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    release_owner_blobs(&pool, &store).await;
+
     Ok(StatusCode::NO_CONTENT)
 }
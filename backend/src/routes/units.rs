@@ -0,0 +1,399 @@
+use crate::error::{AppError, Result};
+use crate::leasing::escalation::rent_on;
+use crate::models::{BuildingAnalytics, CreateUnit, Lease, Unit, UnitAnalytics, UpdateUnit};
+use crate::occupancy::refresh_property;
+use crate::tax::round_cents;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+#[derive(Debug, Deserialize)]
+pub struct UnitAnalyticsQuery {
+    pub property_id: Option<i64>,
+}
+
+fn validate_status(status: &str) -> Result<()> {
+    if matches!(status, "occupied" | "vacant" | "maintenance") {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Unknown unit status '{}'",
+            status
+        )))
+    }
+}
+
+/// Property a unit belongs to, for records that only name the unit
+pub async fn unit_property(pool: &SqlitePool, unit_id: i64) -> Result<i64> {
+    sqlx::query_scalar("SELECT property_id FROM units WHERE id = ?")
+        .bind(unit_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Unit {} does not exist", unit_id)))
+}
+
+/// Reject a unit that isn't part of the record's property
+pub async fn check_unit(pool: &SqlitePool, property_id: i64, unit_id: Option<i64>) -> Result<()> {
+    if let Some(unit_id) = unit_id {
+        if unit_property(pool, unit_id).await? != property_id {
+            return Err(AppError::BadRequest(format!(
+                "Unit {} is not part of property {}",
+                unit_id, property_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub async fn insert_unit(
+    tx: &mut Transaction<'_, Sqlite>,
+    property_id: i64,
+    unit: &CreateUnit,
+) -> Result<i64> {
+    let status = unit.status.as_deref().unwrap_or("vacant");
+    validate_status(status)?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO units (
+            property_id, unit_number, bedrooms, bathrooms, square_feet,
+            market_rent, status, notes
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(property_id)
+    .bind(&unit.unit_number)
+    .bind(unit.bedrooms)
+    .bind(unit.bathrooms)
+    .bind(unit.square_feet)
+    .bind(unit.market_rent)
+    .bind(status)
+    .bind(&unit.notes)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| duplicate_unit(e, &unit.unit_number))?;
+
+    Ok(result.last_insert_rowid())
+}
+
+fn duplicate_unit(err: sqlx::Error, unit_number: &str) -> AppError {
+    match &err {
+        sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Conflict(format!(
+            "Unit {} already exists on this property",
+            unit_number
+        )),
+        _ => err.into(),
+    }
+}
+
+pub async fn list_property_units(
+    State(pool): State<SqlitePool>,
+    Path(property_id): Path<i64>,
+) -> Result<Json<Vec<Unit>>> {
+    sqlx::query("SELECT id FROM properties WHERE id = ?")
+        .bind(property_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", property_id)))?;
+
+    let units =
+        sqlx::query_as::<_, Unit>("SELECT * FROM units WHERE property_id = ? ORDER BY unit_number")
+            .bind(property_id)
+            .fetch_all(&pool)
+            .await?;
+
+    Ok(Json(units))
+}
+
+pub async fn create_unit(
+    State(pool): State<SqlitePool>,
+    Path(property_id): Path<i64>,
+    Json(payload): Json<CreateUnit>,
+) -> Result<(StatusCode, Json<Unit>)> {
+    sqlx::query("SELECT id FROM properties WHERE id = ?")
+        .bind(property_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", property_id)))?;
+
+    let mut tx = pool.begin().await?;
+    let id = insert_unit(&mut tx, property_id, &payload).await?;
+    refresh_property(&mut tx, property_id, Utc::now()).await?;
+    tx.commit().await?;
+
+    let unit = sqlx::query_as::<_, Unit>("SELECT * FROM units WHERE id = ?")
+        .bind(id)
+        .fetch_one(&pool)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(unit)))
+}
+
+pub async fn get_unit(State(pool): State<SqlitePool>, Path(id): Path<i64>) -> Result<Json<Unit>> {
+    let unit = sqlx::query_as::<_, Unit>("SELECT * FROM units WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Unit with id {} not found", id)))?;
+
+    Ok(Json(unit))
+}
+
+pub async fn update_unit(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateUnit>,
+) -> Result<Json<Unit>> {
+    let property_id: i64 = sqlx::query_scalar("SELECT property_id FROM units WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Unit with id {} not found", id)))?;
+
+    if let Some(status) = &payload.status {
+        validate_status(status)?;
+    }
+
+    let mut query = String::from("UPDATE units SET ");
+    let mut updates = Vec::new();
+
+    if payload.unit_number.is_some() {
+        updates.push("unit_number = ?");
+    }
+    if payload.bedrooms.is_some() {
+        updates.push("bedrooms = ?");
+    }
+    if payload.bathrooms.is_some() {
+        updates.push("bathrooms = ?");
+    }
+    if payload.square_feet.is_some() {
+        updates.push("square_feet = ?");
+    }
+    if payload.market_rent.is_some() {
+        updates.push("market_rent = ?");
+    }
+    if payload.status.is_some() {
+        updates.push("status = ?");
+    }
+    if payload.notes.is_some() {
+        updates.push("notes = ?");
+    }
+
+    updates.push("updated_at = CURRENT_TIMESTAMP");
+
+    query.push_str(&updates.join(", "));
+    query.push_str(" WHERE id = ?");
+
+    let mut q = sqlx::query(&query);
+
+    if let Some(v) = &payload.unit_number {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.bedrooms {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.bathrooms {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.square_feet {
+        q = q.bind(v);
+    }
+    if let Some(v) = payload.market_rent {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.status {
+        q = q.bind(v);
+    }
+    if let Some(v) = &payload.notes {
+        q = q.bind(v);
+    }
+
+    q = q.bind(id);
+
+    // Occupied and vacant follow the unit's leases; only maintenance sticks
+    let mut tx = pool.begin().await?;
+    q.execute(&mut *tx)
+        .await
+        .map_err(|e| duplicate_unit(e, payload.unit_number.as_deref().unwrap_or_default()))?;
+    refresh_property(&mut tx, property_id, Utc::now()).await?;
+    tx.commit().await?;
+
+    let unit = sqlx::query_as::<_, Unit>("SELECT * FROM units WHERE id = ?")
+        .bind(id)
+        .fetch_one(&pool)
+        .await?;
+
+    Ok(Json(unit))
+}
+
+pub async fn delete_unit(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    let active: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM tenants WHERE unit_id = ? AND status = 'active'")
+            .bind(id)
+            .fetch_one(&pool)
+            .await?;
+    if active > 0 {
+        return Err(AppError::Conflict(format!(
+            "Unit {} still has active tenants",
+            id
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let property_id: i64 =
+        sqlx::query_scalar("DELETE FROM units WHERE id = ? RETURNING property_id")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Unit with id {} not found", id)))?;
+
+    refresh_property(&mut tx, property_id, Utc::now()).await?;
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Rent in effect today on each active lease, as (property, unit, rent). A
+/// shared lease is counted once however many tenants are on it.
+async fn active_lease_rents(pool: &SqlitePool) -> Result<Vec<(i64, Option<i64>, f64)>> {
+    let leases = sqlx::query_as::<_, Lease>("SELECT * FROM leases WHERE status = 'active'")
+        .fetch_all(pool)
+        .await?;
+
+    let now = Utc::now();
+    let mut rents = Vec::with_capacity(leases.len());
+    for lease in &leases {
+        rents.push((
+            lease.property_id,
+            lease.unit_id,
+            rent_on(pool, lease, now).await?,
+        ));
+    }
+
+    Ok(rents)
+}
+
+/// Rent, tenancy and maintenance per unit, optionally for one building
+pub async fn get_unit_analytics(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<UnitAnalyticsQuery>,
+) -> Result<Json<Vec<UnitAnalytics>>> {
+    let rows = sqlx::query_as::<_, UnitAnalytics>(
+        r#"
+        SELECT
+            u.id AS unit_id,
+            u.property_id,
+            p.title AS property_title,
+            u.unit_number,
+            u.status,
+            u.bedrooms,
+            u.square_feet,
+            u.market_rent,
+            NULL AS current_rent,
+            (SELECT COUNT(*) FROM tenants t
+             WHERE t.unit_id = u.id AND t.status = 'active') AS active_tenants,
+            (SELECT COUNT(*) FROM maintenance_records m
+             WHERE m.unit_id = u.id AND m.status NOT IN ('completed', 'cancelled')) AS open_work_orders,
+            (SELECT TOTAL(m.cost) FROM maintenance_records m
+             WHERE m.unit_id = u.id AND m.status = 'completed') AS maintenance_cost
+        FROM units u
+        JOIN properties p ON p.id = u.property_id
+        WHERE (?1 IS NULL OR u.property_id = ?1)
+        ORDER BY p.title, u.unit_number
+        "#,
+    )
+    .bind(params.property_id)
+    .fetch_all(&pool)
+    .await?;
+
+    // A whole-property lease is shared evenly across the property's units
+    let rents = active_lease_rents(&pool).await?;
+    let units_in = |property_id: i64| rows.iter().filter(|r| r.property_id == property_id).count();
+    let unit_rents: Vec<Option<f64>> = rows
+        .iter()
+        .map(|row| {
+            let shares: Vec<f64> = rents
+                .iter()
+                .filter_map(|(property_id, unit_id, rent)| match unit_id {
+                    Some(unit_id) if *unit_id == row.unit_id => Some(*rent),
+                    None if *property_id == row.property_id => {
+                        Some(rent / units_in(row.property_id) as f64)
+                    }
+                    _ => None,
+                })
+                .collect();
+            (!shares.is_empty()).then(|| round_cents(shares.iter().sum()))
+        })
+        .collect();
+    let rows = rows
+        .into_iter()
+        .zip(unit_rents)
+        .map(|(mut row, rent)| {
+            row.current_rent = rent;
+            row
+        })
+        .collect();
+
+    Ok(Json(rows))
+}
+
+/// Unit counts, occupancy and rent rolled up per building. Maintenance
+/// includes work on common areas that isn't tied to a unit.
+pub async fn get_building_analytics(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<BuildingAnalytics>>> {
+    let rows = sqlx::query_as::<_, BuildingAnalytics>(
+        r#"
+        SELECT
+            p.id AS property_id,
+            p.title AS property_title,
+            p.address,
+            COUNT(u.id) AS total_units,
+            COALESCE(SUM(u.status = 'occupied'), 0) AS occupied_units,
+            COALESCE(SUM(u.status = 'vacant'), 0) AS vacant_units,
+            CASE WHEN COUNT(u.id) > 0
+                 THEN 100.0 * SUM(u.status = 'occupied') / COUNT(u.id)
+                 ELSE 0.0 END AS occupancy_rate,
+            COALESCE(SUM(u.square_feet), 0) AS total_square_feet,
+            TOTAL(u.market_rent) AS market_rent,
+            0.0 AS current_rent,
+            (SELECT COUNT(*) FROM maintenance_records m
+             WHERE m.property_id = p.id AND m.status NOT IN ('completed', 'cancelled')) AS open_work_orders,
+            (SELECT TOTAL(m.cost) FROM maintenance_records m
+             WHERE m.property_id = p.id AND m.status = 'completed') AS maintenance_cost
+        FROM properties p
+        LEFT JOIN units u ON u.property_id = p.id
+        GROUP BY p.id
+        ORDER BY p.title
+        "#,
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let rents = active_lease_rents(&pool).await?;
+    let rows = rows
+        .into_iter()
+        .map(|mut row| {
+            row.current_rent = round_cents(
+                rents
+                    .iter()
+                    .filter(|(property_id, _, _)| *property_id == row.property_id)
+                    .map(|(_, _, rent)| rent)
+                    .sum(),
+            );
+            row
+        })
+        .collect();
+
+    Ok(Json(rows))
+}
@@ -0,0 +1,219 @@
+// Maintenance SLA evaluation
+// Response is the first move out of 'pending'; resolution is the completed date
+use crate::error::Result;
+use crate::models::{MaintenanceRecord, MaintenanceSla, SlaCompliance, SlaTarget};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+
+/// Share of the target window after which an open work order is at risk
+const AT_RISK_FRACTION: f64 = 0.75;
+
+pub async fn load_targets(pool: &SqlitePool) -> Result<HashMap<String, SlaTarget>> {
+    let targets = sqlx::query_as::<_, SlaTarget>("SELECT * FROM maintenance_sla_targets")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(targets
+        .into_iter()
+        .map(|t| (t.priority.clone(), t))
+        .collect())
+}
+
+/// Evaluate each record against the target for its priority as of `now`
+pub async fn evaluate(
+    pool: &SqlitePool,
+    records: &[MaintenanceRecord],
+    now: DateTime<Utc>,
+) -> Result<Vec<MaintenanceSla>> {
+    let targets = load_targets(pool).await?;
+    if records.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Records that started out past 'pending' (imported with their current
+    // status, or created already scheduled) never had a response to time
+    let placeholders = vec!["?"; records.len()].join(", ");
+    let query = format!(
+        r#"
+        SELECT maintenance_id,
+               MIN(CASE WHEN from_status = 'pending' AND to_status != 'pending' THEN changed_at END),
+               MAX(from_status IS NULL AND to_status != 'pending')
+        FROM maintenance_status_history
+        WHERE maintenance_id IN ({})
+        GROUP BY maintenance_id
+        "#,
+        placeholders
+    );
+    let mut q = sqlx::query_as::<_, (i64, Option<DateTime<Utc>>, bool)>(&query);
+    for record in records {
+        q = q.bind(record.id);
+    }
+    let responses: HashMap<i64, Response> = q
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(id, responded_at, started_past_pending)| {
+            let response = if started_past_pending {
+                Response::NotTracked
+            } else {
+                Response::Tracked(responded_at)
+            };
+            (id, response)
+        })
+        .collect();
+
+    Ok(records
+        .iter()
+        .map(|record| evaluate_record(record, targets.get(&record.priority), &responses, now))
+        .collect())
+}
+
+/// How a record's response is timed, from its status history
+enum Response {
+    /// First move out of 'pending', if there has been one
+    Tracked(Option<DateTime<Utc>>),
+    NotTracked,
+}
+
+fn evaluate_record(
+    record: &MaintenanceRecord,
+    target: Option<&SlaTarget>,
+    responses: &HashMap<i64, Response>,
+    now: DateTime<Utc>,
+) -> MaintenanceSla {
+    let resolved_at = record
+        .completed_date
+        .filter(|_| record.status == "completed");
+    let (response_tracked, responded_at) = match responses.get(&record.id) {
+        Some(Response::NotTracked) => (false, None),
+        Some(Response::Tracked(at)) => (true, at.or(resolved_at)),
+        None => (true, resolved_at),
+    };
+
+    let tracked = record.status != "cancelled";
+    let due = |hours: f64| record.created_at + Duration::seconds((hours * 3600.0) as i64);
+    let response_due = target
+        .filter(|_| tracked && response_tracked)
+        .map(|t| due(t.response_hours));
+    let resolution_due = target.filter(|_| tracked).map(|t| due(t.resolution_hours));
+
+    let response_status = window_status(record.created_at, response_due, responded_at, now);
+    let resolution_status = window_status(record.created_at, resolution_due, resolved_at, now);
+    let sla_status = if severity(response_status) >= severity(resolution_status) {
+        response_status
+    } else {
+        resolution_status
+    };
+
+    MaintenanceSla {
+        maintenance_id: record.id,
+        property_id: record.property_id,
+        contractor_id: record.contractor_id,
+        title: record.title.clone(),
+        priority: record.priority.clone(),
+        status: record.status.clone(),
+        created_at: record.created_at,
+        response_due,
+        responded_at,
+        response_status: response_status.to_string(),
+        resolution_due,
+        resolved_at,
+        resolution_status: resolution_status.to_string(),
+        sla_status: sla_status.to_string(),
+    }
+}
+
+fn window_status(
+    start: DateTime<Utc>,
+    due: Option<DateTime<Utc>>,
+    finished: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> &'static str {
+    let Some(due) = due else {
+        return "not_applicable";
+    };
+
+    if let Some(finished) = finished {
+        return if finished <= due { "met" } else { "breached" };
+    }
+
+    if now > due {
+        return "breached";
+    }
+
+    let window = (due - start).num_seconds() as f64;
+    let elapsed = (now - start).num_seconds() as f64;
+    if window > 0.0 && elapsed >= window * AT_RISK_FRACTION {
+        "at_risk"
+    } else {
+        "on_track"
+    }
+}
+
+fn severity(status: &str) -> u8 {
+    match status {
+        "breached" => 4,
+        "at_risk" => 3,
+        "on_track" => 2,
+        "met" => 1,
+        _ => 0,
+    }
+}
+
+/// Compliance over the work orders whose outcome is known: completed ones,
+/// plus open ones that have already breached. No rate when there are none.
+pub fn compliance<'a>(
+    id: Option<i64>,
+    name: String,
+    evaluations: impl Iterator<Item = &'a MaintenanceSla>,
+) -> SlaCompliance {
+    let mut met = 0;
+    let mut breached = 0;
+
+    for sla in evaluations {
+        match sla.sla_status.as_str() {
+            "met" => met += 1,
+            "breached" => breached += 1,
+            _ => {}
+        }
+    }
+
+    let evaluated = met + breached;
+    let compliance_rate = (evaluated > 0).then(|| (met as f32 / evaluated as f32) * 100.0);
+
+    SlaCompliance {
+        id,
+        name,
+        evaluated,
+        met,
+        breached,
+        compliance_rate,
+    }
+}
+
+/// Group evaluations by a key and compute compliance for each group
+pub fn compliance_by<F>(
+    evaluations: &[MaintenanceSla],
+    key: F,
+    names: &HashMap<i64, String>,
+    unassigned: &str,
+) -> Vec<SlaCompliance>
+where
+    F: Fn(&MaintenanceSla) -> Option<i64>,
+{
+    let mut groups: BTreeMap<Option<i64>, Vec<&MaintenanceSla>> = BTreeMap::new();
+    for sla in evaluations {
+        groups.entry(key(sla)).or_default().push(sla);
+    }
+
+    groups
+        .into_iter()
+        .map(|(id, slas)| {
+            let name = id
+                .and_then(|id| names.get(&id).cloned())
+                .unwrap_or_else(|| unassigned.to_string());
+            compliance(id, name, slas.into_iter())
+        })
+        .collect()
+}
@@ -0,0 +1,260 @@
+use crate::error::{AppError, Result};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageReader, RgbImage};
+use std::io::Cursor;
+
+/// Resized copies generated for every uploaded photo: name and the longest
+/// edge in pixels
+pub const VARIANTS: &[(&str, u32)] = &[("thumb", 320), ("web", 1600)];
+
+const JPEG_QUALITY: u8 = 80;
+
+const GPS_IFD_TAG: u16 = 0x8825;
+
+pub struct RenderedVariant {
+    pub name: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+/// Formats we can decode and resize
+pub fn is_processable(content_type: &str) -> bool {
+    matches!(
+        content_type,
+        "image/jpeg" | "image/png" | "image/gif" | "image/webp"
+    )
+}
+
+/// Decode the photo, honour its EXIF orientation and produce a JPEG for each
+/// entry in `VARIANTS`. Re-encoding drops all metadata from the variants.
+/// CPU-bound, so call it from `spawn_blocking`.
+pub fn render_variants(data: &[u8]) -> Result<Vec<RenderedVariant>> {
+    let unreadable =
+        |e: image::ImageError| AppError::BadRequest(format!("Unreadable image: {}", e));
+
+    let mut decoder = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()?
+        .into_decoder()
+        .map_err(unreadable)?;
+    let orientation = decoder.orientation().map_err(unreadable)?;
+    let mut img = DynamicImage::from_decoder(decoder).map_err(unreadable)?;
+    img.apply_orientation(orientation);
+    let img = flatten(&img);
+
+    VARIANTS
+        .iter()
+        .map(|&(name, max_edge)| {
+            let resized = if img.width() > max_edge || img.height() > max_edge {
+                let (width, height) = scaled(img.width(), img.height(), max_edge);
+                image::imageops::resize(&img, width, height, FilterType::Lanczos3)
+            } else {
+                img.clone()
+            };
+
+            let mut out = Vec::new();
+            JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY)
+                .encode_image(&resized)
+                .map_err(|e| AppError::Internal(format!("JPEG encoding failed: {}", e)))?;
+
+            Ok(RenderedVariant {
+                name,
+                width: resized.width(),
+                height: resized.height(),
+                data: out,
+            })
+        })
+        .collect()
+}
+
+/// Dimensions that fit within `max_edge` while keeping the aspect ratio
+fn scaled(width: u32, height: u32, max_edge: u32) -> (u32, u32) {
+    let ratio = f64::from(max_edge) / f64::from(width.max(height));
+    (
+        ((f64::from(width) * ratio).round() as u32).max(1),
+        ((f64::from(height) * ratio).round() as u32).max(1),
+    )
+}
+
+/// JPEG has no alpha channel, so composite transparent images onto white
+fn flatten(img: &DynamicImage) -> RgbImage {
+    if !img.color().has_alpha() {
+        return img.to_rgb8();
+    }
+    let rgba = img.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend =
+            |c: u8| ((u16::from(c) * u16::from(a) + 255 * (255 - u16::from(a))) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+/// Remove location data from an uploaded photo before it is stored. JPEGs keep
+/// the rest of their EXIF (orientation, camera) with the GPS block blanked;
+/// PNG and WebP drop their EXIF chunk entirely.
+pub fn strip_gps(data: &mut Vec<u8>, content_type: &str) {
+    match content_type {
+        "image/jpeg" => strip_jpeg_gps(data),
+        "image/png" => strip_png_exif(data),
+        "image/webp" => strip_webp_exif(data),
+        _ => {}
+    }
+}
+
+fn strip_jpeg_gps(data: &mut [u8]) {
+    let mut pos = 2; // after SOI
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        // Start of scan: no more metadata segments
+        if marker == 0xDA {
+            break;
+        }
+        let len = usize::from(u16::from_be_bytes([data[pos + 2], data[pos + 3]]));
+        // The length counts its own two bytes; anything shorter is corrupt
+        if len < 2 {
+            break;
+        }
+        let end = (pos + 2 + len).min(data.len());
+        let is_exif = data
+            .get(pos + 4..end)
+            .is_some_and(|payload| payload.starts_with(b"Exif\0\0"));
+        if marker == 0xE1 && is_exif {
+            blank_gps_ifd(&mut data[pos + 10..end]);
+        }
+        pos = end;
+    }
+}
+
+/// Blank the GPS IFD of a TIFF/EXIF block in place: zero its out-of-line
+/// values and entries and set its entry count to 0. Offsets elsewhere in the
+/// block stay valid because nothing moves.
+fn blank_gps_ifd(tiff: &mut [u8]) {
+    let little_endian = match tiff.get(..2) {
+        Some(b"II") => true,
+        Some(b"MM") => false,
+        _ => return,
+    };
+    let u16_at = |buf: &[u8], at: usize| -> Option<u16> {
+        let bytes = [*buf.get(at)?, *buf.get(at + 1)?];
+        Some(if little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    };
+    let u32_at = |buf: &[u8], at: usize| -> Option<usize> {
+        let bytes = [
+            *buf.get(at)?,
+            *buf.get(at + 1)?,
+            *buf.get(at + 2)?,
+            *buf.get(at + 3)?,
+        ];
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        } as usize)
+    };
+
+    let Some(ifd0) = u32_at(tiff, 4) else { return };
+    let Some(count) = u16_at(tiff, ifd0) else {
+        return;
+    };
+    let gps_ifd = (0..usize::from(count))
+        .map(|i| ifd0 + 2 + i * 12)
+        .find(|&entry| u16_at(tiff, entry) == Some(GPS_IFD_TAG))
+        .and_then(|entry| u32_at(tiff, entry + 8));
+    let Some(gps_ifd) = gps_ifd else { return };
+    let Some(gps_count) = u16_at(tiff, gps_ifd) else {
+        return;
+    };
+
+    for i in 0..usize::from(gps_count) {
+        let entry = gps_ifd + 2 + i * 12;
+        let (Some(field_type), Some(n)) = (u16_at(tiff, entry + 2), u32_at(tiff, entry + 4)) else {
+            return;
+        };
+        let unit = match field_type {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 => 8,
+            _ => 0,
+        };
+        let size = unit * n;
+        if size > 4 {
+            if let Some(offset) = u32_at(tiff, entry + 8) {
+                if let Some(value) = tiff.get_mut(offset..offset + size) {
+                    value.fill(0);
+                }
+            }
+        }
+    }
+
+    let entries_end = (gps_ifd + 2 + usize::from(gps_count) * 12 + 4).min(tiff.len());
+    tiff[gps_ifd..entries_end].fill(0);
+}
+
+fn strip_png_exif(data: &mut Vec<u8>) {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..8.min(data.len())]);
+    let mut pos = 8;
+    while pos + 12 <= data.len() {
+        let len = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+        let end = (pos + 12 + len as usize).min(data.len());
+        if &data[pos + 4..pos + 8] != b"eXIf" {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+    *data = out;
+}
+
+fn strip_webp_exif(data: &mut Vec<u8>) {
+    if data.len() < 12 || &data[..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return;
+    }
+    let mut out = data[..12].to_vec();
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let len = u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]])
+            as usize;
+        // Chunks are padded to an even length
+        let end = (pos + 8 + len + (len & 1)).min(data.len());
+        match &data[pos..pos + 4] {
+            b"EXIF" => {}
+            b"VP8X" => {
+                let start = out.len();
+                out.extend_from_slice(&data[pos..end]);
+                // Clear the "has EXIF" flag
+                if let Some(flags) = out.get_mut(start + 8) {
+                    *flags &= !0x08;
+                }
+            }
+            _ => out.extend_from_slice(&data[pos..end]),
+        }
+        pos = end;
+    }
+    let riff_size = (out.len() - 8) as u32;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    *data = out;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_jpeg_gps_skips_truncated_segment() {
+        let mut data = vec![0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x00];
+        data.extend_from_slice(b"Exif\0\0");
+        data.resize(26, 0);
+        let original = data.clone();
+
+        strip_gps(&mut data, "image/jpeg");
+
+        assert_eq!(data, original);
+    }
+}
@@ -0,0 +1,52 @@
+// Fixed-rate mortgage amortization
+use chrono::{DateTime, Datelike, Utc};
+
+/// Level monthly payment on a fully amortizing fixed-rate loan
+pub fn monthly_payment(principal: f64, annual_rate_percent: f64, term_months: i32) -> f64 {
+    if term_months <= 0 {
+        return 0.0;
+    }
+    let rate = annual_rate_percent / 100.0 / 12.0;
+    if rate <= 0.0 {
+        return principal / term_months as f64;
+    }
+
+    principal * rate / (1.0 - (1.0 + rate).powi(-term_months))
+}
+
+/// Interest portion of the payments that fall in `year` for a fully
+/// amortizing fixed-rate loan with monthly payments.
+pub fn interest_paid_in_year(
+    principal: f64,
+    annual_rate_percent: f64,
+    term_months: i32,
+    first_payment: DateTime<Utc>,
+    year: i32,
+) -> f64 {
+    let rate = annual_rate_percent / 100.0 / 12.0;
+    if rate <= 0.0 || term_months <= 0 {
+        return 0.0;
+    }
+
+    let payment = monthly_payment(principal, annual_rate_percent, term_months);
+    let first_month = first_payment.year() * 12 + first_payment.month0() as i32;
+
+    let mut balance = principal;
+    let mut interest_in_year = 0.0;
+
+    for k in 0..term_months {
+        let payment_year = (first_month + k) / 12;
+        if payment_year > year {
+            break;
+        }
+
+        let interest = balance * rate;
+        balance -= payment - interest;
+
+        if payment_year == year {
+            interest_in_year += interest;
+        }
+    }
+
+    interest_in_year
+}
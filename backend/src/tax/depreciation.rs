@@ -0,0 +1,206 @@
+// Straight-line MACRS depreciation for rental real estate
+use super::round_cents;
+use crate::error::{AppError, Result};
+use crate::models::{DepreciableAsset, DepreciationSchedule, DepreciationYear, Property};
+use chrono::{DateTime, Datelike, Utc};
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+/// Recovery period for residential rental property
+pub const RESIDENTIAL_RECOVERY_YEARS: f64 = 27.5;
+/// Recovery period for nonresidential real property
+pub const COMMERCIAL_RECOVERY_YEARS: f64 = 39.0;
+
+/// Residential property types offered by the property form, plus the
+/// generic class used by older records
+pub const RESIDENTIAL_TYPES: &[&str] = &[
+    "residential",
+    "single_family",
+    "multi_family",
+    "condo",
+    "apartment",
+    "townhouse",
+];
+
+pub fn recovery_period(property_type: &str) -> Option<f64> {
+    match property_type {
+        "commercial" => Some(COMMERCIAL_RECOVERY_YEARS),
+        t if RESIDENTIAL_TYPES.contains(&t) => Some(RESIDENTIAL_RECOVERY_YEARS),
+        _ => None,
+    }
+}
+
+/// Yearly deductions for one asset using the mid-month convention: the month
+/// the asset is placed in service counts as half a month.
+pub fn annual_amounts(
+    basis: f64,
+    recovery_years: f64,
+    placed_in_service: DateTime<Utc>,
+) -> Vec<(i32, f64)> {
+    let total_months = recovery_years * 12.0;
+    let monthly = basis / total_months;
+
+    let mut amounts = Vec::new();
+    let mut remaining_months = total_months;
+    let mut claimed = 0.0;
+    let mut year = placed_in_service.year();
+    let mut months = 12.5 - placed_in_service.month() as f64;
+
+    while remaining_months > 1e-9 {
+        let used = months.min(remaining_months);
+        remaining_months -= used;
+
+        // The final year takes whatever rounding left over so the asset is fully recovered
+        let amount = if remaining_months > 1e-9 {
+            round_cents(monthly * used)
+        } else {
+            round_cents(basis - claimed)
+        };
+        claimed += amount;
+        amounts.push((year, amount));
+
+        year += 1;
+        months = 12.0;
+    }
+
+    amounts
+}
+
+/// Build the schedule for one property: the building itself plus every
+/// completed capital improvement recorded against it.
+pub async fn property_schedule(
+    pool: &SqlitePool,
+    property: &Property,
+) -> Result<DepreciationSchedule> {
+    let recovery_years = recovery_period(&property.property_type).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Property type '{}' has no depreciation recovery period",
+            property.property_type
+        ))
+    })?;
+    let purchase_price = property.purchase_price.ok_or_else(|| {
+        AppError::BadRequest(format!("Property {} has no purchase price", property.id))
+    })?;
+    let placed_in_service = property.placed_in_service_date.ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Property {} has no placed-in-service date",
+            property.id
+        ))
+    })?;
+    let land_value = property.land_value.unwrap_or(0.0);
+
+    if land_value > purchase_price {
+        return Err(AppError::BadRequest(format!(
+            "Property {} land value exceeds its purchase price",
+            property.id
+        )));
+    }
+
+    let mut assets = vec![DepreciableAsset {
+        description: format!("Building - {}", property.title),
+        maintenance_record_id: None,
+        basis: round_cents(purchase_price - land_value),
+        recovery_period_years: recovery_years,
+        placed_in_service_date: placed_in_service,
+    }];
+
+    let improvements = sqlx::query_as::<_, (i64, String, f64, DateTime<Utc>)>(
+        r#"
+        SELECT id, title, cost, completed_date
+        FROM maintenance_records
+        WHERE property_id = ?
+          AND capital_improvement = 1
+          AND status = 'completed'
+          AND cost IS NOT NULL
+          AND completed_date IS NOT NULL
+        ORDER BY completed_date ASC
+        "#,
+    )
+    .bind(property.id)
+    .fetch_all(pool)
+    .await?;
+
+    for (id, title, cost, completed_date) in improvements {
+        assets.push(DepreciableAsset {
+            description: title,
+            maintenance_record_id: Some(id),
+            basis: round_cents(cost),
+            recovery_period_years: recovery_years,
+            placed_in_service_date: completed_date,
+        });
+    }
+
+    let mut by_year: BTreeMap<i32, (f64, f64)> = BTreeMap::new();
+    for asset in &assets {
+        for (year, amount) in annual_amounts(
+            asset.basis,
+            asset.recovery_period_years,
+            asset.placed_in_service_date,
+        ) {
+            let entry = by_year.entry(year).or_default();
+            if asset.maintenance_record_id.is_some() {
+                entry.1 += amount;
+            } else {
+                entry.0 += amount;
+            }
+        }
+    }
+
+    let years = accumulate(by_year, &assets.iter().collect::<Vec<_>>());
+
+    Ok(DepreciationSchedule {
+        property_id: property.id,
+        property_title: property.title.clone(),
+        property_type: property.property_type.clone(),
+        purchase_price,
+        land_value,
+        assets,
+        years,
+    })
+}
+
+/// Combine several property schedules into one portfolio-wide schedule
+pub fn combine_years(schedules: &[DepreciationSchedule]) -> Vec<DepreciationYear> {
+    let mut by_year: BTreeMap<i32, (f64, f64)> = BTreeMap::new();
+    for schedule in schedules {
+        for year in &schedule.years {
+            let entry = by_year.entry(year.year).or_default();
+            entry.0 += year.building;
+            entry.1 += year.improvements;
+        }
+    }
+
+    let assets: Vec<&DepreciableAsset> = schedules.iter().flat_map(|s| s.assets.iter()).collect();
+
+    accumulate(by_year, &assets)
+}
+
+/// Running totals per year. Remaining basis only counts assets already in
+/// service by that year, so a later improvement doesn't inflate earlier rows.
+fn accumulate(
+    by_year: BTreeMap<i32, (f64, f64)>,
+    assets: &[&DepreciableAsset],
+) -> Vec<DepreciationYear> {
+    let mut accumulated = 0.0;
+
+    by_year
+        .into_iter()
+        .map(|(year, (building, improvements))| {
+            let total = building + improvements;
+            accumulated += total;
+            let basis_in_service: f64 = assets
+                .iter()
+                .filter(|a| a.placed_in_service_date.year() <= year)
+                .map(|a| a.basis)
+                .sum();
+            DepreciationYear {
+                year,
+                building: round_cents(building),
+                improvements: round_cents(improvements),
+                total: round_cents(total),
+                accumulated: round_cents(accumulated),
+                remaining_basis: round_cents((basis_in_service - accumulated).max(0.0)),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,85 @@
+// Form 1099-NEC vendor payment totals
+use super::round_cents;
+use crate::error::Result;
+use crate::models::{Form1099Report, VendorPaymentSummary};
+use sqlx::{FromRow, SqlitePool};
+
+#[derive(FromRow)]
+struct VendorTotalsRow {
+    id: i64,
+    name: String,
+    entity_type: String,
+    tax_id: Option<String>,
+    address: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    zip_code: Option<String>,
+    maintenance_total: f64,
+    expense_total: f64,
+}
+
+/// Reporting threshold for nonemployee compensation. Payments made after
+/// 2025 use the raised $2,000 threshold.
+pub fn nec_threshold(year: i32) -> f64 {
+    if year >= 2026 {
+        2000.0
+    } else {
+        600.0
+    }
+}
+
+/// Vendors paid at least the threshold in `year`. Corporations are generally
+/// exempt from 1099-NEC reporting and are left out.
+pub async fn build_report(pool: &SqlitePool, year: i32) -> Result<Form1099Report> {
+    let threshold = nec_threshold(year);
+
+    let rows = sqlx::query_as::<_, VendorTotalsRow>(
+        r#"
+        SELECT
+            c.id, c.name, c.entity_type, c.tax_id, c.address, c.city, c.state, c.zip_code,
+            COALESCE((
+                SELECT SUM(m.cost) FROM maintenance_records m
+                WHERE m.contractor_id = c.id
+                  AND m.status = 'completed'
+                  AND strftime('%Y', m.completed_date) = ?
+            ), 0.0) AS maintenance_total,
+            COALESCE((
+                SELECT SUM(e.amount) FROM expenses e
+                WHERE e.contractor_id = c.id
+                  AND strftime('%Y', e.expense_date) = ?
+            ), 0.0) AS expense_total
+        FROM contractors c
+        WHERE c.entity_type != 'corporation'
+        ORDER BY c.name ASC
+        "#,
+    )
+    .bind(year.to_string())
+    .bind(year.to_string())
+    .fetch_all(pool)
+    .await?;
+
+    let vendors = rows
+        .into_iter()
+        .map(|row| VendorPaymentSummary {
+            contractor_id: row.id,
+            missing_tax_id: row.tax_id.as_deref().is_none_or(|t| t.trim().is_empty()),
+            name: row.name,
+            entity_type: row.entity_type,
+            tax_id: row.tax_id,
+            address: row.address,
+            city: row.city,
+            state: row.state,
+            zip_code: row.zip_code,
+            maintenance_total: round_cents(row.maintenance_total),
+            expense_total: round_cents(row.expense_total),
+            total_paid: round_cents(row.maintenance_total + row.expense_total),
+        })
+        .filter(|v| v.total_paid >= threshold)
+        .collect();
+
+    Ok(Form1099Report {
+        tax_year: year,
+        threshold,
+        vendors,
+    })
+}
@@ -0,0 +1,13 @@
+// Tax reporting helpers
+// Calculations shared by the tax report endpoints live here so routes stay thin
+
+pub mod amortization;
+pub mod depreciation;
+pub mod form_1099;
+pub mod schedule_e;
+
+/// Round a dollar amount to whole cents (an empty f64 sum is -0.0, so
+/// normalise that to 0.0 as well)
+pub fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0 + 0.0
+}
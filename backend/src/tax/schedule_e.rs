@@ -0,0 +1,221 @@
+// IRS Schedule E (Supplemental Income and Loss) totals per property
+use super::{amortization, depreciation, round_cents};
+use crate::error::{AppError, Result};
+use crate::models::{Loan, Property, ScheduleELines, ScheduleEProperty, ScheduleEReport};
+use sqlx::SqlitePool;
+
+impl ScheduleELines {
+    /// Line-ordered (column name, amount) pairs, as they appear on the form
+    pub fn columns(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("rents_received", self.rents_received),
+            ("advertising", self.advertising),
+            ("auto_travel", self.auto_travel),
+            ("cleaning_maintenance", self.cleaning_maintenance),
+            ("commissions", self.commissions),
+            ("insurance", self.insurance),
+            ("legal_professional", self.legal_professional),
+            ("management_fees", self.management_fees),
+            ("mortgage_interest", self.mortgage_interest),
+            ("other_interest", self.other_interest),
+            ("repairs", self.repairs),
+            ("supplies", self.supplies),
+            ("taxes", self.taxes),
+            ("utilities", self.utilities),
+            ("depreciation", self.depreciation),
+            ("other", self.other),
+            ("total_expenses", self.total_expenses),
+            ("net_income", self.net_income),
+        ]
+    }
+
+    fn add_expense(&mut self, category: &str, amount: f64) {
+        let line = match category {
+            "advertising" => &mut self.advertising,
+            "auto_travel" => &mut self.auto_travel,
+            "cleaning_maintenance" => &mut self.cleaning_maintenance,
+            "commissions" => &mut self.commissions,
+            "insurance" => &mut self.insurance,
+            "legal_professional" => &mut self.legal_professional,
+            "management_fees" => &mut self.management_fees,
+            "mortgage_interest" => &mut self.mortgage_interest,
+            "other_interest" => &mut self.other_interest,
+            "repairs" => &mut self.repairs,
+            "supplies" => &mut self.supplies,
+            "taxes" => &mut self.taxes,
+            "utilities" => &mut self.utilities,
+            _ => &mut self.other,
+        };
+        *line += amount;
+    }
+
+    /// Round every line to cents and fill in the total and net lines
+    fn finish(&mut self) {
+        let expense_lines = [
+            &mut self.advertising,
+            &mut self.auto_travel,
+            &mut self.cleaning_maintenance,
+            &mut self.commissions,
+            &mut self.insurance,
+            &mut self.legal_professional,
+            &mut self.management_fees,
+            &mut self.mortgage_interest,
+            &mut self.other_interest,
+            &mut self.repairs,
+            &mut self.supplies,
+            &mut self.taxes,
+            &mut self.utilities,
+            &mut self.depreciation,
+            &mut self.other,
+        ];
+
+        let mut total = 0.0;
+        for line in expense_lines {
+            *line = round_cents(*line);
+            total += *line;
+        }
+
+        self.rents_received = round_cents(self.rents_received);
+        self.total_expenses = round_cents(total);
+        self.net_income = round_cents(self.rents_received - self.total_expenses);
+    }
+
+    fn add(&mut self, other: &ScheduleELines) {
+        self.rents_received += other.rents_received;
+        self.advertising += other.advertising;
+        self.auto_travel += other.auto_travel;
+        self.cleaning_maintenance += other.cleaning_maintenance;
+        self.commissions += other.commissions;
+        self.insurance += other.insurance;
+        self.legal_professional += other.legal_professional;
+        self.management_fees += other.management_fees;
+        self.mortgage_interest += other.mortgage_interest;
+        self.other_interest += other.other_interest;
+        self.repairs += other.repairs;
+        self.supplies += other.supplies;
+        self.taxes += other.taxes;
+        self.utilities += other.utilities;
+        self.depreciation += other.depreciation;
+        self.other += other.other;
+    }
+}
+
+pub async fn build_report(pool: &SqlitePool, year: i32) -> Result<ScheduleEReport> {
+    let properties = sqlx::query_as::<_, Property>("SELECT * FROM properties ORDER BY id ASC")
+        .fetch_all(pool)
+        .await?;
+
+    let mut report_properties = Vec::new();
+    let mut totals = ScheduleELines::default();
+
+    for property in properties {
+        let (lines, notes) = property_lines(pool, &property, year).await?;
+        totals.add(&lines);
+
+        report_properties.push(ScheduleEProperty {
+            property_id: property.id,
+            property_title: property.title,
+            address: format!(
+                "{}, {}, {} {}",
+                property.address, property.city, property.state, property.zip_code
+            ),
+            property_type: property.property_type,
+            lines,
+            notes,
+        });
+    }
+
+    totals.finish();
+
+    Ok(ScheduleEReport {
+        tax_year: year,
+        properties: report_properties,
+        totals,
+    })
+}
+
+async fn property_lines(
+    pool: &SqlitePool,
+    property: &Property,
+    year: i32,
+) -> Result<(ScheduleELines, Vec<String>)> {
+    let year_str = year.to_string();
+    let mut lines = ScheduleELines::default();
+    let mut notes = Vec::new();
+
+    let rents: Option<f64> = sqlx::query_scalar(
+        r#"
+        SELECT SUM(amount) FROM rent_payments
+        WHERE property_id = ? AND strftime('%Y', payment_date) = ?
+        "#,
+    )
+    .bind(property.id)
+    .bind(&year_str)
+    .fetch_one(pool)
+    .await?;
+    lines.rents_received = rents.unwrap_or(0.0);
+
+    let expenses = sqlx::query_as::<_, (String, f64)>(
+        r#"
+        SELECT category, SUM(amount) FROM expenses
+        WHERE property_id = ? AND strftime('%Y', expense_date) = ?
+        GROUP BY category
+        "#,
+    )
+    .bind(property.id)
+    .bind(&year_str)
+    .fetch_all(pool)
+    .await?;
+    for (category, amount) in expenses {
+        lines.add_expense(&category, amount);
+    }
+
+    // Capital improvements are excluded here; they reach the form through depreciation
+    let repairs: Option<f64> = sqlx::query_scalar(
+        r#"
+        SELECT SUM(cost) FROM maintenance_records
+        WHERE property_id = ?
+          AND status = 'completed'
+          AND capital_improvement = 0
+          AND strftime('%Y', completed_date) = ?
+        "#,
+    )
+    .bind(property.id)
+    .bind(&year_str)
+    .fetch_one(pool)
+    .await?;
+    lines.repairs += repairs.unwrap_or(0.0);
+
+    let loans = sqlx::query_as::<_, Loan>("SELECT * FROM loans WHERE property_id = ?")
+        .bind(property.id)
+        .fetch_all(pool)
+        .await?;
+    for loan in loans {
+        lines.mortgage_interest += amortization::interest_paid_in_year(
+            loan.original_principal,
+            loan.interest_rate,
+            loan.term_months,
+            loan.first_payment_date,
+            year,
+        );
+    }
+
+    match depreciation::property_schedule(pool, property).await {
+        Ok(schedule) => {
+            lines.depreciation = schedule
+                .years
+                .iter()
+                .find(|y| y.year == year)
+                .map(|y| y.total)
+                .unwrap_or(0.0);
+        }
+        Err(AppError::BadRequest(reason)) => {
+            notes.push(format!("Depreciation not included: {}", reason));
+        }
+        Err(e) => return Err(e),
+    }
+
+    lines.finish();
+
+    Ok((lines, notes))
+}